@@ -0,0 +1,42 @@
+//!build.rs
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// FNV-1a hash of paths and contents of all source files; cached answers of other builds are
+// dropped, since solutions may have changed without a new version of crate
+fn main() {
+    println!("cargo:rerun-if-changed=src");
+    let mut files = Vec::new();
+    collect_files(Path::new("src"), &mut files);
+    files.sort();
+    const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+    let hash = files
+        .iter()
+        .flat_map(|file| {
+            let content = fs::read(file).unwrap_or_default();
+            file.to_string_lossy()
+                .into_owned()
+                .into_bytes()
+                .into_iter()
+                .chain(content)
+        })
+        .fold(FNV_OFFSET_BASIS, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(FNV_PRIME)
+        });
+    println!("cargo:rustc-env=AOC_BUILD_FINGERPRINT={:016x}", hash);
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for path in entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+        if path.is_dir() {
+            collect_files(&path, files);
+        } else {
+            files.push(path);
+        }
+    }
+}
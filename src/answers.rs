@@ -0,0 +1,149 @@
+//!answers.rs
+
+use anyhow::{anyhow, Context, Result};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// file of expected answers in input directory
+pub const ANSWERS_FILE: &str = "answers.txt";
+
+/// path of answers file in input directory
+pub fn answers_path(input_dir: &Path) -> PathBuf {
+    input_dir.join(ANSWERS_FILE)
+}
+
+/// fingerprint of puzzle input: FNV-1a 64 bit hash of input without trailing whitespace
+pub fn fingerprint(input: &str) -> String {
+    hash_bytes(input.trim_end().as_bytes())
+}
+
+/// FNV-1a 64 bit hash of bytes as hex string
+pub fn hash_bytes(bytes: &[u8]) -> String {
+    const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+    let hash = bytes.iter().fold(FNV_OFFSET_BASIS, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(FNV_PRIME)
+    });
+    format!("{:016x}", hash)
+}
+
+/// result of comparing an answer with expected answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verification {
+    Match,
+    Mismatch { expected: String },
+    Unknown,
+}
+
+impl Display for Verification {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verification::Match => write!(f, "match"),
+            Verification::Mismatch { expected } => write!(f, "mismatch (expected {})", expected),
+            Verification::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+/// expected answers keyed by day, part and input fingerprint.
+/// Each line of answers file contains `day part fingerprint answer`; lines starting with `#` are comments.
+#[derive(Debug, Default)]
+pub struct Answers {
+    expected: HashMap<(u8, u8, String), String>,
+}
+
+impl FromStr for Answers {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut answers = Answers::default();
+        for (index, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let bad_line = || anyhow!("bad answer in line {}: `{}`", index + 1, line);
+            let mut fields = line.split_ascii_whitespace();
+            let day = fields.next().ok_or_else(bad_line)?.parse::<u8>()?;
+            let part = fields.next().ok_or_else(bad_line)?.parse::<u8>()?;
+            let fingerprint = fields.next().ok_or_else(bad_line)?;
+            let answer = fields.next().ok_or_else(bad_line)?;
+            if fields.next().is_some() {
+                return Err(bad_line());
+            }
+            answers.insert(day, part, fingerprint, answer);
+        }
+        Ok(answers)
+    }
+}
+
+impl Answers {
+    /// load answers file; a missing file results in no known answers
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Answers::default());
+        }
+        std::fs::read_to_string(path)
+            .with_context(|| format!("cannot read answers file `{}`", path.display()))?
+            .parse()
+            .with_context(|| format!("bad answers file `{}`", path.display()))
+    }
+    pub fn insert(&mut self, day: u8, part: u8, fingerprint: &str, answer: &str) {
+        self.expected
+            .insert((day, part, fingerprint.to_string()), answer.to_string());
+    }
+    pub fn get(&self, day: u8, part: u8, fingerprint: &str) -> Option<&str> {
+        self.expected
+            .get(&(day, part, fingerprint.to_string()))
+            .map(|a| a.as_str())
+    }
+    pub fn verify(&self, day: u8, part: u8, fingerprint: &str, answer: &str) -> Verification {
+        match self.get(day, part, fingerprint) {
+            Some(expected) if expected == answer => Verification::Match,
+            Some(expected) => Verification::Mismatch {
+                expected: expected.to_string(),
+            },
+            None => Verification::Unknown,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_verify() {
+        let input = "# day part fingerprint answer\n\
+                     1 1 0123456789abcdef 42\n\
+                     \n\
+                     1 2 0123456789abcdef 4711\n";
+        let answers = Answers::from_str(input).unwrap();
+        assert_eq!(
+            answers.verify(1, 1, "0123456789abcdef", "42"),
+            Verification::Match
+        );
+        assert_eq!(
+            answers.verify(1, 2, "0123456789abcdef", "42"),
+            Verification::Mismatch {
+                expected: "4711".into()
+            }
+        );
+        assert_eq!(
+            answers.verify(1, 1, "fedcba9876543210", "42"),
+            Verification::Unknown
+        );
+        assert!(Answers::from_str("1 1 0123456789abcdef").is_err());
+    }
+
+    #[test]
+    fn test_fingerprint() {
+        // FNV-1a test vectors
+        assert_eq!(fingerprint(""), "cbf29ce484222325");
+        assert_eq!(fingerprint("a"), "af63dc4c8601ec8c");
+        assert_eq!(fingerprint("a\n"), fingerprint("a"));
+    }
+}
@@ -0,0 +1,268 @@
+//!bench.rs
+
+use crate::cli::BenchArgs;
+use crate::input;
+use crate::solution::{Parts, Timings};
+use crate::years;
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::Duration;
+
+/// min, median and max time of one step of a day in milliseconds
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchResult {
+    pub day: u8,
+    /// `parse`, `part 1` or `part 2`
+    pub step: String,
+    pub min_ms: f64,
+    pub median_ms: f64,
+    pub max_ms: f64,
+}
+
+impl BenchResult {
+    fn new(day: u8, step: &str, times: &[Duration]) -> Self {
+        let mut times_ms: Vec<f64> = times.iter().map(|t| t.as_secs_f64() * 1_000.0).collect();
+        times_ms.sort_by(|a, b| a.total_cmp(b));
+        let len = times_ms.len();
+        let median_ms = if len.is_multiple_of(2) {
+            (times_ms[len / 2 - 1] + times_ms[len / 2]) / 2.0
+        } else {
+            times_ms[len / 2]
+        };
+        BenchResult {
+            day,
+            step: step.into(),
+            min_ms: times_ms[0],
+            median_ms,
+            max_ms: times_ms[len - 1],
+        }
+    }
+    fn from_timings(day: u8, timings: &Timings) -> Vec<Self> {
+        let mut results = vec![BenchResult::new(day, "parse", &timings.parse)];
+        for (part, times) in timings.parts.iter() {
+            results.push(BenchResult::new(day, &format!("part {}", part), times));
+        }
+        results
+    }
+    /// relative change of median compared to baseline, e.g. 0.1 for 10 % slower
+    pub fn change(&self, baseline: &BenchResult) -> f64 {
+        if baseline.median_ms <= 0.0 {
+            return 0.0;
+        }
+        (self.median_ms - baseline.median_ms) / baseline.median_ms
+    }
+}
+
+/// saved bench results to compare later runs with
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Baseline {
+    pub results: Vec<BenchResult>,
+}
+
+impl Baseline {
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let baseline = std::fs::read_to_string(path)
+            .with_context(|| format!("cannot read baseline `{}`", path.display()))?;
+        let baseline = serde_json::from_str(&baseline)
+            .with_context(|| format!("bad baseline `{}`", path.display()))?;
+        Ok(Some(baseline))
+    }
+    pub fn save(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)? + "\n")
+            .with_context(|| format!("cannot write baseline `{}`", path.display()))
+    }
+    /// replace results of same day and step with new results and keep results of other days
+    pub fn merge(&mut self, results: Vec<BenchResult>) {
+        for result in results {
+            match self
+                .results
+                .iter_mut()
+                .find(|r| r.day == result.day && r.step == result.step)
+            {
+                Some(old) => *old = result,
+                None => self.results.push(result),
+            }
+        }
+        self.results
+            .sort_by(|a, b| (a.day, &a.step).cmp(&(b.day, &b.step)));
+    }
+    pub fn get(&self, day: u8, step: &str) -> Option<&BenchResult> {
+        self.results.iter().find(|r| r.day == day && r.step == step)
+    }
+    /// results, which are slower than baseline by more than threshold, e.g. 0.1 for 10 %
+    pub fn regressions<'a>(
+        &self,
+        results: &'a [BenchResult],
+        threshold: f64,
+    ) -> Vec<&'a BenchResult> {
+        results
+            .iter()
+            .filter(|r| {
+                self.get(r.day, &r.step)
+                    .is_some_and(|b| r.change(b) > threshold)
+            })
+            .collect()
+    }
+}
+
+/// run parse, part 1 and part 2 of selected days several times and compare with baseline
+pub fn run_bench(args: &BenchArgs) -> Result<()> {
+    if args.iterations == 0 {
+        return Err(anyhow!("--iterations must be at least 1"));
+    }
+    let selected_days = args.select.selected_days();
+    let input_file = args.select.input.as_deref();
+    if input_file.is_some() && selected_days.len() != 1 {
+        return Err(anyhow!("--input requires exactly one selected day"));
+    }
+    let parts = Parts::from(args.select.part);
+    let input_dir = args.select.input_dir()?;
+    let baseline = Baseline::load(&args.baseline)?;
+    let threshold = args.threshold / 100.0;
+    let mut results: Vec<BenchResult> = Vec::new();
+    for day in years::days_of_year(args.select.year)?
+        .iter()
+        .filter(|d| selected_days.contains(&d.day()))
+    {
+        let (selected_parts, skipped_parts) = day.select_parts(parts, args.select.include_slow);
+        for part in skipped_parts {
+            println!(
+                "day {:02} part {} skipped because of long run time (use --include-slow)",
+                day.day(),
+                part
+            );
+        }
+        if selected_parts.is_empty() {
+            continue;
+        }
+        let input = input::load_input(day.day(), &input_dir, input_file)?;
+        let timings = day
+            .bench(&input, &selected_parts, args.iterations)
+            .with_context(|| format!("day {:02}", day.day()))?;
+        for result in BenchResult::from_timings(day.day(), &timings) {
+            let mut line = format!(
+                "day {:02} {:<6}  min {:>10.3} ms  median {:>10.3} ms  max {:>10.3} ms",
+                result.day, result.step, result.min_ms, result.median_ms, result.max_ms
+            );
+            if let Some(base) = baseline
+                .as_ref()
+                .and_then(|b| b.get(result.day, &result.step))
+            {
+                let change = result.change(base);
+                line += &format!(
+                    "  baseline {:>10.3} ms ({:+.1} %)",
+                    base.median_ms,
+                    change * 100.0
+                );
+                if change > threshold {
+                    line += "  REGRESSION";
+                }
+            }
+            println!("{}", line);
+            results.push(result);
+        }
+    }
+    let regressions = baseline
+        .as_ref()
+        .map(|b| b.regressions(&results, threshold).len())
+        .unwrap_or_default();
+    if args.save_baseline || baseline.is_none() {
+        // baseline of days, which are not benched in this run, is kept
+        let mut new_baseline = baseline.unwrap_or_default();
+        new_baseline.merge(results);
+        new_baseline.save(&args.baseline)?;
+        println!("saved baseline to `{}`", args.baseline.display());
+    }
+    if regressions > 0 {
+        return Err(anyhow!(
+            "{} step(s) are more than {} % slower than baseline",
+            regressions,
+            args.threshold
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::cli::{Cli, Command};
+    use clap::Parser;
+
+    #[test]
+    fn test_bench_result() {
+        let times: Vec<Duration> = [4, 1, 3, 2]
+            .into_iter()
+            .map(Duration::from_millis)
+            .collect();
+        let result = BenchResult::new(5, "parse", &times);
+        assert_eq!(result.min_ms, 1.0);
+        assert_eq!(result.median_ms, 2.5);
+        assert_eq!(result.max_ms, 4.0);
+        let times: Vec<Duration> = [7, 5, 6].into_iter().map(Duration::from_millis).collect();
+        assert_eq!(BenchResult::new(5, "part 1", &times).median_ms, 6.0);
+    }
+
+    #[test]
+    fn test_regressions() {
+        let result = |step: &str, median_ms: f64| BenchResult {
+            day: 1,
+            step: step.into(),
+            min_ms: median_ms,
+            median_ms,
+            max_ms: median_ms,
+        };
+        let baseline = Baseline {
+            results: vec![result("parse", 1.0), result("part 1", 10.0)],
+        };
+        let results = vec![
+            result("parse", 1.05),
+            result("part 1", 12.0),
+            result("part 2", 100.0),
+        ];
+        let regressions = baseline.regressions(&results, 0.1);
+        assert_eq!(regressions, vec![&results[1]]);
+    }
+
+    #[test]
+    fn test_merge() {
+        let result = |day: u8, step: &str, median_ms: f64| BenchResult {
+            day,
+            step: step.into(),
+            min_ms: median_ms,
+            median_ms,
+            max_ms: median_ms,
+        };
+        let mut baseline = Baseline {
+            results: vec![result(1, "parse", 1.0), result(2, "parse", 2.0)],
+        };
+        baseline.merge(vec![result(2, "parse", 3.0), result(1, "part 1", 4.0)]);
+        assert_eq!(
+            baseline.results,
+            vec![
+                result(1, "parse", 1.0),
+                result(1, "part 1", 4.0),
+                result(2, "parse", 3.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bench_args() {
+        let cli = Cli::parse_from(["aoc", "bench", "21", "-n", "3", "--save-baseline"]);
+        match cli.command {
+            Some(Command::Bench(bench)) => {
+                assert_eq!(bench.select.selected_days(), vec![21]);
+                assert_eq!(bench.iterations, 3);
+                assert!(bench.save_baseline);
+                assert_eq!(bench.baseline, Path::new("bench_baseline.json"));
+            }
+            _ => panic!("expected bench command"),
+        }
+    }
+}
@@ -0,0 +1,196 @@
+//!cache.rs
+
+use crate::answers;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// file of cached answers, relative to working directory
+pub const CACHE_FILE: &str = "target/result_cache.json";
+/// answers of other versions or builds of crate are dropped, since solutions may have changed.
+/// Fingerprint of build is a hash of all source files, see build.rs.
+const VERSION: &str = concat!(
+    env!("CARGO_PKG_VERSION"),
+    "+",
+    env!("AOC_BUILD_FINGERPRINT")
+);
+
+/// path of cache file
+pub fn cache_path() -> PathBuf {
+    PathBuf::from(CACHE_FILE)
+}
+
+/// key of cached answers of an input: hash of exact input together with version and build of crate.
+/// Trailing whitespace is part of the key, since it may change answers (e.g. day 15).
+pub fn cache_key(input: &str) -> String {
+    answers::hash_bytes(format!("{}\n{}", VERSION, input).as_bytes())
+}
+
+/// answer of a part with its times from the run, which solved it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CachedAnswer {
+    pub version: String,
+    pub day: u8,
+    pub part: u8,
+    pub key: String,
+    pub answer: String,
+    pub parse_time_ms: f64,
+    pub solve_time_ms: f64,
+}
+
+impl CachedAnswer {
+    pub fn parse_time(&self) -> Duration {
+        Duration::from_secs_f64(self.parse_time_ms / 1_000.0)
+    }
+    pub fn solve_time(&self) -> Duration {
+        Duration::from_secs_f64(self.solve_time_ms / 1_000.0)
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheFile {
+    answers: Vec<CachedAnswer>,
+}
+
+/// answers of parts keyed by day, part and cache key of input. Days are solved in parallel,
+/// therefore answers are behind a mutex.
+#[derive(Debug, Default)]
+pub struct ResultCache {
+    answers: Mutex<HashMap<(u8, u8, String), CachedAnswer>>,
+    changed: AtomicBool,
+}
+
+impl ResultCache {
+    /// load cache file; a missing file results in an empty cache
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(ResultCache::default());
+        }
+        let cache = std::fs::read_to_string(path)
+            .with_context(|| format!("cannot read result cache `{}`", path.display()))?;
+        let cache: CacheFile = serde_json::from_str(&cache)
+            .with_context(|| format!("bad result cache `{}`", path.display()))?;
+        let answers = cache
+            .answers
+            .into_iter()
+            .filter(|a| a.version == VERSION)
+            .map(|a| ((a.day, a.part, a.key.clone()), a))
+            .collect();
+        Ok(ResultCache {
+            answers: Mutex::new(answers),
+            changed: AtomicBool::new(false),
+        })
+    }
+    /// write cache file, if answers have been added since loading it
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if !self.changed.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+        let mut answers: Vec<CachedAnswer> =
+            self.answers.lock().unwrap().values().cloned().collect();
+        answers.sort_by(|a, b| (a.day, a.part, &a.key).cmp(&(b.day, b.part, &b.key)));
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("cannot create directory `{}`", dir.display()))?;
+        }
+        std::fs::write(
+            path,
+            serde_json::to_string_pretty(&CacheFile { answers })? + "\n",
+        )
+        .with_context(|| format!("cannot write result cache `{}`", path.display()))
+    }
+    pub fn get(&self, day: u8, part: u8, key: &str) -> Option<CachedAnswer> {
+        self.answers
+            .lock()
+            .unwrap()
+            .get(&(day, part, key.to_string()))
+            .cloned()
+    }
+    pub fn insert(
+        &self,
+        day: u8,
+        part: u8,
+        key: &str,
+        answer: &str,
+        parse_time: Duration,
+        solve_time: Duration,
+    ) {
+        let cached = CachedAnswer {
+            version: VERSION.to_string(),
+            day,
+            part,
+            key: key.to_string(),
+            answer: answer.to_string(),
+            parse_time_ms: parse_time.as_secs_f64() * 1_000.0,
+            solve_time_ms: solve_time.as_secs_f64() * 1_000.0,
+        };
+        self.answers
+            .lock()
+            .unwrap()
+            .insert((day, part, key.to_string()), cached);
+        self.changed.store(true, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::cli::Cli;
+    use clap::Parser;
+
+    #[test]
+    fn test_result_cache() {
+        let path = std::env::temp_dir()
+            .join(format!("aoc_cache_{}", std::process::id()))
+            .join("result_cache.json");
+        let key = cache_key("1 2 3");
+        assert_ne!(key, answers::fingerprint("1 2 3"));
+        assert_ne!(key, cache_key("1 2 3\n"));
+        assert_eq!(key, cache_key("1 2 3"));
+
+        let cache = ResultCache::load(&path).unwrap();
+        assert_eq!(cache.get(9, 1, &key), None);
+        cache.insert(
+            9,
+            1,
+            &key,
+            "42",
+            Duration::from_millis(1),
+            Duration::from_millis(250),
+        );
+        cache.save(&path).unwrap();
+
+        let cache = ResultCache::load(&path).unwrap();
+        let cached = cache.get(9, 1, &key).unwrap();
+        assert_eq!(cached.answer, "42");
+        assert_eq!(cached.solve_time(), Duration::from_millis(250));
+        assert_eq!(cache.get(9, 2, &key), None);
+
+        // answers of other versions are dropped
+        let old = std::fs::read_to_string(&path)
+            .unwrap()
+            .replace(VERSION, "0.0.0-old");
+        std::fs::write(&path, old).unwrap();
+        let cache = ResultCache::load(&path).unwrap();
+        assert_eq!(cache.get(9, 1, &key), None);
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_version_contains_build() {
+        let (version, build) = VERSION.split_once('+').unwrap();
+        assert_eq!(version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(build.len(), 16);
+    }
+
+    #[test]
+    fn test_no_cache_flag() {
+        assert!(!Cli::parse_from(["aoc", "17"]).run.no_cache);
+        assert!(Cli::parse_from(["aoc", "17", "--no-cache"]).run.no_cache);
+    }
+}
@@ -0,0 +1,346 @@
+//!cli.rs
+
+use crate::input;
+use crate::report::Format;
+use crate::years::DEFAULT_YEAR;
+use anyhow::{anyhow, Result};
+use clap::{Args, Parser, Subcommand};
+use std::{ops::RangeInclusive, path::PathBuf, str::FromStr, time::Duration};
+
+/// Solutions of Advent of Code 2023
+#[derive(Parser)]
+#[command(version, about, args_conflicts_with_subcommands = true)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+    #[command(flatten)]
+    pub run: RunArgs,
+    /// log progress to stderr; `-v` logs info, `-vv` debug and `-vvv` trace events
+    #[arg(short, long, action = clap::ArgAction::Count, global = true)]
+    pub verbose: u8,
+}
+
+/// arguments of running days
+#[derive(Args)]
+pub struct RunArgs {
+    #[command(flatten)]
+    pub select: SelectArgs,
+    /// output format of report
+    #[arg(short, long, value_enum, default_value_t)]
+    pub format: Format,
+    /// time budget per day in seconds; parts, which are not solved in time, are reported as timed out
+    #[arg(long, value_name = "SECONDS", value_parser = parse_time_budget)]
+    pub time_budget: Option<Duration>,
+    /// number of days to solve in parallel; results are still printed in order of days
+    #[arg(short, long, value_name = "N", default_value_t = 1, value_parser = clap::value_parser!(u16).range(1..))]
+    pub jobs: u16,
+    /// write image of grid of selected day to file after solving; format is `.ppm` or `.svg`.
+    /// Requires a single selected day.
+    #[arg(long, value_name = "FILE")]
+    pub render: Option<PathBuf>,
+    /// solve all selected parts, even if their answers are cached; cache is updated with new answers
+    #[arg(long)]
+    pub no_cache: bool,
+    /// count allocations and peak of allocated bytes per day and part; cached answers are not used
+    #[arg(long)]
+    pub profile_mem: bool,
+}
+
+fn parse_time_budget(s: &str) -> Result<Duration, String> {
+    match s.parse::<f64>() {
+        Ok(seconds) if seconds > 0.0 && seconds.is_finite() => Ok(Duration::from_secs_f64(seconds)),
+        _ => Err(format!("`{}` is not a positive number of seconds", s)),
+    }
+}
+
+/// selection of days, parts and input
+#[derive(Args)]
+pub struct SelectArgs {
+    /// days to run: single days, comma separated lists or ranges, e.g. `5`, `1,3,7` or `10-15`.
+    /// Runs all days if omitted.
+    #[arg(value_delimiter = ',')]
+    pub days: Vec<DayRange>,
+    /// run only part 1 or part 2 of selected days
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    pub part: Option<u8>,
+    /// read puzzle input from file instead of input directory; requires a single selected day.
+    /// Input directory is `assets` or value of environment variable `AOC_INPUT_DIR`.
+    #[arg(short, long, value_name = "FILE")]
+    pub input: Option<PathBuf>,
+    /// read inputs and answers of profile from its sub directory of input directory, e.g. `assets/alice`
+    #[arg(long, value_name = "NAME", value_parser = input::parse_profile, conflicts_with = "input")]
+    pub profile: Option<String>,
+    /// also solve parts with long run time
+    #[arg(long)]
+    pub include_slow: bool,
+    /// year of days; inputs of other years than 2023 are in sub directory `YYYY` of input directory
+    #[arg(long, default_value_t = DEFAULT_YEAR, value_parser = clap::value_parser!(u16).range(2015..))]
+    pub year: u16,
+}
+
+/// arguments of solving days for all profiles
+#[derive(Args)]
+pub struct MatrixArgs {
+    #[command(flatten)]
+    pub select: SelectArgs,
+    /// output format of matrix
+    #[arg(short, long, value_enum, default_value_t)]
+    pub format: Format,
+    /// time budget per day and profile in seconds; parts, which are not solved in time, are reported as timed out
+    #[arg(long, value_name = "SECONDS", value_parser = parse_time_budget)]
+    pub time_budget: Option<Duration>,
+    /// number of days to solve in parallel
+    #[arg(short, long, value_name = "N", default_value_t = 1, value_parser = clap::value_parser!(u16).range(1..))]
+    pub jobs: u16,
+    /// solve all selected parts, even if their answers are cached; cache is updated with new answers
+    #[arg(long)]
+    pub no_cache: bool,
+}
+
+/// arguments of benchmarking days
+#[derive(Args)]
+pub struct BenchArgs {
+    #[command(flatten)]
+    pub select: SelectArgs,
+    /// number of runs of parse, part 1 and part 2 of each day
+    #[arg(short = 'n', long, default_value_t = 10)]
+    pub iterations: usize,
+    /// baseline file to compare with; created if it does not exist
+    #[arg(
+        short,
+        long,
+        value_name = "FILE",
+        default_value = "bench_baseline.json"
+    )]
+    pub baseline: PathBuf,
+    /// overwrite baseline with results of this run
+    #[arg(short, long)]
+    pub save_baseline: bool,
+    /// median time in percent above baseline, which is flagged as regression
+    #[arg(short, long, default_value_t = 10.0)]
+    pub threshold: f64,
+}
+
+/// arguments of generating random puzzle inputs
+#[derive(Args)]
+pub struct GenArgs {
+    /// day to generate puzzle input for
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+    pub day: u8,
+    /// size of input; its meaning depends on day, e.g. number of lines or width of grid
+    #[arg(short, long)]
+    pub size: Option<usize>,
+    /// seed of random generator to replay an input; a random seed is used, if omitted
+    #[arg(long)]
+    pub seed: Option<u64>,
+    /// write input to file instead of stdout
+    #[arg(short, long, value_name = "FILE")]
+    pub output: Option<PathBuf>,
+    /// year of day
+    #[arg(long, default_value_t = DEFAULT_YEAR, value_parser = clap::value_parser!(u16).range(2015..))]
+    pub year: u16,
+}
+
+/// arguments of listing days
+#[derive(Args)]
+pub struct ListArgs {
+    /// year of days
+    #[arg(long, default_value_t = DEFAULT_YEAR, value_parser = clap::value_parser!(u16).range(2015..))]
+    pub year: u16,
+}
+
+/// arguments of adding a new day
+#[derive(Args)]
+pub struct NewArgs {
+    /// day to add
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+    pub day: u8,
+    /// year of day; days of other years than 2023 are added to module `year_YYYY`
+    #[arg(long, default_value_t = DEFAULT_YEAR, value_parser = clap::value_parser!(u16).range(2015..))]
+    pub year: u16,
+}
+
+/// arguments of stepping through simulation of a day
+#[derive(Args)]
+pub struct StepArgs {
+    /// day to simulate
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+    pub day: u8,
+    /// read puzzle input from file instead of input directory
+    #[arg(short, long, value_name = "FILE")]
+    pub input: Option<PathBuf>,
+    /// run simulation with given steps per second instead of waiting for commands
+    #[arg(short, long, value_name = "STEPS_PER_SECOND", value_parser = parse_speed)]
+    pub speed: Option<f64>,
+    /// year of day
+    #[arg(long, default_value_t = DEFAULT_YEAR, value_parser = clap::value_parser!(u16).range(2015..))]
+    pub year: u16,
+}
+
+fn parse_speed(s: &str) -> Result<f64, String> {
+    match s.parse::<f64>() {
+        Ok(speed) if speed > 0.0 && speed.is_finite() => Ok(speed),
+        _ => Err(format!(
+            "`{}` is not a positive number of steps per second",
+            s
+        )),
+    }
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// list available days and which parts are slow
+    List(ListArgs),
+    /// solve selected days and compare answers with expected answers in `answers.txt` of input directory
+    Verify(RunArgs),
+    /// solve selected days with inputs of all profiles, i.e. sub directories of input directory, and
+    /// print matrix of answers and their verification
+    Matrix(MatrixArgs),
+    /// run parse, part 1 and part 2 of selected days several times and compare median times with baseline
+    Bench(BenchArgs),
+    /// write random puzzle input of a day; seed and size are printed to stderr to replay it
+    Gen(GenArgs),
+    /// add skeleton of a day with test stub and empty puzzle input and register it; run in root of crate
+    New(NewArgs),
+    /// step through simulation of a day in terminal, e.g. tilt cycles of day 14, beams of day 16
+    /// or pulses of day 20
+    Step(StepArgs),
+}
+
+impl SelectArgs {
+    /// sorted list of selected days without duplicates; all days if no day is selected
+    pub fn selected_days(&self) -> Vec<u8> {
+        let mut days: Vec<u8> = if self.days.is_empty() {
+            (1..=25).collect()
+        } else {
+            self.days.iter().flat_map(|dr| dr.0.clone()).collect()
+        };
+        days.sort();
+        days.dedup();
+        days
+    }
+    /// input directory of selected profile and year
+    pub fn input_dir(&self) -> Result<PathBuf> {
+        let mut input_dir = input::input_dir();
+        if let Some(profile) = self.profile.as_deref() {
+            input_dir = input::profile_input_dir(&input_dir, profile);
+            if !input_dir.is_dir() {
+                return Err(anyhow!(
+                    "profile `{}` has no directory `{}`",
+                    profile,
+                    input_dir.display()
+                ));
+            }
+        }
+        Ok(input::year_input_dir(&input_dir, self.year))
+    }
+}
+
+/// single day or range of days
+#[derive(Clone)]
+pub struct DayRange(RangeInclusive<u8>);
+
+impl FromStr for DayRange {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_day = |d: &str| -> Result<u8, String> {
+            match d.trim().parse::<u8>() {
+                Ok(day) if (1..=25).contains(&day) => Ok(day),
+                _ => Err(format!("`{}` is not a day between 1 and 25", d.trim())),
+            }
+        };
+        match s.split_once('-') {
+            Some((first, last)) => {
+                let (first, last) = (parse_day(first)?, parse_day(last)?);
+                if first > last {
+                    return Err(format!("`{}` is not an ascending range of days", s));
+                }
+                Ok(DayRange(first..=last))
+            }
+            None => {
+                let day = parse_day(s)?;
+                Ok(DayRange(day..=day))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_day_selection() {
+        let cli = Cli::parse_from(["aoc", "5,1-3", "2", "24-25"]);
+        assert_eq!(cli.run.select.selected_days(), vec![1, 2, 3, 5, 24, 25]);
+        let cli = Cli::parse_from(["aoc", "--part", "2"]);
+        assert_eq!(cli.run.select.selected_days().len(), 25);
+        assert_eq!(cli.run.select.part, Some(2));
+        assert!(Cli::try_parse_from(["aoc", "26"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "7-3"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "1", "--part", "3"]).is_err());
+    }
+
+    #[test]
+    fn test_input_and_profile() {
+        let cli = Cli::parse_from(["aoc", "12", "--input", "example.txt"]);
+        assert_eq!(cli.run.select.input, Some(PathBuf::from("example.txt")));
+        assert!(Cli::try_parse_from(["aoc", "1", "--profile", "bob", "-i", "x.txt"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "1", "--profile", "2024"]).is_err());
+    }
+
+    #[test]
+    fn test_format() {
+        let cli = Cli::parse_from(["aoc", "1", "--format", "markdown"]);
+        assert!(cli.run.format == Format::Markdown);
+        assert!(Cli::try_parse_from(["aoc", "1", "--format", "xml"]).is_err());
+    }
+
+    #[test]
+    fn test_time_budget_and_slow_parts() {
+        let cli = Cli::parse_from(["aoc", "17", "--include-slow", "--time-budget", "2.5"]);
+        assert!(cli.run.select.include_slow);
+        assert_eq!(cli.run.time_budget, Some(Duration::from_millis(2_500)));
+        assert!(Cli::try_parse_from(["aoc", "--time-budget", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_jobs() {
+        assert_eq!(Cli::parse_from(["aoc", "17"]).run.jobs, 1);
+        assert_eq!(Cli::parse_from(["aoc", "-j", "8"]).run.jobs, 8);
+        assert!(Cli::try_parse_from(["aoc", "--jobs", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_year() {
+        assert_eq!(cli_year(&["aoc", "1"]), DEFAULT_YEAR);
+        assert_eq!(cli_year(&["aoc", "1", "--year", "2024"]), 2024);
+        assert!(Cli::try_parse_from(["aoc", "--year", "2014"]).is_err());
+    }
+
+    #[test]
+    fn test_verbose() {
+        assert_eq!(Cli::parse_from(["aoc", "1"]).verbose, 0);
+        assert_eq!(Cli::parse_from(["aoc", "-vv", "1"]).verbose, 2);
+        assert_eq!(Cli::parse_from(["aoc", "verify", "1", "-v"]).verbose, 1);
+    }
+
+    #[test]
+    fn test_verify_args() {
+        let cli = Cli::parse_from(["aoc", "verify", "3-4", "-p", "1"]);
+        match cli.command {
+            Some(Command::Verify(run)) => {
+                assert_eq!(run.select.selected_days(), vec![3, 4]);
+                assert_eq!(run.select.part, Some(1));
+                assert!(run.format == Format::Text);
+            }
+            _ => panic!("expected verify command"),
+        }
+    }
+
+    fn cli_year(args: &[&str]) -> u16 {
+        Cli::parse_from(args).run.select.year
+    }
+}
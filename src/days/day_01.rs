@@ -1,16 +1,66 @@
 //!day_01.rs
 
-use anyhow::Result;
+use crate::generate::InputRng;
+use crate::parse::ParseError;
+use crate::solution::Solution;
+use anyhow::{anyhow, Context, Result};
+use rand::Rng;
 
-pub fn day_01() -> Result<()> {
-    let input = include_str!("../../assets/day_01.txt");
-    part_1(input)?;
-    part_2(input)
+/// Trebuchet?!: sum of calibration values of digits (part 1) and of digits, which may be spelled
+/// out (part 2)
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    const GEN_SIZE: (usize, &'static str) = (1_000, "number of lines");
+    type Input<'a> = Vec<&'a str>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        // lines of calibration document contain only digits and letters
+        for line in input.lines() {
+            if let Some((index, c)) = line
+                .char_indices()
+                .find(|(_, c)| !c.is_ascii_alphanumeric())
+            {
+                return Err(
+                    ParseError::new(&line[index..index + c.len_utf8()], "digit or letter").into(),
+                );
+            }
+        }
+        Ok(input.lines().collect())
+    }
+    fn part_1(lines: &Self::Input<'_>) -> Result<Self::Answer1> {
+        Ok(part_1(lines))
+    }
+    fn part_2(lines: &Self::Input<'_>) -> Result<Self::Answer2> {
+        part_2(lines)
+    }
+    fn generate(rng: &mut InputRng, size: usize) -> Result<String> {
+        let mut input = String::new();
+        for _ in 0..size {
+            // each line needs at least one digit
+            let digit_index = rng.gen_range(0..6);
+            for index in 0..6 {
+                match rng.gen_range(0..4) {
+                    _ if index == digit_index => input.push(rng.gen_range('1'..='9')),
+                    0 => input.push_str(DIGITS[rng.gen_range(0..DIGITS.len())]),
+                    1 => input.push(rng.gen_range('1'..='9')),
+                    _ => {
+                        (0..rng.gen_range(1..5)).for_each(|_| input.push(rng.gen_range('a'..='z')))
+                    }
+                }
+            }
+            input.push('\n');
+        }
+        Ok(input)
+    }
 }
 
-fn part_1(input: &str) -> Result<()> {
-    let sum: u32 = input
-        .lines()
+fn part_1(lines: &[&str]) -> u32 {
+    lines
+        .iter()
         .map(|l| match l.chars().find(|c| c.is_ascii_digit()) {
             Some(left) => {
                 let right = l.chars().rev().find(|c| c.is_ascii_digit()).unwrap();
@@ -21,10 +71,7 @@ fn part_1(input: &str) -> Result<()> {
             }
             None => 0,
         })
-        .sum();
-    println!("result day 01 part 1: {}", sum);
-    assert_eq!(sum, 56_465);
-    Ok(())
+        .sum()
 }
 
 const DIGITS: [&str; 9] = [
@@ -32,14 +79,17 @@ const DIGITS: [&str; 9] = [
 ];
 const CHARS: [char; 9] = ['1', '2', '3', '4', '5', '6', '7', '8', '9'];
 
-pub fn part_2(input: &str) -> Result<()> {
-    let sum: u32 = input
-        .lines()
+fn part_2(lines: &[&str]) -> Result<u32> {
+    lines
+        .iter()
         .map(|l| {
             let mut index = 0;
             let left = loop {
-                if l[index..].chars().next().unwrap().is_ascii_digit() {
-                    break l[index..].chars().next().unwrap();
+                let Some(c) = l[index..].chars().next() else {
+                    return Err(anyhow!("no digit in line `{}`", l));
+                };
+                if c.is_ascii_digit() {
+                    break c;
                 }
                 match DIGITS
                     .into_iter()
@@ -49,13 +99,16 @@ pub fn part_2(input: &str) -> Result<()> {
                     .next()
                 {
                     Some(d) => break d,
-                    None => index += 1,
+                    None => index += c.len_utf8(),
                 }
             };
             let mut len = l[..].len();
             let right = loop {
-                if l[..len].chars().last().unwrap().is_ascii_digit() {
-                    break l[..len].chars().last().unwrap();
+                let Some(c) = l[..len].chars().last() else {
+                    return Err(anyhow!("no digit in line `{}`", l));
+                };
+                if c.is_ascii_digit() {
+                    break c;
                 }
                 match DIGITS
                     .into_iter()
@@ -65,7 +118,7 @@ pub fn part_2(input: &str) -> Result<()> {
                     .next()
                 {
                     Some(d) => break d,
-                    None => len -= 1,
+                    None => len -= c.len_utf8(),
                 }
             };
             let mut number = String::new();
@@ -73,10 +126,51 @@ pub fn part_2(input: &str) -> Result<()> {
             number.push(right);
             number
                 .parse::<u32>()
-                .unwrap_or_else(|_| panic!("could not parse {}{}", left, right))
+                .with_context(|| format!("could not parse {}{}", left, right))
         })
-        .sum();
-    println!("result day 01 part 2: {}", sum);
-    assert_eq!(sum, 55_902);
-    Ok(())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_example_part1() -> Result<()> {
+        let input = "1abc2\n\
+                           pqr3stu8vwx\n\
+                           a1b2c3d4e5f\n\
+                           treb7uchet";
+        let calibration = Day01::parse(input)?;
+        assert_eq!(Day01::part_1(&calibration)?, 142);
+        Ok(())
+    }
+
+    #[test]
+    fn test_example_part2() -> Result<()> {
+        let input = "two1nine\n\
+                           eightwothree\n\
+                           abcone2threexyz\n\
+                           xtwone3four\n\
+                           4nineeightseven2\n\
+                           zoneight234\n\
+                           7pqrstsixteen";
+        let calibration = Day01::parse(input)?;
+        assert_eq!(Day01::part_2(&calibration)?, 281);
+        Ok(())
+    }
+
+    #[test]
+    fn test_line_without_digits() -> Result<()> {
+        let calibration = Day01::parse("two1nine\nabcxyz")?;
+        assert_eq!(Day01::part_1(&calibration)?, 11);
+        assert!(Day01::part_2(&calibration).is_err());
+        let err = Day01::parse("one\ntwo3äone").err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "day 00: expected digit or letter, found `ä`"
+        );
+        Ok(())
+    }
 }
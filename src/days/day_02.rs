@@ -1,9 +1,14 @@
 //!day_02.rs
 
-use anyhow::{anyhow, Result};
+use crate::generate::InputRng;
+use crate::parse::{self, ParseError};
+use crate::solution::Solution;
+use anyhow::Result;
+use rand::{seq::SliceRandom, Rng};
 
+/// max number of red, green and blue cubes
 #[derive(Default)]
-struct Cubes {
+pub struct Cubes {
     red: u32,
     green: u32,
     blue: u32,
@@ -14,15 +19,15 @@ impl Cubes {
         Self { red, green, blue }
     }
 
-    fn max_from_str(&mut self, handful: &str) -> Result<()> {
+    fn max_from_str(&mut self, handful: &str) -> Result<(), ParseError> {
         for cube in handful.split(',').map(|c| c.trim()) {
-            let (num, ctype) = cube.split_once(' ').ok_or(anyhow!("bad input"))?;
-            let num = num.parse::<u32>()?;
+            let (num, ctype) = parse::split_once(cube, " ")?;
+            let num = parse::number::<u32>(num)?;
             match ctype {
                 "red" => self.red = self.red.max(num),
                 "green" => self.green = self.green.max(num),
                 "blue" => self.blue = self.blue.max(num),
-                _ => return Err(anyhow!("bad input")),
+                _ => return Err(ParseError::new(ctype, "`red`, `green` or `blue`")),
             }
         }
         Ok(())
@@ -37,26 +42,81 @@ impl Cubes {
     }
 }
 
-pub fn day_02() -> Result<()> {
-    let input = include_str!("../../assets/day_02.txt");
-    let max_cubes = Cubes::new(12, 13, 14);
-    let mut result = 0;
-    let mut power = 0;
-    for line in input.lines() {
-        let mut cubes = Cubes::default();
-        let (game, handfuls) = line.split_once(':').ok_or(anyhow!("bad input"))?;
-        let game = game[5..].parse::<u32>()?;
-        for handful in handfuls.split(';') {
-            cubes.max_from_str(handful)?;
+/// Cube Conundrum: sum of ids of possible games (part 1) and sum of powers of minimal cube sets
+/// (part 2)
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    const GEN_SIZE: (usize, &'static str) = (100, "number of games");
+    /// game id and max number of cubes per color shown in this game
+    type Input<'a> = Vec<(u32, Cubes)>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        let mut games = Vec::new();
+        for line in input.lines() {
+            let mut cubes = Cubes::default();
+            let (game, handfuls) = parse::split_once(line, ":")?;
+            let game = match game.strip_prefix("Game ") {
+                Some(id) => parse::number::<u32>(id)?,
+                None => return Err(ParseError::new(game, "`Game <id>`").into()),
+            };
+            for handful in handfuls.split(';') {
+                cubes.max_from_str(handful)?;
+            }
+            games.push((game, cubes));
         }
-        if cubes.is_possible(&max_cubes) {
-            result += game;
+        Ok(games)
+    }
+    fn part_1(input: &Self::Input<'_>) -> Result<Self::Answer1> {
+        let max_cubes = Cubes::new(12, 13, 14);
+        Ok(input
+            .iter()
+            .filter(|(_, cubes)| cubes.is_possible(&max_cubes))
+            .map(|(game, _)| game)
+            .sum())
+    }
+    fn part_2(input: &Self::Input<'_>) -> Result<Self::Answer2> {
+        Ok(input.iter().map(|(_, cubes)| cubes.power()).sum())
+    }
+    fn generate(rng: &mut InputRng, size: usize) -> Result<String> {
+        let mut input = String::new();
+        for game in 1..=size {
+            let handfuls: Vec<String> = (0..rng.gen_range(1..=6))
+                .map(|_| {
+                    let mut colors = ["red", "green", "blue"];
+                    colors.shuffle(rng);
+                    let num_colors = rng.gen_range(1..=colors.len());
+                    colors[..num_colors]
+                        .iter()
+                        .map(|color| format!("{} {}", rng.gen_range(1..=20), color))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                })
+                .collect();
+            input.push_str(&format!("Game {}: {}\n", game, handfuls.join("; ")));
         }
-        power += cubes.power();
+        Ok(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_example() -> Result<()> {
+        let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green\n\
+                           Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue\n\
+                           Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red\n\
+                           Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red\n\
+                           Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+        let games = Day02::parse(input)?;
+        assert_eq!(Day02::part_1(&games)?, 8);
+        assert_eq!(Day02::part_2(&games)?, 2286);
+        Ok(())
     }
-    println!("result day 02 part 1: {}", result);
-    assert_eq!(result, 2_416);
-    println!("result day 02 part 2: {}", power);
-    assert_eq!(power, 63_307);
-    Ok(())
 }
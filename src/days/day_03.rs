@@ -1,79 +1,101 @@
 //!day_03.rs
 
+use crate::generate::{self, InputRng};
+use crate::my_lib::my_map_point::MapPoint;
+use crate::my_lib::my_map_point::MapSize;
+use crate::my_lib::my_map_two_dim::MyMap2D;
+use crate::parse;
+use crate::render::{Image, Rgb};
+use crate::solution::Solution;
 use anyhow::Result;
-use my_lib::my_map_point::MapPoint;
-use my_lib::my_map_two_dim::MyMap2D;
-
-// number of chars in one line of day_03.txt
-const X: usize = 140;
-// number of lines in day_03.txt
-const Y: usize = 140;
+use rand::Rng;
+use std::collections::HashSet;
 
+/// char of engine schematic and id of number it belongs to; 0 for no number
 #[derive(Copy, Clone, Default)]
-struct Cell {
+pub struct Cell {
     val: char,
     id: u32,
 }
 
-pub fn day_03() -> Result<()> {
-    let input = include_str!("../../assets/day_03.txt");
-    let mut char_map: MyMap2D<Cell, X, Y> = MyMap2D::default();
-    let mut id = 1;
-    let mut last_is_digit = false;
-    for (y, line) in input.lines().enumerate() {
-        for (x, c) in line.chars().enumerate() {
-            if x == 0 && last_is_digit {
-                id += 1;
-                last_is_digit = false;
-            }
-            let cell = char_map.get_mut(MapPoint::<X, Y>::new(x, y));
-            cell.val = c;
-            if c.is_ascii_digit() {
-                cell.id = id;
-                last_is_digit = true;
-            } else if last_is_digit {
-                id += 1;
-                last_is_digit = false;
-            }
-        }
-    }
+/// Gear Ratios: sum of part numbers (part 1) and sum of gear ratios (part 2)
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+    const GEN_SIZE: (usize, &'static str) = (140, "width and height of schematic");
+    type Input<'a> = MyMap2D<Cell>;
+    type Answer1 = u32;
+    type Answer2 = u32;
 
-    let mut result_part1 = 0;
-    let mut result_part2 = 0;
-    last_is_digit = false;
-    let mut is_part_number = false;
-    let mut digits = String::new();
-    for (point, cell) in char_map.iter() {
-        // init new line
-        if point.x() == 0 {
-            if is_part_number {
-                result_part1 += digits.parse::<u32>()?;
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        let mut char_map: MyMap2D<Cell> = MyMap2D::init(parse::grid_size(input)?, Cell::default());
+        let mut id = 1;
+        let mut last_is_digit = false;
+        for (y, line) in input
+            .lines()
+            .map(|l| l.trim())
+            .filter(|l| !l.is_empty())
+            .enumerate()
+        {
+            for (x, c) in line.chars().enumerate() {
+                if x == 0 && last_is_digit {
+                    id += 1;
+                    last_is_digit = false;
+                }
+                let cell = char_map.get_mut(MapPoint::new(x, y));
+                cell.val = c;
+                if c.is_ascii_digit() {
+                    cell.id = id;
+                    last_is_digit = true;
+                } else if last_is_digit {
+                    id += 1;
+                    last_is_digit = false;
+                }
             }
-            is_part_number = false;
-            last_is_digit = false;
-            digits = "".into();
         }
-
-        if cell.val.is_ascii_digit() {
-            digits.push(cell.val);
-            last_is_digit = true;
-            // check for is_part_number
-            is_part_number = is_part_number
-                || char_map
-                    .iter_neighbors_with_corners(point)
-                    .any(|(_, _, c)| c.val != '.' && !c.val.is_ascii_digit());
-        } else {
-            if is_part_number {
-                result_part1 += digits.parse::<u32>()?;
+        Ok(char_map)
+    }
+    fn part_1(char_map: &Self::Input<'_>) -> Result<Self::Answer1> {
+        let mut result_part1 = 0;
+        let mut last_is_digit = false;
+        let mut is_part_number = false;
+        let mut digits = String::new();
+        for (point, cell) in char_map.iter() {
+            // init new line
+            if point.x() == 0 {
+                if is_part_number {
+                    result_part1 += digits.parse::<u32>()?;
+                }
                 is_part_number = false;
-            }
-            if last_is_digit {
-                digits = "".into();
                 last_is_digit = false;
+                digits = "".into();
+            }
+
+            if cell.val.is_ascii_digit() {
+                digits.push(cell.val);
+                last_is_digit = true;
+                // check for is_part_number
+                is_part_number = is_part_number
+                    || char_map
+                        .iter_neighbors_with_corners(point)
+                        .any(|(_, _, c)| c.val != '.' && !c.val.is_ascii_digit());
+            } else {
+                if is_part_number {
+                    result_part1 += digits.parse::<u32>()?;
+                    is_part_number = false;
+                }
+                if last_is_digit {
+                    digits = "".into();
+                    last_is_digit = false;
+                }
             }
         }
-        // Part 2
-        if cell.val == '*' {
+        Ok(result_part1)
+    }
+    fn part_2(char_map: &Self::Input<'_>) -> Result<Self::Answer2> {
+        let mut result_part2 = 0;
+        for (point, _) in char_map.iter().filter(|(_, cell)| cell.val == '*') {
             let mut ids: Vec<u32> = char_map
                 .iter_neighbors_with_corners(point)
                 .filter(|(_, _, c)| c.id > 0)
@@ -99,11 +121,84 @@ pub fn day_03() -> Result<()> {
                 result_part2 += digits_id0 * digits_id1;
             }
         }
+        Ok(result_part2)
+    }
+    fn generate(rng: &mut InputRng, size: usize) -> Result<String> {
+        let mut rows = vec![vec!['.'; size]; size];
+        for row in rows.iter_mut() {
+            // numbers with at least one cell between them
+            let mut x = rng.gen_range(0..4);
+            while x < size {
+                let len = rng.gen_range(1..=3).min(size - x);
+                row[x] = rng.gen_range('1'..='9');
+                for cell in row[x + 1..x + len].iter_mut() {
+                    *cell = rng.gen_range('0'..='9');
+                }
+                x += len + rng.gen_range(1..8);
+            }
+        }
+        let symbols = ['*', '*', '*', '#', '+', '$', '/', '@', '=', '%', '&', '-'];
+        Ok(generate::grid(
+            rng,
+            MapSize::new(size, size),
+            |rng, p| match rows[p.y()][p.x()] {
+                '.' if rng.gen_bool(0.1) => symbols[rng.gen_range(0..symbols.len())],
+                c => c,
+            },
+        ))
     }
+    // part numbers are green, other numbers red and symbols yellow
+    fn render(char_map: &Self::Input<'_>) -> Result<Option<Image>> {
+        let is_symbol = |c: &Cell| c.val != '.' && !c.val.is_ascii_digit();
+        let part_ids: HashSet<u32> = char_map
+            .iter()
+            .filter(|(_, cell)| is_symbol(cell))
+            .flat_map(|(point, _)| char_map.iter_neighbors_with_corners(point))
+            .filter(|(_, _, c)| c.id > 0)
+            .map(|(_, _, c)| c.id)
+            .collect();
+        Ok(Some(Image::from_map(char_map, |_, cell| {
+            if is_symbol(cell) {
+                Rgb::YELLOW
+            } else if part_ids.contains(&cell.id) {
+                Rgb::GREEN
+            } else if cell.id > 0 {
+                Rgb::RED
+            } else {
+                Rgb::BLACK
+            }
+        })))
+    }
+}
 
-    println!("result day 03 part 1: {}", result_part1);
-    assert_eq!(result_part1, 535_235);
-    println!("result day 03 part 2: {}", result_part2);
-    assert_eq!(result_part2, 79_844_424);
-    Ok(())
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_example() -> Result<()> {
+        let input = "467..114..\n\
+                           ...*......\n\
+                           ..35..633.\n\
+                           ......#...\n\
+                           617*......\n\
+                           .....+.58.\n\
+                           ..592.....\n\
+                           ......755.\n\
+                           ...$.*....\n\
+                           .664.598..";
+        let char_map = Day03::parse(input)?;
+        assert_eq!(Day03::part_1(&char_map)?, 4361);
+        assert_eq!(Day03::part_2(&char_map)?, 467835);
+        // 114 and 58 are no part numbers
+        let image = Day03::render(&char_map)?.unwrap();
+        let colour = |x, y| image.get(MapPoint::new(x, y));
+        assert_eq!(colour(0, 0), Rgb::GREEN);
+        assert_eq!(colour(5, 0), Rgb::RED);
+        assert_eq!(colour(8, 5), Rgb::RED);
+        assert_eq!(colour(3, 1), Rgb::YELLOW);
+        assert_eq!(colour(0, 1), Rgb::BLACK);
+        Ok(())
+    }
 }
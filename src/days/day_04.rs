@@ -1,49 +1,135 @@
 //!day_04.rs
 
-use anyhow::Result;
-use my_lib::my_array::MyArray;
-
-const NUM_CARDS: usize = 201;
-
-pub fn day_04() -> Result<()> {
-    let input = include_str!("../../assets/day_04.txt");
-    let mut result_part1 = 0;
-    let mut num_per_card: MyArray<u32, NUM_CARDS> = MyArray::init(1, NUM_CARDS);
-    for (current_card, line) in input
-        .lines()
-        .map(|l| l.split_once(':').unwrap().1.trim())
-        .enumerate()
-    {
-        let (win_str, my_str) = line.split_once('|').unwrap();
-        let winners: Vec<u8> = win_str
-            .trim()
-            .split_ascii_whitespace()
-            .map(|n| n.parse::<u8>().unwrap())
-            .collect();
-        let my_numbers: Vec<u8> = my_str
-            .trim()
-            .split_ascii_whitespace()
-            .map(|n| n.parse::<u8>().unwrap())
-            .collect();
-        let my_winners = winners
+use crate::generate::InputRng;
+use crate::my_lib::my_array::MyArray;
+use crate::parse;
+use crate::solution::Solution;
+use anyhow::{anyhow, Result};
+use rand::{seq::SliceRandom, Rng};
+
+// max number of cards
+const NUM_CARDS: usize = 256;
+
+/// Scratchcards: points of cards (part 1) and number of won copies of cards (part 2)
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    const GEN_SIZE: (usize, &'static str) = (200, "number of cards");
+    /// number of my winning numbers per card
+    type Input<'a> = Vec<usize>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        let mut my_winners_per_card = Vec::with_capacity(NUM_CARDS);
+        for line in input.lines() {
+            let line = parse::split_once(line, ":")?.1.trim();
+            let (win_str, my_str) = parse::split_once(line, "|")?;
+            let winners: Vec<u8> = win_str
+                .trim()
+                .split_ascii_whitespace()
+                .map(parse::number::<u8>)
+                .collect::<Result<_, _>>()?;
+            let my_numbers: Vec<u8> = my_str
+                .trim()
+                .split_ascii_whitespace()
+                .map(parse::number::<u8>)
+                .collect::<Result<_, _>>()?;
+            let my_winners = winners
+                .iter()
+                .filter(|w| my_numbers.iter().any(|m| m == *w))
+                .count();
+            my_winners_per_card.push(my_winners);
+        }
+        Ok(my_winners_per_card)
+    }
+    fn part_1(input: &Self::Input<'_>) -> Result<Self::Answer1> {
+        Ok(input
             .iter()
-            .filter(|w| my_numbers.iter().any(|m| m == *w))
-            .count();
-        if my_winners > 0 {
-            result_part1 += 2_u32.pow((my_winners - 1) as u32);
+            .filter(|w| **w > 0)
+            .map(|w| 2_u32.pow((w - 1) as u32))
+            .sum())
+    }
+    fn part_2(input: &Self::Input<'_>) -> Result<Self::Answer2> {
+        if input.len() > NUM_CARDS {
+            return Err(anyhow!("more than {} cards", NUM_CARDS));
+        }
+        let mut num_per_card: MyArray<u32, NUM_CARDS> = MyArray::init(1, input.len());
+        for (current_card, &my_winners) in input.iter().enumerate() {
             let num_curent_card = num_per_card[current_card];
             for index in current_card + 1..=current_card + my_winners {
-                if index < NUM_CARDS {
+                if index < num_per_card.len() {
                     *num_per_card.get_mut(index).unwrap() += num_curent_card;
                 }
             }
         }
+        Ok(num_per_card.iter().sum::<u32>())
     }
+    fn generate(rng: &mut InputRng, size: usize) -> Result<String> {
+        if size > NUM_CARDS {
+            return Err(anyhow!("at most {} cards", NUM_CARDS));
+        }
+        let mut input = String::new();
+        let mut num_per_card = vec![1_u32; size];
+        for card in 0..size {
+            // cards do not win copies past end of table; cards with many copies win nothing,
+            // which keeps total number of cards small
+            let max_winners = if num_per_card[card] > 10_000 {
+                0
+            } else {
+                (size - card - 1).min(10)
+            };
+            let my_winners = rng.gen_range(0..=max_winners);
+            for index in card + 1..=card + my_winners {
+                num_per_card[index] += num_per_card[card];
+            }
+            let numbers: Vec<usize> = rand::seq::index::sample(rng, 99, 35)
+                .into_iter()
+                .map(|n| n + 1)
+                .collect();
+            let (winners, others) = numbers.split_at(10);
+            let mut my_numbers: Vec<usize> = winners[..my_winners]
+                .iter()
+                .chain(others[..25 - my_winners].iter())
+                .copied()
+                .collect();
+            my_numbers.shuffle(rng);
+            let format_numbers = |numbers: &[usize]| {
+                numbers
+                    .iter()
+                    .map(|n| format!("{:>2}", n))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            };
+            input.push_str(&format!(
+                "Card {:>3}: {} | {}\n",
+                card + 1,
+                format_numbers(winners),
+                format_numbers(&my_numbers)
+            ));
+        }
+        Ok(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
 
-    println!("result day 04 part 1: {}", result_part1);
-    assert_eq!(result_part1, 21_485);
-    let result_part2 = num_per_card.iter().sum::<u32>();
-    println!("result day 04 part 2: {}", result_part2);
-    assert_eq!(result_part2, 11_024_379);
-    Ok(())
+    use super::*;
+
+    #[test]
+    fn test_example() -> Result<()> {
+        let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53\n\
+                           Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19\n\
+                           Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1\n\
+                           Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83\n\
+                           Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36\n\
+                           Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+        let my_winners_per_card = Day04::parse(input)?;
+        assert_eq!(my_winners_per_card, vec![4, 2, 2, 1, 0, 0]);
+        assert_eq!(Day04::part_1(&my_winners_per_card)?, 13);
+        assert_eq!(Day04::part_2(&my_winners_per_card)?, 30);
+        Ok(())
+    }
 }
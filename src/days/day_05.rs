@@ -1,85 +1,25 @@
 //!day_05.rs
 
-use anyhow::Result;
+use crate::generate::InputRng;
+use crate::my_lib::my_interval::{Interval, IntervalSet, PiecewiseLinear};
+use crate::parse::{self, ParseError};
+use crate::solution::Solution;
+use anyhow::{anyhow, Result};
+use rand::{seq::SliceRandom, Rng};
 
-#[derive(Clone, Copy)]
-struct CategoryRange {
-    start: u64,
-    range: u64,
-}
-
-impl CategoryRange {
-    fn single(value: u64) -> Self {
-        CategoryRange {
-            start: value,
-            range: 1,
-        }
-    }
-    fn new(start: u64, range: u64) -> Self {
-        CategoryRange { start, range }
-    }
-    fn end(&self) -> u64 {
-        self.start + self.range - 1
-    }
-}
-
-struct TransferMap {
-    source: CategoryRange,
-    destination: CategoryRange,
-}
-
-impl From<&str> for TransferMap {
-    fn from(value: &str) -> Self {
-        let mut value_iter = value.split_ascii_whitespace();
-        let destination_start = match value_iter.next() {
-            Some(ds) => ds.parse::<u64>().expect("bad input"),
-            None => panic!("bad input"),
-        };
-        let source_start = match value_iter.next() {
-            Some(ss) => ss.parse::<u64>().expect("bad input"),
-            None => panic!("bad input"),
-        };
-        let range = match value_iter.next() {
-            Some(r) => r.parse::<u64>().expect("bad input"),
-            None => panic!("bad input"),
-        };
-        if value_iter.next().is_some() {
-            panic!("bad input");
-        }
-        Self {
-            source: CategoryRange::new(source_start, range),
-            destination: CategoryRange::new(destination_start, range),
-        }
-    }
-}
-
-impl TransferMap {
-    fn transfer_category_range(
-        &self,
-        input_range: &CategoryRange,
-    ) -> Option<(CategoryRange, Vec<CategoryRange>)> {
-        let overlapping_start = input_range.start.max(self.source.start);
-        let overlapping_end = input_range.end().min(self.source.end());
-        if overlapping_start <= overlapping_end {
-            let overlaping_range = CategoryRange::new(
-                overlapping_start + self.destination.start - self.source.start,
-                overlapping_end - overlapping_start + 1,
-            );
-            let mut remaining_ranges: Vec<CategoryRange> = Vec::new();
-            if input_range.start < overlapping_start {
-                let pre_range =
-                    CategoryRange::new(input_range.start, overlapping_start - input_range.start);
-                remaining_ranges.push(pre_range);
-            }
-            if input_range.end() > overlapping_end {
-                let post_range =
-                    CategoryRange::new(overlapping_end + 1, input_range.end() - overlapping_end);
-                remaining_ranges.push(post_range);
-            }
-            return Some((overlaping_range, remaining_ranges));
-        }
-        None
-    }
+// source range of line of map and its destination start
+fn transfer_range(line: &str) -> Result<(Interval<u64>, u64), ParseError> {
+    let mut value_iter = line.split_ascii_whitespace();
+    let destination_start = parse::number::<u64>(parse::next_token(
+        &mut value_iter,
+        line,
+        "destination start",
+    )?)?;
+    let source_start =
+        parse::number::<u64>(parse::next_token(&mut value_iter, line, "source start")?)?;
+    let range = parse::number::<u64>(parse::next_token(&mut value_iter, line, "range")?)?;
+    parse::no_more_tokens(&mut value_iter, "end of line")?;
+    Ok((Interval::with_len(source_start, range), destination_start))
 }
 
 #[derive(Copy, Clone, Default)]
@@ -109,19 +49,20 @@ impl TransferMapType {
     }
 }
 
+/// maps of categories from seed to location
 #[derive(Default)]
-struct TransferMapSet {
-    seed_to_soil: Vec<TransferMap>,
-    soil_to_fertilizer: Vec<TransferMap>,
-    fertilizer_to_water: Vec<TransferMap>,
-    water_to_light: Vec<TransferMap>,
-    light_to_temperature: Vec<TransferMap>,
-    temperature_to_humidity: Vec<TransferMap>,
-    humidity_to_location: Vec<TransferMap>,
+pub struct TransferMapSet {
+    seed_to_soil: PiecewiseLinear<u64>,
+    soil_to_fertilizer: PiecewiseLinear<u64>,
+    fertilizer_to_water: PiecewiseLinear<u64>,
+    water_to_light: PiecewiseLinear<u64>,
+    light_to_temperature: PiecewiseLinear<u64>,
+    temperature_to_humidity: PiecewiseLinear<u64>,
+    humidity_to_location: PiecewiseLinear<u64>,
 }
 
 impl TransferMapSet {
-    fn get_trans_map(&self, map_type: TransferMapType) -> &Vec<TransferMap> {
+    fn get_trans_map(&self, map_type: TransferMapType) -> &PiecewiseLinear<u64> {
         match map_type {
             TransferMapType::SeedToSoil => &self.seed_to_soil,
             TransferMapType::SoilToFertilizer => &self.soil_to_fertilizer,
@@ -132,7 +73,7 @@ impl TransferMapSet {
             TransferMapType::HumidityToLocation => &self.humidity_to_location,
         }
     }
-    fn get_trans_map_mut(&mut self, map_type: TransferMapType) -> &mut Vec<TransferMap> {
+    fn get_trans_map_mut(&mut self, map_type: TransferMapType) -> &mut PiecewiseLinear<u64> {
         match map_type {
             TransferMapType::SeedToSoil => &mut self.seed_to_soil,
             TransferMapType::SoilToFertilizer => &mut self.soil_to_fertilizer,
@@ -143,34 +84,23 @@ impl TransferMapSet {
             TransferMapType::HumidityToLocation => &mut self.humidity_to_location,
         }
     }
-    fn add_transfer_map(&mut self, map: TransferMap, map_type: TransferMapType) {
-        self.get_trans_map_mut(map_type).push(map);
+    fn add_transfer_range(
+        &mut self,
+        (source, destination_start): (Interval<u64>, u64),
+        map_type: TransferMapType,
+    ) {
+        self.get_trans_map_mut(map_type)
+            .add_piece(source, destination_start);
     }
 
-    fn get_min_location_from_seed_ranges(&self, mut seed_ranges: Vec<CategoryRange>) -> u64 {
+    fn get_min_location_from_seed_ranges(&self, seed_ranges: IntervalSet<u64>) -> Result<u64> {
+        let mut ranges = seed_ranges;
         let mut map_type = Some(TransferMapType::default());
         while let Some(tmt) = map_type {
-            let mut transfered_ranges: Vec<CategoryRange> = Vec::new();
-            while let Some(input_range) = seed_ranges.pop() {
-                let mut no_transfer = true;
-                for transfer_map in self.get_trans_map(tmt).iter() {
-                    if let Some((transfered_range, remaining_ranges)) =
-                        transfer_map.transfer_category_range(&input_range)
-                    {
-                        transfered_ranges.push(transfered_range);
-                        seed_ranges.extend_from_slice(&remaining_ranges[..]);
-                        no_transfer = false;
-                        break;
-                    }
-                }
-                if no_transfer {
-                    transfered_ranges.push(input_range);
-                }
-            }
-            seed_ranges = transfered_ranges;
+            ranges = self.get_trans_map(tmt).map(&ranges);
             map_type = tmt.next();
         }
-        seed_ranges.iter().map(|cr| cr.start).min().unwrap()
+        ranges.min().ok_or_else(|| anyhow!("no seeds"))
     }
 }
 
@@ -178,50 +108,200 @@ impl TransferMapSet {
 // www.youtube.com/@hyper-neutrino
 // github.com/hyper-neutrino
 
-pub fn day_05() -> Result<()> {
-    let input = include_str!("../../assets/day_05.txt");
-    let mut transfer_maps = TransferMapSet::default();
-    let mut seed_input: Vec<u64> = Vec::new();
-    let mut transfer_map_type: Option<TransferMapType> = None;
-    for line in input.lines().filter(|l| !l.is_empty()) {
-        if seed_input.is_empty() {
-            seed_input = line
-                .split_once(':')
-                .unwrap()
-                .1
-                .trim()
-                .split_ascii_whitespace()
-                .map(|u| u.parse::<u64>().expect("bad input"))
+/// If You Give A Seed A Fertilizer: lowest location of seeds (part 1) and of seed ranges (part 2)
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    const GEN_SIZE: (usize, &'static str) = (30, "number of ranges per map");
+    /// seed input and transfer maps
+    type Input<'a> = (Vec<u64>, TransferMapSet);
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        let mut transfer_maps = TransferMapSet::default();
+        let mut seed_input: Vec<u64> = Vec::new();
+        let mut transfer_map_type: Option<TransferMapType> = None;
+        for line in input.lines().filter(|l| !l.is_empty()) {
+            if seed_input.is_empty() {
+                seed_input = parse::split_once(line, ":")?
+                    .1
+                    .split_ascii_whitespace()
+                    .map(parse::number::<u64>)
+                    .collect::<Result<_, _>>()?;
+            } else if line.contains("map") {
+                transfer_map_type = match transfer_map_type {
+                    Some(tmt) => tmt.next(),
+                    None => Some(TransferMapType::default()),
+                };
+            } else if let Some(tmt) = transfer_map_type {
+                transfer_maps.add_transfer_range(transfer_range(line)?, tmt);
+            }
+        }
+        Ok((seed_input, transfer_maps))
+    }
+    fn part_1((seed_input, transfer_maps): &Self::Input<'_>) -> Result<Self::Answer1> {
+        let seeds: IntervalSet<u64> = seed_input.iter().map(|s| Interval::single(*s)).collect();
+        transfer_maps.get_min_location_from_seed_ranges(seeds)
+    }
+    fn part_2((seed_input, transfer_maps): &Self::Input<'_>) -> Result<Self::Answer2> {
+        let mut seeds: IntervalSet<u64> = IntervalSet::new();
+        let mut seed_iter = seed_input.iter();
+        while let Some(&start_seed) = seed_iter.next() {
+            let &seed_range = seed_iter
+                .next()
+                .ok_or_else(|| anyhow!("seed {} has no range", start_seed))?;
+            seeds.insert(Interval::with_len(start_seed, seed_range));
+        }
+        transfer_maps.get_min_location_from_seed_ranges(seeds)
+    }
+    fn generate(rng: &mut InputRng, size: usize) -> Result<String> {
+        generate_almanac(rng, size, 1 << 32)
+    }
+}
+
+/// almanac with ranges per map, which all map numbers below category_space
+fn generate_almanac(rng: &mut InputRng, ranges: usize, category_space: u64) -> Result<String> {
+    if ranges == 0 || ranges as u64 >= category_space || category_space < 20 {
+        return Err(anyhow!(
+            "at least one range per map and less ranges than category space"
+        ));
+    }
+    let seeds: Vec<String> = (0..10)
+        .flat_map(|_| {
+            [
+                rng.gen_range(0..category_space),
+                rng.gen_range(1..category_space / 20),
+            ]
+        })
+        .map(|n| n.to_string())
+        .collect();
+    let mut input = format!("seeds: {}\n", seeds.join(" "));
+    for map_name in [
+        "seed-to-soil",
+        "soil-to-fertilizer",
+        "fertilizer-to-water",
+        "water-to-light",
+        "light-to-temperature",
+        "temperature-to-humidity",
+        "humidity-to-location",
+    ] {
+        // split category space into ranges and shuffle them, which maps category space onto itself
+        let mut cuts: Vec<u64> =
+            rand::seq::index::sample(rng, category_space as usize - 1, ranges - 1)
+                .into_iter()
+                .map(|c| c as u64 + 1)
                 .collect();
-        } else if line.contains("map") {
-            transfer_map_type = match transfer_map_type {
-                Some(tmt) => tmt.next(),
-                None => Some(TransferMapType::default()),
+        cuts.push(0);
+        cuts.push(category_space);
+        cuts.sort();
+        let mut ranges: Vec<(u64, u64)> = cuts.windows(2).map(|c| (c[0], c[1] - c[0])).collect();
+        ranges.shuffle(rng);
+        input.push_str(&format!("\n{} map:\n", map_name));
+        let mut destination = 0;
+        for (source, range) in ranges {
+            // numbers of missing ranges are not transfered
+            if rng.gen_bool(0.9) {
+                input.push_str(&format!("{} {} {}\n", destination, source, range));
+            }
+            destination += range;
+        }
+    }
+    Ok(input)
+}
+
+#[cfg(test)]
+mod reference {
+
+    use super::*;
+
+    /// transfers every single seed through all maps
+    pub fn part_2(input: &str) -> Result<u64> {
+        let (seed_input, transfer_maps) = Day05::parse(input)?;
+        let mut min_location = u64::MAX;
+        for seed_range in seed_input.chunks(2) {
+            let &[start, range] = seed_range else {
+                return Err(anyhow!("seed {} has no range", seed_range[0]));
             };
-        } else if let Some(tmt) = transfer_map_type {
-            transfer_maps.add_transfer_map(TransferMap::from(line), tmt);
+            for seed in start..start + range {
+                let mut number = seed;
+                let mut map_type = Some(TransferMapType::default());
+                while let Some(tmt) = map_type {
+                    if let Some((source, destination_start)) = transfer_maps
+                        .get_trans_map(tmt)
+                        .pieces()
+                        .find(|(source, _)| source.start() <= number && number < source.end())
+                    {
+                        number = number - source.start() + destination_start;
+                    }
+                    map_type = tmt.next();
+                }
+                min_location = min_location.min(number);
+            }
         }
+        Ok(min_location)
     }
+}
 
-    let seeds: Vec<CategoryRange> = seed_input
-        .iter()
-        .map(|s| CategoryRange::single(*s))
-        .collect();
+#[cfg(test)]
+mod tests {
 
-    let lowest_location = transfer_maps.get_min_location_from_seed_ranges(seeds);
-    println!("result day 05 part 1: {}", lowest_location);
-    assert_eq!(lowest_location, 261_668_924);
+    use super::*;
+    use crate::differential;
 
-    // Part 2
-    let mut seeds: Vec<CategoryRange> = Vec::new();
-    let mut seed_iter = seed_input.iter();
-    while let Some(&start_seed) = seed_iter.next() {
-        let &seed_range = seed_iter.next().expect("bad input");
-        seeds.push(CategoryRange::new(start_seed, seed_range));
+    #[test]
+    fn test_example() -> Result<()> {
+        let input = "seeds: 79 14 55 13\n\
+                           \n\
+                           seed-to-soil map:\n\
+                           50 98 2\n\
+                           52 50 48\n\
+                           \n\
+                           soil-to-fertilizer map:\n\
+                           0 15 37\n\
+                           37 52 2\n\
+                           39 0 15\n\
+                           \n\
+                           fertilizer-to-water map:\n\
+                           49 53 8\n\
+                           0 11 42\n\
+                           42 0 7\n\
+                           57 7 4\n\
+                           \n\
+                           water-to-light map:\n\
+                           88 18 7\n\
+                           18 25 70\n\
+                           \n\
+                           light-to-temperature map:\n\
+                           45 77 23\n\
+                           81 45 19\n\
+                           68 64 13\n\
+                           \n\
+                           temperature-to-humidity map:\n\
+                           0 69 1\n\
+                           1 0 69\n\
+                           \n\
+                           humidity-to-location map:\n\
+                           60 56 37\n\
+                           56 93 4";
+        let almanac = Day05::parse(input)?;
+        assert_eq!(Day05::part_1(&almanac)?, 35);
+        assert_eq!(Day05::part_2(&almanac)?, 46);
+        assert_eq!(reference::part_2(input)?, 46);
+        Ok(())
     }
-    let lowest_location = transfer_maps.get_min_location_from_seed_ranges(seeds);
-    println!("result day 05 part 2: {}", lowest_location);
-    assert_eq!(lowest_location, 24_261_545);
 
-    Ok(())
+    #[test]
+    fn test_differential_part_2() -> Result<()> {
+        if let Some(divergence) = differential::first_divergence(
+            0..20,
+            |rng| generate_almanac(rng, 6, 5_000),
+            |input| Day05::part_2(&Day05::parse(input)?),
+            reference::part_2,
+        )? {
+            panic!("day 05 part 2: {}", divergence);
+        }
+        Ok(())
+    }
 }
@@ -1,6 +1,10 @@
 //!day_06.rs
 
+use crate::generate::InputRng;
+use crate::parse;
+use crate::solution::Solution;
 use anyhow::{anyhow, Result};
+use rand::Rng;
 
 struct RaceParam {
     time: f64,
@@ -12,81 +16,93 @@ impl RaceParam {
         Self { time, distance }
     }
     fn calc_times(&self) -> (u64, u64) {
+        // distance must be beaten, so exclude hold times, which only reach distance
         let time_1 = ((self.time / 2.0) - ((self.time / 2.0).powf(2.0) - self.distance).sqrt())
-            .ceil() as u64;
+            .floor() as u64
+            + 1;
         let time_2 = ((self.time / 2.0) + ((self.time / 2.0).powf(2.0) - self.distance).sqrt())
-            .floor() as u64;
+            .ceil() as u64
+            - 1;
         (time_1, time_2)
     }
 }
 
-pub fn day_06() -> Result<()> {
-    let input = include_str!("../../assets/day_06.txt");
-    let mut input_iter = input.lines();
-    let times = input_iter
-        .next()
-        .ok_or(anyhow!("bad input"))?
-        .split_once(':')
-        .ok_or(anyhow!("bad input"))?
-        .1
-        .trim();
-    let distances = input_iter
-        .next()
-        .ok_or(anyhow!("bad input"))?
-        .split_once(':')
-        .ok_or(anyhow!("bad input"))?
-        .1
-        .trim();
-    let mut races: Vec<RaceParam> = Vec::with_capacity(4);
-    for (time, distance) in times
-        .split_ascii_whitespace()
-        .map(|t| t.parse::<f64>().expect("bad input"))
-        .zip(
-            distances
-                .split_ascii_whitespace()
-                .map(|d| d.parse::<f64>().expect("bad input")),
-        )
-    {
-        races.push(RaceParam::new(time, distance));
+/// Wait For It: product of ways to win each race (part 1) and ways to win one long race (part 2)
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+    const GEN_SIZE: (usize, &'static str) = (4, "number of races");
+    /// times and distances of races
+    type Input<'a> = (&'a str, &'a str);
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        let mut input_iter = input.lines();
+        let times = parse::next_token(&mut input_iter, input, "line of times")?;
+        let times = parse::split_once(times, ":")?.1.trim();
+        let distances = parse::next_token(&mut input_iter, input, "line of distances")?;
+        let distances = parse::split_once(distances, ":")?.1.trim();
+        Ok((times, distances))
     }
+    fn part_1((times, distances): &Self::Input<'_>) -> Result<Self::Answer1> {
+        let mut races: Vec<RaceParam> = Vec::with_capacity(4);
+        for (time, distance) in times
+            .split_ascii_whitespace()
+            .zip(distances.split_ascii_whitespace())
+        {
+            races.push(RaceParam::new(
+                parse::number::<f64>(time)?,
+                parse::number::<f64>(distance)?,
+            ));
+        }
 
-    let mut result_part1: u64 = 1;
-    for race in races.iter() {
+        let mut result_part1: u64 = 1;
+        for race in races.iter() {
+            let (time_1, time_2) = race.calc_times();
+            result_part1 *= time_2 - time_1 + 1;
+        }
+        Ok(result_part1)
+    }
+    fn part_2((times, distances): &Self::Input<'_>) -> Result<Self::Answer2> {
+        // ignore spaces between numbers
+        let time = String::from_iter(times.split_ascii_whitespace()).parse::<f64>()?;
+        let distance = String::from_iter(distances.split_ascii_whitespace()).parse::<f64>()?;
+        let race = RaceParam::new(time, distance);
         let (time_1, time_2) = race.calc_times();
-        result_part1 *= time_2 - time_1 + 1;
+        Ok(time_2 - time_1 + 1)
+    }
+    fn generate(rng: &mut InputRng, size: usize) -> Result<String> {
+        // part 2 joins numbers of all races, which must fit into f64
+        if !(1..=4).contains(&size) {
+            return Err(anyhow!("1 to 4 races"));
+        }
+        let mut times = String::from("Time:    ");
+        let mut distances = String::from("Distance:");
+        for _ in 0..size {
+            let time = rng.gen_range(10..100_u64);
+            // record is reached by holding button shorter than optimum, therefore it can be beaten
+            let hold = rng.gen_range(1..(time - 1) / 2);
+            times.push_str(&format!(" {:>6}", time));
+            distances.push_str(&format!(" {:>6}", hold * (time - hold)));
+        }
+        Ok(format!("{}\n{}\n", times, distances))
     }
-    println!("result day 06 part 1: {}", result_part1);
-    assert_eq!(result_part1, 345_015);
+}
+
+#[cfg(test)]
+mod tests {
 
-    // part 2
-    let mut input_iter = input.lines();
-    let time = String::from_iter(
-        input_iter
-            .next()
-            .ok_or(anyhow!("bad input"))?
-            .split_once(':')
-            .ok_or(anyhow!("bad input"))?
-            .1
-            .trim()
-            .split_ascii_whitespace(),
-    )
-    .parse::<f64>()?;
-    let distance = String::from_iter(
-        input_iter
-            .next()
-            .ok_or(anyhow!("bad input"))?
-            .split_once(':')
-            .ok_or(anyhow!("bad input"))?
-            .1
-            .trim()
-            .split_ascii_whitespace(),
-    )
-    .parse::<f64>()?;
-    let race = RaceParam::new(time, distance);
-    let (time_1, time_2) = race.calc_times();
-    let result_part2 = time_2 - time_1 + 1;
-    println!("result day 06 part 2: {}", result_part2);
-    assert_eq!(result_part2, 42_588_603);
+    use super::*;
 
-    Ok(())
+    #[test]
+    fn test_example() -> Result<()> {
+        let input = "Time:      7  15   30\n\
+                           Distance:  9  40  200";
+        let races = Day06::parse(input)?;
+        assert_eq!(Day06::part_1(&races)?, 288);
+        assert_eq!(Day06::part_2(&races)?, 71503);
+        Ok(())
+    }
 }
@@ -1,6 +1,10 @@
 //!day_07.rs
 
+use crate::generate::{self, InputRng};
+use crate::parse::{self, ParseError};
+use crate::solution::Solution;
 use anyhow::Result;
+use rand::Rng;
 use std::cmp::Ordering;
 
 // Playing Card definitions
@@ -52,9 +56,11 @@ impl PlayingCard for NoJokers {
     }
 }
 
-impl From<char> for NoJokers {
-    fn from(value: char) -> Self {
-        match value {
+impl TryFrom<char> for NoJokers {
+    type Error = ParseError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        Ok(match value {
             '2' => NoJokers::Two,
             '3' => NoJokers::Three,
             '4' => NoJokers::Four,
@@ -68,8 +74,8 @@ impl From<char> for NoJokers {
             'Q' => NoJokers::Queen,
             'K' => NoJokers::King,
             'A' => NoJokers::Ace,
-            _ => panic!("invalid card char"),
-        }
+            _ => return Err(ParseError::char(value, "card")),
+        })
     }
 }
 
@@ -114,9 +120,11 @@ impl PlayingCard for HasJokers {
     }
 }
 
-impl From<char> for HasJokers {
-    fn from(value: char) -> Self {
-        match value {
+impl TryFrom<char> for HasJokers {
+    type Error = ParseError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        Ok(match value {
             'J' => HasJokers::Joker,
             '2' => HasJokers::Two,
             '3' => HasJokers::Three,
@@ -130,8 +138,8 @@ impl From<char> for HasJokers {
             'Q' => HasJokers::Queen,
             'K' => HasJokers::King,
             'A' => HasJokers::Ace,
-            _ => panic!("invalid card char"),
-        }
+            _ => return Err(ParseError::char(value, "card")),
+        })
     }
 }
 
@@ -213,22 +221,38 @@ impl HandType {
 }
 
 #[derive(Default, Eq)]
-struct CardHand<C: PlayingCard + PartialEq + Eq + PartialOrd + Ord + Default + From<char>> {
+struct CardHand<
+    C: PlayingCard + PartialEq + Eq + PartialOrd + Ord + Default + TryFrom<char, Error = ParseError>,
+> {
     hand: [C; 5],
     bid: u64,
     hand_type: HandType,
 }
 
-impl<C: PlayingCard + PartialEq + Eq + PartialOrd + Ord + Default + From<char>> PartialEq
-    for CardHand<C>
+impl<
+        C: PlayingCard
+            + PartialEq
+            + Eq
+            + PartialOrd
+            + Ord
+            + Default
+            + TryFrom<char, Error = ParseError>,
+    > PartialEq for CardHand<C>
 {
     fn eq(&self, other: &Self) -> bool {
         self.hand == other.hand
     }
 }
 
-impl<C: PlayingCard + PartialEq + Eq + PartialOrd + Ord + Default + From<char>> Ord
-    for CardHand<C>
+impl<
+        C: PlayingCard
+            + PartialEq
+            + Eq
+            + PartialOrd
+            + Ord
+            + Default
+            + TryFrom<char, Error = ParseError>,
+    > Ord for CardHand<C>
 {
     fn cmp(&self, other: &Self) -> Ordering {
         match self.hand_type.cmp(&other.hand_type) {
@@ -248,73 +272,118 @@ impl<C: PlayingCard + PartialEq + Eq + PartialOrd + Ord + Default + From<char>>
     }
 }
 
-impl<C: PlayingCard + PartialEq + Eq + PartialOrd + Ord + Default + From<char>> PartialOrd
-    for CardHand<C>
+impl<
+        C: PlayingCard
+            + PartialEq
+            + Eq
+            + PartialOrd
+            + Ord
+            + Default
+            + TryFrom<char, Error = ParseError>,
+    > PartialOrd for CardHand<C>
 {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
     }
 }
 
-impl<C: PlayingCard + PartialEq + Eq + PartialOrd + Ord + Default + From<char>> CardHand<C> {
-    fn new(hand: &str, bid: u64) -> Self {
+impl<
+        C: PlayingCard
+            + PartialEq
+            + Eq
+            + PartialOrd
+            + Ord
+            + Default
+            + TryFrom<char, Error = ParseError>,
+    > CardHand<C>
+{
+    fn new(hand: &str, bid: u64) -> Result<Self, ParseError> {
         if hand.chars().count() != 5 {
-            panic!("invalid hand");
+            return Err(ParseError::new(hand, "hand of 5 cards"));
         }
         let mut result = Self::default();
-        for (i, c) in hand.chars().map(|c| C::from(c)).enumerate() {
-            result.hand[i] = c;
+        for (i, (index, c)) in hand.char_indices().enumerate() {
+            result.hand[i] = C::try_from(c).map_err(|e| e.at(&hand[index..]))?;
         }
         result.bid = bid;
         result.hand_type = HandType::from_hand(&result.hand);
 
-        result
+        Ok(result)
     }
 }
 
-pub fn day_07() -> Result<()> {
-    let input = include_str!("../../assets/day_07.txt");
-    // part 1
-    let mut card_hands: Vec<CardHand<NoJokers>> = input
-        .lines()
-        .map(|l| {
-            let (hand, bid) = l.split_once(' ').unwrap();
-            CardHand::new(hand, bid.parse::<u64>().unwrap())
-        })
-        .collect();
-    card_hands.sort();
+/// Camel Cards: total winnings of hands without (part 1) and with jokers (part 2)
+pub struct Day07;
 
-    let mut result_part1: u64 = 0;
-    for (rank, bid) in card_hands
-        .iter()
-        .enumerate()
-        .map(|(r, b)| (1_u64 + r as u64, b.bid))
-    {
-        result_part1 += rank * bid;
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+    const GEN_SIZE: (usize, &'static str) = (1_000, "number of hands");
+    /// hands and bids
+    type Input<'a> = Vec<(&'a str, u64)>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        input
+            .lines()
+            .map(|l| {
+                let (hand, bid) = parse::split_once(l, " ")?;
+                Ok((hand, parse::number::<u64>(bid)?))
+            })
+            .collect()
+    }
+    fn part_1(input: &Self::Input<'_>) -> Result<Self::Answer1> {
+        Ok(total_winnings::<NoJokers>(input)?)
     }
-    println!("result day 07 part 1: {}", result_part1);
-    assert_eq!(result_part1, 251_927_063);
+    fn part_2(input: &Self::Input<'_>) -> Result<Self::Answer2> {
+        Ok(total_winnings::<HasJokers>(input)?)
+    }
+    fn generate(rng: &mut InputRng, size: usize) -> Result<String> {
+        let hands = generate::names(rng, size, 5, b"23456789TJQKA", |_| true)?;
+        Ok(hands
+            .iter()
+            .map(|hand| format!("{} {}\n", hand, rng.gen_range(1..=1_000)))
+            .collect())
+    }
+}
 
-    // part 2
-    let mut card_hands: Vec<CardHand<HasJokers>> = input
-        .lines()
-        .map(|l| {
-            let (hand, bid) = l.split_once(' ').unwrap();
-            CardHand::new(hand, bid.parse::<u64>().unwrap())
-        })
-        .collect();
+fn total_winnings<
+    C: PlayingCard + PartialEq + Eq + PartialOrd + Ord + Default + TryFrom<char, Error = ParseError>,
+>(
+    hands_and_bids: &[(&str, u64)],
+) -> Result<u64, ParseError> {
+    let mut card_hands: Vec<CardHand<C>> = hands_and_bids
+        .iter()
+        .map(|(hand, bid)| CardHand::new(hand, *bid))
+        .collect::<Result<_, _>>()?;
     card_hands.sort();
 
-    let mut result_part2: u64 = 0;
+    let mut total_winnings: u64 = 0;
     for (rank, bid) in card_hands
         .iter()
         .enumerate()
         .map(|(r, b)| (1_u64 + r as u64, b.bid))
     {
-        result_part2 += rank * bid;
+        total_winnings += rank * bid;
     }
-    println!("result day 07 part 2: {}", result_part2);
-    assert_eq!(result_part2, 255_632_664);
+    Ok(total_winnings)
+}
 
-    Ok(())
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_example() -> Result<()> {
+        let input = "32T3K 765\n\
+                           T55J5 684\n\
+                           KK677 28\n\
+                           KTJJT 220\n\
+                           QQQJA 483";
+        let hands = Day07::parse(input)?;
+        assert_eq!(Day07::part_1(&hands)?, 6440);
+        assert_eq!(Day07::part_2(&hands)?, 5905);
+        Ok(())
+    }
 }
@@ -1,10 +1,16 @@
 //!day_08.RS
 
-use anyhow::Result;
+use crate::generate::{self, InputRng};
+use crate::my_lib::my_cycle::find_cycle;
+use crate::parse::{self, ParseError};
+use crate::solution::Solution;
+use anyhow::{anyhow, Result};
 use num::integer::lcm;
+use rand::{seq::SliceRandom, Rng};
 use std::collections::HashMap;
 
-struct Instructions {
+/// left and right node of a node
+pub struct Instructions {
     left: String,
     right: String,
 }
@@ -22,76 +28,230 @@ impl Instructions {
     }
 }
 
-pub fn day_08() -> Result<()> {
-    let input = include_str!("../../assets/day_08.txt");
-    let mut lines = input.lines().filter(|l| !l.is_empty());
-    let directions = lines.next().unwrap();
-    let mut map: HashMap<String, Instructions> = HashMap::new();
-    for line in lines {
-        let (key, value) = line.split_once('=').unwrap();
-        let trim_value = |c: char| !c.is_alphabetic();
-        let (left, right) = value.trim_matches(trim_value).split_once(',').unwrap();
-        map.insert(
-            key.trim().to_string(),
-            Instructions::new(left.trim().to_string(), right.trim().to_string()),
-        );
-    }
+/// steps of ghost from start_key to a node ending with `Z`, if ghost is on such a node exactly
+/// after each multiple of these steps
+fn ghost_cycle(
+    directions: &str,
+    map: &HashMap<String, Instructions>,
+    start_key: &str,
+) -> Result<u64> {
+    let directions: Vec<bool> = directions.chars().map(|c| c == 'R').collect();
+    // state is current node and index of next direction
+    let ghost_path = find_cycle((start_key.to_string(), 0), |(key, index)| {
+        let next_key = map
+            .get(key)
+            .ok_or_else(|| anyhow!("unknown node `{}`", key))?
+            .direction(directions[*index]);
+        Ok::<_, anyhow::Error>((next_key.to_owned(), (index + 1) % directions.len()))
+    })?;
+    ghost_path
+        .periodic_step(|(key, _)| key.ends_with('Z'))
+        .map(|steps| steps as u64)
+        .ok_or_else(|| {
+            anyhow!(
+                "ghost from `{}` does not reach end nodes in a fixed cycle",
+                start_key
+            )
+        })
+}
 
-    // part 1
-    let mut current_key = String::from("AAA");
-    let mut steps: u64 = 0;
-    // direction: false -> left; true -> right
-    for direction in directions.chars().map(|c| c == 'R').cycle() {
-        steps += 1;
-        current_key = map
-            .get(&current_key)
-            .unwrap()
-            .direction(direction)
-            .to_owned();
-        if current_key == "ZZZ" {
-            break;
-        }
-    }
-    println!("result day 08 part 1: {}", steps);
-    assert_eq!(steps, 13_301);
+/// Haunted Wasteland: steps from `AAA` to `ZZZ` (part 1) and of all ghosts at once (part 2)
+pub struct Day08;
 
-    // part 2
-    // hint for solution: every path from each starting node to it's corresponding ending node
-    // cycles in a fixed cycle, respectivly.
-    // the solution is to identify the cycles and than calc the Lowest Common Multiple (LCM) of them
-    let current_keys: Vec<String> = map
-        .keys()
-        .filter(|k| k.ends_with('A'))
-        .map(|k| k.to_owned())
-        .collect();
-    let mut steps_per_key: Vec<u64> = Vec::with_capacity(current_keys.len());
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+    const GEN_SIZE: (usize, &'static str) = (6, "number of start nodes");
+    /// directions and map of nodes
+    type Input<'a> = (&'a str, HashMap<String, Instructions>);
+    type Answer1 = u64;
+    type Answer2 = u64;
 
-    for start_key in current_keys.iter() {
-        current_key = start_key.clone();
-        steps = 0;
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        let mut lines = input.lines().filter(|l| !l.is_empty());
+        let directions = parse::next_token(&mut lines, input, "directions")?;
+        if let Some(index) = directions.find(|c| c != 'L' && c != 'R') {
+            return Err(ParseError::new(&directions[index..], "`L` or `R`").into());
+        }
+        let mut map: HashMap<String, Instructions> = HashMap::new();
+        for line in lines {
+            let (key, value) = parse::split_once(line, "=")?;
+            let trim_value = |c: char| !c.is_alphanumeric();
+            let (left, right) = parse::split_once(value.trim_matches(trim_value), ",")?;
+            map.insert(
+                key.trim().to_string(),
+                Instructions::new(left.trim().to_string(), right.trim().to_string()),
+            );
+        }
+        Ok((directions, map))
+    }
+    fn part_1((directions, map): &Self::Input<'_>) -> Result<Self::Answer1> {
+        let mut current_key = String::from("AAA");
+        let mut steps: u64 = 0;
         // direction: false -> left; true -> right
         for direction in directions.chars().map(|c| c == 'R').cycle() {
             steps += 1;
             current_key = map
                 .get(&current_key)
-                .unwrap()
+                .ok_or_else(|| anyhow!("unknown node `{}`", current_key))?
                 .direction(direction)
                 .to_owned();
+            if current_key == "ZZZ" {
+                break;
+            }
+        }
+        Ok(steps)
+    }
+    // hint for solution: every path from each starting node to it's corresponding ending node
+    // cycles in a fixed cycle, respectivly.
+    // the solution is to identify the cycles and than calc the Lowest Common Multiple (LCM) of them.
+    // ghost_cycle fails, if a path does not cycle this way.
+    fn part_2((directions, map): &Self::Input<'_>) -> Result<Self::Answer2> {
+        let mut start_keys: Vec<&String> = map.keys().filter(|k| k.ends_with('A')).collect();
+        if start_keys.is_empty() {
+            return Err(anyhow!("no start node ending with `A`"));
+        }
+        start_keys.sort();
+        let mut lcm_step = 1;
+        for start_key in start_keys {
+            lcm_step = lcm(lcm_step, ghost_cycle(directions, map, start_key)?);
+        }
+        Ok(lcm_step)
+    }
+    fn generate(rng: &mut InputRng, size: usize) -> Result<String> {
+        if size == 0 {
+            return Err(anyhow!("at least one start node"));
+        }
+        let directions: String = (0..rng.gen_range(5..=20))
+            .map(|_| if rng.gen_bool(0.5) { 'L' } else { 'R' })
+            .collect();
+        let cycles: Vec<usize> = (0..size).map(|_| rng.gen_range(5..=100)).collect();
+        const LETTERS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+        // first start node is AAA with end node ZZZ
+        let mut prefixes = vec![String::from("AA")];
+        prefixes.extend(generate::names(rng, size - 1, 2, LETTERS, |n| {
+            n != "AA" && n != "ZZ"
+        })?);
+        let num_inner_nodes = cycles.iter().map(|c| 2 * (c - 1)).sum();
+        let mut inner_nodes = generate::names(rng, num_inner_nodes, 3, LETTERS, |n| {
+            !n.ends_with('A') && !n.ends_with('Z')
+        })?
+        .into_iter();
+        // each cycle has two nodes per step, which both lead to both nodes of next step.
+        // Start and end node lead to first step and last step leads to end node, therefore
+        // every path from start node reaches end node after cycle steps and repeats.
+        let mut lines = Vec::new();
+        for (prefix, cycle) in prefixes.iter().zip(cycles) {
+            let (start_node, end_node) = if prefix == "AA" {
+                (String::from("AAA"), String::from("ZZZ"))
+            } else {
+                (format!("{}A", prefix), format!("{}Z", prefix))
+            };
+            let steps: Vec<(String, String)> = (1..cycle)
+                .map(|_| (inner_nodes.next().unwrap(), inner_nodes.next().unwrap()))
+                .collect();
+            for node in [&start_node, &end_node] {
+                lines.push(format!("{} = ({}, {})", node, steps[0].0, steps[0].1));
+            }
+            for (index, (left, right)) in steps.iter().enumerate() {
+                let (next_left, next_right) = match steps.get(index + 1) {
+                    Some((l, r)) => (l, r),
+                    None => (&end_node, &end_node),
+                };
+                lines.push(format!("{} = ({}, {})", left, next_left, next_right));
+                lines.push(format!("{} = ({}, {})", right, next_right, next_left));
+            }
+        }
+        lines.shuffle(rng);
+        Ok(format!("{}\n\n{}\n", directions, lines.join("\n")))
+    }
+}
 
-            if current_key.ends_with('Z') {
+#[cfg(test)]
+mod reference {
+
+    use super::*;
+
+    /// moves all ghosts at once, until all of them are at a node ending with `Z`
+    pub fn part_2(input: &str) -> Result<u64> {
+        let (directions, map) = Day08::parse(input)?;
+        let mut current_keys: Vec<&String> = map.keys().filter(|k| k.ends_with('A')).collect();
+        if current_keys.is_empty() {
+            return Err(anyhow!("no start node ending with `A`"));
+        }
+        let mut steps: u64 = 0;
+        for direction in directions.chars().map(|c| c == 'R').cycle() {
+            steps += 1;
+            for current_key in current_keys.iter_mut() {
+                *current_key = map
+                    .get(*current_key)
+                    .ok_or_else(|| anyhow!("unknown node `{}`", current_key))?
+                    .direction(direction);
+            }
+            if current_keys.iter().all(|k| k.ends_with('Z')) {
                 break;
             }
         }
-        steps_per_key.push(steps);
+        Ok(steps)
     }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::differential;
 
-    let mut step_iter = steps_per_key.iter();
-    let mut lcm_step = *step_iter.next().unwrap();
-    for next_key in step_iter {
-        lcm_step = lcm(lcm_step, *next_key);
+    #[test]
+    fn test_example_part1() -> Result<()> {
+        let input = "RL\n\
+                           \n\
+                           AAA = (BBB, CCC)\n\
+                           BBB = (DDD, EEE)\n\
+                           CCC = (ZZZ, GGG)\n\
+                           DDD = (DDD, DDD)\n\
+                           EEE = (EEE, EEE)\n\
+                           GGG = (GGG, GGG)\n\
+                           ZZZ = (ZZZ, ZZZ)";
+        let network = Day08::parse(input)?;
+        assert_eq!(Day08::part_1(&network)?, 2);
+        let input = "LLR\n\
+                           \n\
+                           AAA = (BBB, BBB)\n\
+                           BBB = (AAA, ZZZ)\n\
+                           ZZZ = (ZZZ, ZZZ)";
+        let network = Day08::parse(input)?;
+        assert_eq!(Day08::part_1(&network)?, 6);
+        Ok(())
     }
-    println!("result day 08 part 2: {}", lcm_step);
-    assert_eq!(lcm_step, 7_309_459_565_207);
 
-    Ok(())
+    #[test]
+    fn test_example_part2() -> Result<()> {
+        let input = "LR\n\
+                           \n\
+                           11A = (11B, XXX)\n\
+                           11B = (XXX, 11Z)\n\
+                           11Z = (11B, XXX)\n\
+                           22A = (22B, XXX)\n\
+                           22B = (22C, 22C)\n\
+                           22C = (22Z, 22Z)\n\
+                           22Z = (22B, 22B)\n\
+                           XXX = (XXX, XXX)";
+        let network = Day08::parse(input)?;
+        assert_eq!(Day08::part_2(&network)?, 6);
+        assert_eq!(reference::part_2(input)?, 6);
+        Ok(())
+    }
+
+    #[test]
+    fn test_differential_part_2() -> Result<()> {
+        if let Some(divergence) = differential::first_divergence(
+            0..10,
+            |rng| Day08::generate(rng, 3),
+            |input| Day08::part_2(&Day08::parse(input)?),
+            reference::part_2,
+        )? {
+            panic!("day 08 part 2: {}", divergence);
+        }
+        Ok(())
+    }
 }
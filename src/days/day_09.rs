@@ -1,11 +1,17 @@
 //!day_09.rs
 
-use anyhow::Result;
+use crate::generate::InputRng;
+use crate::parse;
+use crate::solution::Solution;
+use anyhow::{anyhow, Context, Result};
+use rand::Rng;
 
 // solution hint: use a recursive algorithmen
-fn calc_next_in_sequence(sequence: &mut Vec<i64>) {
-    if sequence.len() == 1 {
-        panic!("day 09: sequence reduced to length 1");
+fn calc_next_in_sequence(sequence: &mut Vec<i64>) -> Result<()> {
+    if sequence.len() < 2 {
+        return Err(anyhow!(
+            "differences of sequence do not become zero before sequence has less than two values"
+        ));
     }
     let mut delta_sequence: Vec<i64> = Vec::with_capacity(sequence.len() - 1);
     let mut sequence_iter = sequence.iter();
@@ -20,7 +26,7 @@ fn calc_next_in_sequence(sequence: &mut Vec<i64>) {
         delta_sequence.push(0);
         delta_sequence.push(0);
     } else {
-        calc_next_in_sequence(&mut delta_sequence);
+        calc_next_in_sequence(&mut delta_sequence)?;
     }
     let last_delta = *delta_sequence.last().unwrap();
     let last = *sequence.last().unwrap();
@@ -28,26 +34,96 @@ fn calc_next_in_sequence(sequence: &mut Vec<i64>) {
     let first_delta = delta_sequence[0];
     let first = sequence[0];
     sequence.insert(0, first - first_delta);
+    Ok(())
 }
 
-pub fn day_09() -> Result<()> {
-    let input = include_str!("../../assets/day_09.txt");
-
-    let mut result_part1: i64 = 0;
-    let mut result_part2: i64 = 0;
-    for line in input.lines() {
-        let mut sequence: Vec<i64> = line
-            .split_whitespace()
-            .map(|v| v.parse::<i64>().expect("bad input"))
-            .collect();
-        calc_next_in_sequence(&mut sequence);
-        result_part1 += *sequence.last().unwrap();
-        result_part2 += sequence[0];
-    }
-    println!("result day 09 part 1: {}", result_part1);
-    assert_eq!(result_part1, 2_038_472_161);
-    println!("result day 09 part 2: {}", result_part2);
-    assert_eq!(result_part2, 1_091);
+/// Mirage Maintenance: sum of next (part 1) and of previous values of sequences (part 2)
+pub struct Day09;
 
-    Ok(())
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+    const GEN_SIZE: (usize, &'static str) = (200, "number of sequences");
+    type Input<'a> = Vec<Vec<i64>>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        let sequences = input
+            .lines()
+            .map(|line| {
+                line.split_whitespace()
+                    .map(parse::number::<i64>)
+                    .collect::<Result<_, _>>()
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(sequences)
+    }
+    fn part_1(input: &Self::Input<'_>) -> Result<Self::Answer1> {
+        let mut result_part1: i64 = 0;
+        for (index, sequence) in input.iter().enumerate() {
+            let mut sequence = sequence.clone();
+            calc_next_in_sequence(&mut sequence)
+                .with_context(|| format!("sequence {}", index + 1))?;
+            result_part1 += *sequence.last().unwrap();
+        }
+        Ok(result_part1)
+    }
+    fn part_2(input: &Self::Input<'_>) -> Result<Self::Answer2> {
+        let mut result_part2: i64 = 0;
+        for (index, sequence) in input.iter().enumerate() {
+            let mut sequence = sequence.clone();
+            calc_next_in_sequence(&mut sequence)
+                .with_context(|| format!("sequence {}", index + 1))?;
+            result_part2 += sequence[0];
+        }
+        Ok(result_part2)
+    }
+    fn generate(rng: &mut InputRng, size: usize) -> Result<String> {
+        let mut input = String::new();
+        for _ in 0..size {
+            // integrate constant sequence degree times, which results in polynomial sequence
+            let degree = rng.gen_range(0..=6);
+            let mut sequence = vec![rng.gen_range(-5..=5_i64); 21 - degree];
+            for _ in 0..degree {
+                let mut value = rng.gen_range(-20..=20);
+                let mut integrated = vec![value];
+                for delta in sequence {
+                    value += delta;
+                    integrated.push(value);
+                }
+                sequence = integrated;
+            }
+            let sequence: Vec<String> = sequence.iter().map(|v| v.to_string()).collect();
+            input.push_str(&sequence.join(" "));
+            input.push('\n');
+        }
+        Ok(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_example() -> Result<()> {
+        let input = "0 3 6 9 12 15\n\
+                           1 3 6 10 15 21\n\
+                           10 13 16 21 30 45";
+        let history = Day09::parse(input)?;
+        assert_eq!(Day09::part_1(&history)?, 114);
+        assert_eq!(Day09::part_2(&history)?, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_sequence_without_zero_differences() -> Result<()> {
+        assert!(Day09::part_1(&Day09::parse("5")?).is_err());
+        let history = Day09::parse("0 3 6\n1 2 4 8")?;
+        let err = Day09::part_1(&history).unwrap_err();
+        assert_eq!(err.to_string(), "sequence 2");
+        assert!(Day09::part_2(&history).is_err());
+        Ok(())
+    }
 }
@@ -1,15 +1,15 @@
 //!day_10.rs
 
+use crate::generate::{self, InputRng};
+use crate::my_lib::my_compass::Compass;
+use crate::my_lib::my_map_point::{MapPoint, MapSize};
+use crate::my_lib::my_map_two_dim::MyMap2D;
+use crate::parse::{self, ParseError};
+use crate::render::{Image, Rgb};
+use crate::solution::Solution;
 use anyhow::{anyhow, Result};
-use my_lib::my_compass::Compass;
-use my_lib::my_map_point::MapPoint;
-use my_lib::my_map_two_dim::MyMap2D;
-
-// values taken from ../../assets/day_10.txt
-// number of chars in one line
-const X: usize = 140;
-// number of lines
-const Y: usize = 140;
+use rand::Rng;
+use std::cmp::Ordering;
 
 #[derive(Copy, Clone, PartialEq, Default)]
 enum PipeSegment {
@@ -26,22 +26,24 @@ impl PipeSegment {
     }
 }
 
-#[derive(Copy, Clone, PartialEq)]
+#[derive(Clone, PartialEq)]
 struct Pipe {
-    layout: MyMap2D<PipeSegment, 3, 3>,
+    layout: MyMap2D<PipeSegment>,
 }
 
 impl Default for Pipe {
     fn default() -> Self {
         let mut pipe = Pipe {
-            layout: MyMap2D::default(),
+            layout: MyMap2D::init(MapSize::new(3, 3), PipeSegment::None),
         };
         pipe.layout.set(Pipe::center(), PipeSegment::Pipe);
         pipe
     }
 }
 
-impl From<char> for Pipe {
+impl TryFrom<char> for Pipe {
+    type Error = ParseError;
+
     // pipe types
     // | is a vertical pipe connecting north and south.
     // - is a horizontal pipe connecting east and west.
@@ -51,7 +53,7 @@ impl From<char> for Pipe {
     // F is a 90-degree bend connecting south and east.
     // . is ground; there is no pipe in this tile --> is NOT PIPE!
     // S is the starting position of the animal; there is a pipe on this tile, but your sketch doesn't show what shape the pipe has.
-    fn from(value: char) -> Self {
+    fn try_from(value: char) -> Result<Self, Self::Error> {
         let mut pipe = Pipe::default();
         match value {
             '|' => {
@@ -79,15 +81,15 @@ impl From<char> for Pipe {
                 pipe.set(Compass::E, PipeSegment::Pipe).unwrap();
             }
             'S' => (),
-            _ => panic!("bad pipe char"),
+            _ => return Err(ParseError::char(value, "pipe")),
         }
-        pipe
+        Ok(pipe)
     }
 }
 
 impl Pipe {
-    fn center() -> MapPoint<3, 3> {
-        MapPoint::<3, 3>::new(1, 1)
+    fn center() -> MapPoint {
+        MapPoint::new(1, 1)
     }
     fn from_neighbours(neighbours: &[(Option<char>, Compass)]) -> Result<Pipe> {
         // if neighbours.len() is > 4,
@@ -96,7 +98,11 @@ impl Pipe {
         }
         let mut pipe = Pipe::default();
         let mut found_gates: usize = 0;
-        for (op, o) in neighbours.iter().map(|(c, o)| (c.map(Pipe::from), o)) {
+        // ground has no gates
+        for (op, o) in neighbours
+            .iter()
+            .map(|(c, o)| (c.and_then(|c| Pipe::try_from(c).ok()), o))
+        {
             if let Some(neighbor_pipe) = op {
                 if neighbor_pipe.has_gate(o.flip())? {
                     if found_gates == 2 {
@@ -119,7 +125,7 @@ impl Pipe {
         }
         Ok(*self
             .layout
-            .get(Pipe::center().neighbor(orientation).unwrap()))
+            .get(self.layout.neighbor(Pipe::center(), orientation).unwrap()))
     }
     fn get_gates(&self) -> Option<(Compass, Compass)> {
         let mut iter_gates = self
@@ -127,11 +133,8 @@ impl Pipe {
             .iter_neighbors(Pipe::center())
             .filter(|(.., ps)| **ps == PipeSegment::Pipe)
             .map(|(_, o, _)| o);
-        let gate_1 = match iter_gates.next() {
-            Some(gate) => gate,
-            // uninitialized Start_point
-            None => return None,
-        };
+        // uninitialized Start_point returns None
+        let gate_1 = iter_gates.next()?;
         let gate_2 = iter_gates.next().unwrap();
         Some((gate_1, gate_2))
     }
@@ -139,9 +142,10 @@ impl Pipe {
         if !orientation.is_cardinal() {
             return Err(anyhow!("only cardinal orientation allowed"));
         }
-        Ok(*self
-            .layout
-            .set(Pipe::center().neighbor(orientation).unwrap(), segment))
+        Ok(*self.layout.set(
+            self.layout.neighbor(Pipe::center(), orientation).unwrap(),
+            segment,
+        ))
     }
     fn has_gate(&self, orientation: Compass) -> Result<bool> {
         if !orientation.is_cardinal() {
@@ -149,7 +153,7 @@ impl Pipe {
         }
         Ok(*self
             .layout
-            .get(Pipe::center().neighbor(orientation).unwrap())
+            .get(self.layout.neighbor(Pipe::center(), orientation).unwrap())
             == PipeSegment::Pipe)
     }
     fn init_pipe_sides(&mut self) -> Result<Compass> {
@@ -160,7 +164,7 @@ impl Pipe {
         self.set_pipe_side(exit_gate, current_side);
         Ok(exit_gate)
     }
-    fn extend_pipe_sides(&mut self, flow_direction: Compass, previous: Pipe) -> Result<()> {
+    fn extend_pipe_sides(&mut self, flow_direction: Compass, previous: &Pipe) -> Result<()> {
         // flow_direction points toward tile from previous; flip() to gate initial entry gate
         let entry_gate = flow_direction.flip();
         // safety check
@@ -181,7 +185,10 @@ impl Pipe {
         Ok(())
     }
     fn set_pipe_side(&mut self, initial_orientation: Compass, mut current_side: PipeSegment) {
-        for (segment, _) in Pipe::center().iter_neighbors(initial_orientation, true, false, true) {
+        let size = self.layout.size();
+        for (segment, _) in
+            Pipe::center().iter_neighbors(initial_orientation, true, false, true, size)
+        {
             if *self.layout.get(segment) == PipeSegment::Pipe {
                 current_side = match current_side {
                     PipeSegment::LeftSide => PipeSegment::RightSide,
@@ -199,7 +206,7 @@ impl Pipe {
     }
 }
 
-#[derive(Copy, Clone, PartialEq)]
+#[derive(Clone, PartialEq)]
 enum Tile {
     Unknown(char),
     Pipe(Pipe),
@@ -213,9 +220,14 @@ impl Default for Tile {
     }
 }
 
-impl From<char> for Tile {
-    fn from(value: char) -> Self {
-        Tile::Unknown(value)
+impl TryFrom<char> for Tile {
+    type Error = ParseError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '|' | '-' | 'L' | 'J' | '7' | 'F' | '.' | 'S' => Ok(Tile::Unknown(value)),
+            _ => Err(ParseError::char(value, "pipe, `.` or `S`")),
+        }
     }
 }
 
@@ -230,7 +242,7 @@ impl Tile {
         match self {
             Tile::Unknown(c) => {
                 if *c == 'S' {
-                    *self = Tile::Pipe(pipe);
+                    *self = Tile::Pipe(pipe.clone());
                     Ok(pipe)
                 } else {
                     Err(anyhow!("Tile is not start tile"))
@@ -243,11 +255,11 @@ impl Tile {
     fn change_to_pipe(&mut self) -> Result<Pipe> {
         match self {
             Tile::Unknown(c) => {
-                let pipe = Pipe::from(*c);
-                *self = Tile::Pipe(pipe);
+                let pipe = Pipe::try_from(*c)?;
+                *self = Tile::Pipe(pipe.clone());
                 Ok(pipe)
             }
-            Tile::Pipe(pipe) => Ok(*pipe),
+            Tile::Pipe(pipe) => Ok(pipe.clone()),
             _ => Err(anyhow!("unable to convert to pipe.")),
         }
     }
@@ -279,47 +291,52 @@ impl Tile {
     }
     fn is_side_tile(&self) -> Option<Self> {
         match self {
-            Tile::LeftOfPipe | Tile::RightOfPipe => Some(*self),
+            Tile::LeftOfPipe | Tile::RightOfPipe => Some(self.clone()),
             _ => None,
         }
     }
 }
 
-#[derive(Copy, Clone, Default)]
-struct TileMap<const X: usize, const Y: usize> {
-    map: MyMap2D<Tile, X, Y>,
-    start_tile: MapPoint<X, Y>,
+/// tiles with pipe loop from start tile
+#[derive(Clone, Default)]
+pub struct TileMap {
+    map: MyMap2D<Tile>,
+    start_tile: MapPoint,
     number_of_pipe_tiles: usize,
     outside: Option<Tile>,
 }
 
-impl<const X: usize, const Y: usize> From<&str> for TileMap<X, Y> {
-    fn from(value: &str) -> Self {
-        let mut map = TileMap::<X, Y>::default();
-        for (y, line) in value.trim().lines().enumerate() {
-            for (x, pipe_char) in line.trim().chars().enumerate() {
-                *map.map.get_mut(MapPoint::<X, Y>::new(x, y)) = Tile::from(pipe_char);
-                if pipe_char == 'S' {
-                    map.start_tile = MapPoint::<X, Y>::new(x, y);
-                }
-            }
-        }
-        map
+impl TryFrom<&str> for TileMap {
+    type Error = ParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let mut map = TileMap {
+            map: parse::grid(value)?,
+            ..Default::default()
+        };
+        map.start_tile = map
+            .map
+            .iter()
+            .find(|(_, t)| t.get_unknown_tile_char() == Some('S'))
+            .map(|(p, _)| p)
+            .ok_or_else(|| ParseError::new(&value[value.len()..], "start tile `S`"))?;
+        Ok(map)
     }
 }
 
-impl<const X: usize, const Y: usize> TileMap<X, Y> {
+impl TileMap {
     fn get_number_of_pipe_tiles(&self) -> usize {
         self.number_of_pipe_tiles
     }
-    fn set_start_pipe(&mut self) -> Result<(MapPoint<X, Y>, Compass)> {
+    fn set_start_pipe(&mut self) -> Result<(MapPoint, Compass)> {
         let neighbor_tiles: Vec<(Option<char>, Compass)> = self
             .map
             .iter_neighbors(self.start_tile)
             .map(|(_, o, t)| (t.get_unknown_tile_char(), o))
             .collect();
         let start_pipe = Pipe::from_neighbours(&neighbor_tiles[..])?;
-        self.map
+        let start_pipe = self
+            .map
             .get_mut(self.start_tile)
             .set_start_pipe(start_pipe)?;
         // add start pipe to number of pipes
@@ -331,24 +348,20 @@ impl<const X: usize, const Y: usize> TileMap<X, Y> {
         let next_tile = self.flow_to_next_tile(self.start_tile, flow_direction)?;
         Ok((next_tile, flow_direction))
     }
-    fn is_start_pipe(&self, current_tile: MapPoint<X, Y>) -> bool {
+    fn is_start_pipe(&self, current_tile: MapPoint) -> bool {
         self.start_tile == current_tile
     }
     fn flow_to_next_tile(
         &self,
-        current_tile: MapPoint<X, Y>,
+        current_tile: MapPoint,
         flow_direction: Compass,
-    ) -> Result<MapPoint<X, Y>> {
+    ) -> Result<MapPoint> {
         // flow_direction points from current tile toward next tile with pipe
-        current_tile
-            .neighbor(flow_direction)
+        self.map
+            .neighbor(current_tile, flow_direction)
             .ok_or(anyhow!("flow direction points outside of map"))
     }
-    fn tile_to_pipe(
-        &mut self,
-        current_tile: MapPoint<X, Y>,
-        flow_direction: Compass,
-    ) -> Result<Compass> {
+    fn tile_to_pipe(&mut self, current_tile: MapPoint, flow_direction: Compass) -> Result<Compass> {
         // unwrap at the is ok, since we know, that without an error in change_to_pipe(), get_gates will return pipe gates
         let (gate_1, gate_2) = self
             .map
@@ -368,11 +381,7 @@ impl<const X: usize, const Y: usize> TileMap<X, Y> {
             Ok(gate_1)
         }
     }
-    fn check_pipe_gate(
-        &self,
-        tile_to_check: MapPoint<X, Y>,
-        flow_direction: Compass,
-    ) -> Result<bool> {
+    fn check_pipe_gate(&self, tile_to_check: MapPoint, flow_direction: Compass) -> Result<bool> {
         // flow_direction points toward tile_to_check
         self.map
             .get(tile_to_check)
@@ -380,23 +389,23 @@ impl<const X: usize, const Y: usize> TileMap<X, Y> {
             .ok_or(anyhow!("tile is not a pipe"))?
             .has_gate(flow_direction.flip())
     }
-    fn init_pipe_sides(&mut self) -> Result<(MapPoint<X, Y>, Compass, Pipe)> {
+    fn init_pipe_sides(&mut self) -> Result<(MapPoint, Compass, Pipe)> {
         let start_pipe = self
             .map
             .get_mut(self.start_tile)
             .get_pipe_mut()
             .ok_or(anyhow!("start tile is not a pipe"))?;
         let flow_direction = start_pipe.init_pipe_sides()?;
-        let start_pipe = *start_pipe;
+        let start_pipe = start_pipe.clone();
         let next_tile = self.flow_to_next_tile(self.start_tile, flow_direction)?;
         self.set_pipe_side_tiles(next_tile)?;
         Ok((next_tile, flow_direction, start_pipe))
     }
     fn extend_pipe_sides(
         &mut self,
-        current_tile: MapPoint<X, Y>,
+        current_tile: MapPoint,
         flow_direction: Compass,
-        previous_pipe: Pipe,
+        previous_pipe: &Pipe,
     ) -> Result<(Compass, Pipe)> {
         self.map
             .get_mut(current_tile)
@@ -404,7 +413,7 @@ impl<const X: usize, const Y: usize> TileMap<X, Y> {
             .ok_or(anyhow!("tile is not a pipe"))?
             .extend_pipe_sides(flow_direction, previous_pipe)?;
         self.set_pipe_side_tiles(current_tile)?;
-        let pipe = *self.map.get(current_tile).get_pipe().unwrap();
+        let pipe = self.map.get(current_tile).get_pipe().unwrap().clone();
         let (gate_1, gate_2) = pipe.get_gates().unwrap();
         let entry_gate = flow_direction.flip();
         if entry_gate != gate_1 && entry_gate != gate_2 {
@@ -415,18 +424,19 @@ impl<const X: usize, const Y: usize> TileMap<X, Y> {
             Ok((gate_1, pipe))
         }
     }
-    fn set_pipe_side_tiles(&mut self, current_tile: MapPoint<X, Y>) -> Result<()> {
-        let pipe = *self
+    fn set_pipe_side_tiles(&mut self, current_tile: MapPoint) -> Result<()> {
+        let pipe = self
             .map
             .get(current_tile)
             .get_pipe()
-            .ok_or(anyhow!("tile is not a pipe"))?;
+            .ok_or(anyhow!("tile is not a pipe"))?
+            .clone();
         for (segment, orientation) in pipe
             .iter_pipe_segments()
             .filter(|(ps, _)| ps.is_side_segment())
         {
             // at sidelines of map neighbor could be None
-            if let Some(neighbor) = current_tile.neighbor(orientation) {
+            if let Some(neighbor) = self.map.neighbor(current_tile, orientation) {
                 match segment {
                     PipeSegment::LeftSide => self.map.get_mut(neighbor).change_to_left_of_pipe(),
                     PipeSegment::RightSide => self.map.get_mut(neighbor).change_to_right_of_pipe(),
@@ -460,12 +470,12 @@ impl<const X: usize, const Y: usize> TileMap<X, Y> {
                     None => break,
                 };
 
-                let mut tiles_to_extend_to: Vec<MapPoint<X, Y>> = vec![tile];
+                let mut tiles_to_extend_to: Vec<MapPoint> = vec![tile];
                 let mut index = 0;
                 while index < tiles_to_extend_to.len() {
                     let check_tile = tiles_to_extend_to[index];
-                    *self.map.get_mut(check_tile) = side_to_extend;
-                    let neighbors_to_extend: Vec<MapPoint<X, Y>> = self
+                    *self.map.get_mut(check_tile) = side_to_extend.clone();
+                    let neighbors_to_extend: Vec<MapPoint> = self
                         .map
                         .iter_neighbors(check_tile)
                         .filter(|(np, _, nt)| {
@@ -480,15 +490,33 @@ impl<const X: usize, const Y: usize> TileMap<X, Y> {
             }
         }
     }
+    // identify left and right side tiles of pipe and check, which one is outside
+    // count inside tiles as result
+    fn mark_sides_count_inside(&mut self) -> Result<usize> {
+        // first set all tiles, which are not pipes and are neighboring to a pipe tile to LeftSide or RightSide
+        // by flowing again through pipe, but this time set pipe sides
+        let (mut current_tile, mut flow_direction, mut previous_pipe) = self.init_pipe_sides()?;
+        while !self.is_start_pipe(current_tile) {
+            let extend_result =
+                self.extend_pipe_sides(current_tile, flow_direction, &previous_pipe)?;
+            flow_direction = extend_result.0;
+            previous_pipe = extend_result.1;
+            current_tile = self.flow_to_next_tile(current_tile, flow_direction)?;
+        }
+
+        // now extend pipe sides to all tiles, which are not directly connected to a pipe tile
+        self.extend_side_tiles();
+        // identify outside and count inside
+        self.identify_outside_count_inside()
+    }
     fn identify_outside_count_inside(&mut self) -> Result<usize> {
         // this works only, if there is at elast one side tile of pipe, which is at side of map
         self.outside = self
             .map
             .iter()
-            .filter(|(p, ..)| p.map_position() != Compass::Center)
-            .find(|(.., t)| t.is_side_tile().is_some())
-            .map(|(.., t)| t.is_side_tile().unwrap());
-        let inside = match self.outside {
+            .filter(|(p, ..)| self.map.map_position(*p) != Compass::Center)
+            .find_map(|(.., t)| t.is_side_tile());
+        let inside = match &self.outside {
             Some(side) => match side {
                 Tile::LeftOfPipe => Tile::RightOfPipe,
                 Tile::RightOfPipe => Tile::LeftOfPipe,
@@ -504,54 +532,170 @@ impl<const X: usize, const Y: usize> TileMap<X, Y> {
     }
 }
 
-pub fn day_10() -> Result<()> {
-    let input = include_str!("../../assets/day_10.txt");
+/// Pipe Maze: steps to farthest tile of loop (part 1) and tiles enclosed by loop (part 2)
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    const GEN_SIZE: (usize, &'static str) = (140, "width and height of tile map");
+    /// tile map with complete pipe
+    type Input<'a> = TileMap;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-    // part 1: build pipe map and count pipe tiles
-    let mut tile_map = TileMap::<X, Y>::from(input);
-    // flow_direction points toward next tile with pipe
-    let (mut current_tile, mut flow_direction) = tile_map.set_start_pipe()?;
-    // safety_counter to prevent infinite loop in case of error
-    let mut safety_counter = X * Y;
-    // let it flow and build the pipe
-    while !tile_map.is_start_pipe(current_tile) && safety_counter > 0 {
-        safety_counter -= 1;
-        flow_direction = tile_map.tile_to_pipe(current_tile, flow_direction)?;
-        current_tile = tile_map.flow_to_next_tile(current_tile, flow_direction)?;
-    }
-    // now current_tile is starting_tile and flow_direction points toward unused gate of starting_tile
-    // check if starting_tile has this gate
-    if !tile_map.check_pipe_gate(current_tile, flow_direction)? {
-        return Err(anyhow!("pipe is broken"));
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        // build pipe map
+        let mut tile_map = TileMap::try_from(input)?;
+        // flow_direction points toward next tile with pipe
+        let (mut current_tile, mut flow_direction) = tile_map.set_start_pipe()?;
+        // safety_counter to prevent infinite loop in case of error
+        let mut safety_counter = tile_map.map.size().len();
+        // let it flow and build the pipe
+        while !tile_map.is_start_pipe(current_tile) && safety_counter > 0 {
+            safety_counter -= 1;
+            flow_direction = tile_map.tile_to_pipe(current_tile, flow_direction)?;
+            current_tile = tile_map.flow_to_next_tile(current_tile, flow_direction)?;
+        }
+        // now current_tile is starting_tile and flow_direction points toward unused gate of starting_tile
+        // check if starting_tile has this gate
+        if !tile_map.check_pipe_gate(current_tile, flow_direction)? {
+            return Err(anyhow!("pipe is broken"));
+        }
+        Ok(tile_map)
+    }
+    // count pipe tiles
+    fn part_1(tile_map: &Self::Input<'_>) -> Result<Self::Answer1> {
+        Ok(tile_map.get_number_of_pipe_tiles() / 2 + tile_map.get_number_of_pipe_tiles() % 2)
+    }
+    fn part_2(tile_map: &Self::Input<'_>) -> Result<Self::Answer2> {
+        tile_map.clone().mark_sides_count_inside()
     }
+    fn generate(rng: &mut InputRng, size: usize) -> Result<String> {
+        if size < 2 {
+            return Err(anyhow!("tile map of at least 2 x 2 tiles"));
+        }
+        // pipe runs along boundary of a region of squares between tiles
+        let squares = MapSize::new(size - 1, size - 1);
+        let region = generate::simple_region(rng, squares, squares.len() * 2 / 5);
+        let pipe = generate::region_boundary(&region);
+        let mut tiles = MyMap2D::init(MapSize::new(size, size), ' ');
+        let gate =
+            |from: MapPoint, to: MapPoint| match (to.x().cmp(&from.x()), to.y().cmp(&from.y())) {
+                (Ordering::Equal, Ordering::Less) => Compass::N,
+                (Ordering::Greater, _) => Compass::E,
+                (Ordering::Equal, Ordering::Greater) => Compass::S,
+                _ => Compass::W,
+            };
+        for (index, tile) in pipe.iter().enumerate() {
+            let previous = pipe[(index + pipe.len() - 1) % pipe.len()];
+            let next = pipe[(index + 1) % pipe.len()];
+            let segment = match (gate(*tile, previous), gate(*tile, next)) {
+                (Compass::N, Compass::S) | (Compass::S, Compass::N) => '|',
+                (Compass::E, Compass::W) | (Compass::W, Compass::E) => '-',
+                (Compass::N, Compass::E) | (Compass::E, Compass::N) => 'L',
+                (Compass::N, Compass::W) | (Compass::W, Compass::N) => 'J',
+                (Compass::S, Compass::W) | (Compass::W, Compass::S) => '7',
+                _ => 'F',
+            };
+            tiles.set(*tile, segment);
+        }
+        // only pipe tiles may connect to start tile
+        let start_tile = pipe[rng.gen_range(0..pipe.len())];
+        tiles.set(start_tile, 'S');
+        let neighbors: Vec<MapPoint> = tiles.iter_neighbors(start_tile).map(|(p, ..)| p).collect();
+        for neighbor in neighbors {
+            if *tiles.get(neighbor) == ' ' {
+                tiles.set(neighbor, '.');
+            }
+        }
+        let junk = ['.', '.', '.', '|', '-', 'L', 'J', '7', 'F'];
+        Ok(generate::grid(rng, tiles.size(), |rng, p| {
+            match *tiles.get(p) {
+                ' ' => junk[rng.gen_range(0..junk.len())],
+                c => c,
+            }
+        }))
+    }
+    // pipe is white, inside tiles are green and outside tiles are blue
+    fn render(tile_map: &Self::Input<'_>) -> Result<Option<Image>> {
+        let mut tile_map = tile_map.clone();
+        tile_map.mark_sides_count_inside()?;
+        let outside = tile_map.outside.clone();
+        Ok(Some(Image::from_map(&tile_map.map, |_, t| match t {
+            Tile::Pipe(_) => Rgb::WHITE,
+            Tile::LeftOfPipe | Tile::RightOfPipe if Some(t) == outside.as_ref() => Rgb::BLUE,
+            Tile::LeftOfPipe | Tile::RightOfPipe => Rgb::GREEN,
+            Tile::Unknown(_) => Rgb::DARK_GREY,
+        })))
+    }
+}
 
-    let farthest_distance_from_start_tile_trough_pipe =
-        tile_map.get_number_of_pipe_tiles() / 2 + tile_map.get_number_of_pipe_tiles() % 2;
-    println!(
-        "result day 10 part 1: {}",
-        farthest_distance_from_start_tile_trough_pipe
-    );
-    assert_eq!(farthest_distance_from_start_tile_trough_pipe, 6_697);
+#[cfg(test)]
+mod tests {
 
-    // part 2: identify left and right side tiles of pipe and check, which one is outside
-    // count outside tiles as result
-    // first set all tiles, which are not pipes and are neighboring to a pipe tile to LeftSide or RightSide
-    // by flowing again through pipe, but this time set pipe sides
-    let (mut current_tile, mut flow_direction, mut previous_pipe) = tile_map.init_pipe_sides()?;
-    while !tile_map.is_start_pipe(current_tile) {
-        let extend_result =
-            tile_map.extend_pipe_sides(current_tile, flow_direction, previous_pipe)?;
-        flow_direction = extend_result.0;
-        previous_pipe = extend_result.1;
-        current_tile = tile_map.flow_to_next_tile(current_tile, flow_direction)?;
-    }
+    use super::*;
 
-    // now extend pipe sides to all tiles, which are not directly connected to a pipe tile
-    tile_map.extend_side_tiles();
-    // identify outside and count inside
-    let number_of_inside_tiles = tile_map.identify_outside_count_inside()?;
-    println!("result day 10 part 2: {}", number_of_inside_tiles);
-    assert_eq!(number_of_inside_tiles, 423);
+    #[test]
+    fn test_example_part1() -> Result<()> {
+        let input = "-L|F7\n\
+                           7S-7|\n\
+                           L|7||\n\
+                           -L-J|\n\
+                           L|-JF";
+        let tile_map = Day10::parse(input)?;
+        assert_eq!(Day10::part_1(&tile_map)?, 4);
+        let input = "7-F7-\n\
+                           .FJ|7\n\
+                           SJLL7\n\
+                           |F--J\n\
+                           LJ.LJ";
+        let tile_map = Day10::parse(input)?;
+        assert_eq!(Day10::part_1(&tile_map)?, 8);
+        Ok(())
+    }
 
-    Ok(())
+    #[test]
+    fn test_example_part2() -> Result<()> {
+        let input = "...........\n\
+                           .S-------7.\n\
+                           .|F-----7|.\n\
+                           .||.....||.\n\
+                           .||.....||.\n\
+                           .|L-7.F-J|.\n\
+                           .|..|.|..|.\n\
+                           .L--J.L--J.\n\
+                           ...........";
+        let tile_map = Day10::parse(input)?;
+        assert_eq!(Day10::part_2(&tile_map)?, 4);
+        let image = Day10::render(&tile_map)?.unwrap();
+        assert_eq!(image.get(MapPoint::new(0, 0)), Rgb::BLUE);
+        assert_eq!(image.get(MapPoint::new(1, 1)), Rgb::WHITE);
+        assert_eq!(image.get(MapPoint::new(2, 6)), Rgb::GREEN);
+        assert_eq!(image.get(MapPoint::new(5, 6)), Rgb::BLUE);
+        let input = ".F----7F7F7F7F-7....\n\
+                           .|F--7||||||||FJ....\n\
+                           .||.FJ||||||||L7....\n\
+                           FJL7L7LJLJ||LJ.L-7..\n\
+                           L--J.L7...LJS7F-7L7.\n\
+                           ....F-J..F7FJ|L7L7L7\n\
+                           ....L7.F7||L7|.L7L7|\n\
+                           .....|FJLJ|FJ|F7|.LJ\n\
+                           ....FJL-7.||.||||...\n\
+                           ....L---J.LJ.LJLJ...";
+        let tile_map = Day10::parse(input)?;
+        assert_eq!(Day10::part_2(&tile_map)?, 8);
+        let input = "FF7FSF7F7F7F7F7F---7\n\
+                           L|LJ||||||||||||F--J\n\
+                           FL-7LJLJ||||||LJL-77\n\
+                           F--JF--7||LJLJ7F7FJ-\n\
+                           L---JF-JLJ.||-FJLJJ7\n\
+                           |F|F-JF---7F7-L7L|7|\n\
+                           |FFJF7L7F-JF7|JL---7\n\
+                           7-L-JL7||F7|L7F-7F7|\n\
+                           L.L7LFJ|||||FJL7||LJ\n\
+                           L7JLJL-JLJLJL--JLJ.L";
+        let tile_map = Day10::parse(input)?;
+        assert_eq!(Day10::part_2(&tile_map)?, 10);
+        Ok(())
+    }
 }
@@ -1,58 +1,29 @@
 //!day_11.rs
 
+use crate::generate::{self, InputRng};
+use rand::Rng;
 use std::fmt::Display;
 
-use anyhow::Result;
-use my_lib::my_map_point::MapPoint;
-
-// values taken from ../../assets/day_11.txt
-// number of chars in one line
-const X: usize = 140;
-// number of lines
-const Y: usize = 140;
-
-#[derive(Copy, Clone)]
-struct SpaceTile {
-    galaxy: u16,
-    expand_x: u16,
-    expand_y: u16,
-}
-
-impl Default for SpaceTile {
-    fn default() -> Self {
-        SpaceTile {
-            galaxy: 0,
-            expand_x: 1,
-            expand_y: 1,
-        }
-    }
-}
-
-impl Display for SpaceTile {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let d = if self.galaxy > 0 {
-            '#'
-        } else if self.expand_x > 1 || self.expand_y > 1 {
-            '*'
-        } else {
-            '.'
-        };
-        write!(f, "{}", d)
-    }
-}
+use crate::my_lib::my_map_point::{MapPoint, MapSize};
+use crate::parse::{self, ParseError};
+use crate::render::{Image, Rgb};
+use crate::solution::Solution;
+use anyhow::{anyhow, Result};
 
+/// galaxies and empty rows and columns of image
 #[derive(Default)]
-struct Cosmos<const X: usize, const Y: usize> {
-    galaxies: Vec<MapPoint<X, Y>>,
+pub struct Cosmos {
+    size: MapSize,
+    galaxies: Vec<MapPoint>,
     empty_space_columns: Vec<usize>,
     empty_space_rows: Vec<usize>,
 }
 
-impl<const X: usize, const Y: usize> Display for Cosmos<X, Y> {
+impl Display for Cosmos {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for y in 0..Y {
-            for x in 0..X {
-                let mp = MapPoint::<X, Y>::new(x, y);
+        for y in 0..self.size.height {
+            for x in 0..self.size.width {
+                let mp = MapPoint::new(x, y);
                 if self.galaxies.contains(&mp) {
                     write!(f, "#")?;
                 } else if self.empty_space_columns.contains(&x)
@@ -69,15 +40,26 @@ impl<const X: usize, const Y: usize> Display for Cosmos<X, Y> {
     }
 }
 
-impl<const X: usize, const Y: usize> Cosmos<X, Y> {
-    fn new(input: &str) -> Self {
-        let mut cosmos = Cosmos::default();
-        let mut empty_space_columns: Vec<bool> = vec![true; X];
-        let mut empty_space_rows: Vec<bool> = vec![true; Y];
-        for (y, line) in input.trim().lines().enumerate() {
-            for (x, c) in line.trim().chars().enumerate() {
+impl Cosmos {
+    fn new(input: &str) -> Result<Self, ParseError> {
+        let mut cosmos = Cosmos {
+            size: parse::grid_size(input)?,
+            ..Default::default()
+        };
+        let mut empty_space_columns: Vec<bool> = vec![true; cosmos.size.width];
+        let mut empty_space_rows: Vec<bool> = vec![true; cosmos.size.height];
+        for (y, line) in input
+            .lines()
+            .map(|l| l.trim())
+            .filter(|l| !l.is_empty())
+            .enumerate()
+        {
+            for (x, (index, c)) in line.char_indices().enumerate() {
+                if c != '#' && c != '.' {
+                    return Err(ParseError::char(c, "`#` or `.`").at(&line[index..]));
+                }
                 if c == '#' {
-                    cosmos.galaxies.push(MapPoint::<X, Y>::new(x, y));
+                    cosmos.galaxies.push(MapPoint::new(x, y));
                     empty_space_columns[x] = false;
                     empty_space_rows[y] = false;
                 }
@@ -95,9 +77,10 @@ impl<const X: usize, const Y: usize> Cosmos<X, Y> {
             .filter(|(_, f)| **f)
             .map(|(y, _)| y)
             .collect();
-        cosmos
+        Ok(cosmos)
     }
-    fn calc_sum_galaxy_distances(&self, factor_empty_space: usize) -> usize {
+    /// sum of distances of all pairs of galaxies, if each empty row and column is factor times wider
+    pub fn calc_sum_galaxy_distances(&self, factor_empty_space: usize) -> usize {
         let mut distance = 0;
         for index_galaxy_1 in 0..(self.galaxies.len() - 1) {
             for index_galaxy_2 in (index_galaxy_1 + 1)..self.galaxies.len() {
@@ -112,8 +95,8 @@ impl<const X: usize, const Y: usize> Cosmos<X, Y> {
     }
     fn calc_distance(
         &self,
-        alpha: &MapPoint<X, Y>,
-        omega: &MapPoint<X, Y>,
+        alpha: &MapPoint,
+        omega: &MapPoint,
         factor_empty_space: usize,
     ) -> usize {
         let min_x = alpha.x().min(omega.x());
@@ -133,27 +116,80 @@ impl<const X: usize, const Y: usize> Cosmos<X, Y> {
                 .count();
         distance + (factor_empty_space.max(1) - 1) * empty_spaces
     }
+    // cosmos after expansion of part 1: each empty column and row is doubled
+    fn expanded_image(&self) -> Image {
+        let expand = |len: usize, empty: &[usize]| -> Vec<usize> {
+            (0..len)
+                .flat_map(|i| std::iter::repeat_n(i, if empty.contains(&i) { 2 } else { 1 }))
+                .collect()
+        };
+        let columns = expand(self.size.width, &self.empty_space_columns);
+        let rows = expand(self.size.height, &self.empty_space_rows);
+        Image::new(MapSize::new(columns.len(), rows.len()), |p| {
+            let (x, y) = (columns[p.x()], rows[p.y()]);
+            if self.galaxies.contains(&MapPoint::new(x, y)) {
+                Rgb::YELLOW
+            } else if self.empty_space_columns.contains(&x) || self.empty_space_rows.contains(&y) {
+                Rgb::DARK_GREY
+            } else {
+                Rgb::BLACK
+            }
+        })
+    }
 }
 
-pub fn day_11() -> Result<()> {
-    let input = include_str!("../../assets/day_11.txt");
-    let cosmos = Cosmos::<X, Y>::new(input);
-    let sum_distance = cosmos.calc_sum_galaxy_distances(2);
-    println!("result day 11 part 1: {}", sum_distance);
-    assert_eq!(sum_distance, 9_799_681);
-    let sum_distance = cosmos.calc_sum_galaxy_distances(1_000_000);
-    println!("result day 11 part 2: {}", sum_distance);
-    assert_eq!(sum_distance, 513_171_773_355);
+/// Cosmic Expansion: sum of distances between galaxies with empty space doubled (part 1) or a
+/// million times larger (part 2)
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    const GEN_SIZE: (usize, &'static str) = (140, "width and height of image");
+    type Input<'a> = Cosmos;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-    Ok(())
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        Ok(Cosmos::new(input)?)
+    }
+    fn part_1(cosmos: &Self::Input<'_>) -> Result<Self::Answer1> {
+        Ok(cosmos.calc_sum_galaxy_distances(2))
+    }
+    fn part_2(cosmos: &Self::Input<'_>) -> Result<Self::Answer2> {
+        Ok(cosmos.calc_sum_galaxy_distances(1_000_000))
+    }
+    fn render(cosmos: &Self::Input<'_>) -> Result<Option<Image>> {
+        Ok(Some(cosmos.expanded_image()))
+    }
+    fn generate(rng: &mut InputRng, size: usize) -> Result<String> {
+        if size < 2 {
+            return Err(anyhow!("image of at least 2 x 2 pixels"));
+        }
+        // first and last row and column are not empty, since top left and bottom right pixel are galaxies
+        let empty_space = |rng: &mut InputRng| -> Vec<bool> {
+            (0..size)
+                .map(|i| i > 0 && i + 1 < size && rng.gen_bool(0.1))
+                .collect()
+        };
+        let empty_space_rows = empty_space(rng);
+        let empty_space_columns = empty_space(rng);
+        let corners = [MapPoint::new(0, 0), MapPoint::new(size - 1, size - 1)];
+        Ok(generate::grid(rng, MapSize::new(size, size), |rng, p| {
+            if empty_space_rows[p.y()] || empty_space_columns[p.x()] {
+                '.'
+            } else if corners.contains(&p) || rng.gen_bool(0.05) {
+                '#'
+            } else {
+                '.'
+            }
+        }))
+    }
 }
 
 #[cfg(test)]
 mod tests {
 
     use super::*;
-    const XT: usize = 10;
-    const YT: usize = 10;
 
     const TEST_INPUT: &str = "...#......
                               .......#..
@@ -168,9 +204,21 @@ mod tests {
 
     #[test]
     fn test_distance() {
-        let cosmos = Cosmos::<XT, YT>::new(TEST_INPUT);
-        eprintln!("{}", cosmos);
+        let cosmos = Cosmos::new(TEST_INPUT).unwrap();
         let sum_distance = cosmos.calc_sum_galaxy_distances(2);
-        println!("result day 11 part 1: {}", sum_distance);
+        assert_eq!(sum_distance, 374);
+        assert_eq!(cosmos.calc_sum_galaxy_distances(10), 1030);
+        assert_eq!(cosmos.calc_sum_galaxy_distances(100), 8410);
+    }
+
+    #[test]
+    fn test_expanded_image() {
+        let cosmos = Cosmos::new(TEST_INPUT).unwrap();
+        let image = Day11::render(&cosmos).unwrap().unwrap();
+        assert_eq!(image.size(), MapSize::new(13, 12));
+        assert_eq!(image.get(MapPoint::new(4, 0)), Rgb::YELLOW);
+        assert_eq!(image.get(MapPoint::new(12, 7)), Rgb::YELLOW);
+        assert_eq!(image.get(MapPoint::new(3, 0)), Rgb::DARK_GREY);
+        assert_eq!(image.get(MapPoint::new(0, 0)), Rgb::BLACK);
     }
 }
@@ -1,32 +1,31 @@
 //!day_12.rs
 
+use crate::generate::InputRng;
+use rand::Rng;
 use std::collections::HashMap;
 
+use crate::parse::{self, ParseError};
+use crate::solution::Solution;
 use anyhow::Result;
+use tracing::debug;
 
-fn springs_and_damaged_clusters(input: &str) -> (&str, Vec<usize>) {
-    input
+/// springs and sizes of damaged clusters of a line of condition records
+pub fn springs_and_damaged_clusters(input: &str) -> Result<(&str, Vec<usize>), ParseError> {
+    let (springs, d) = parse::split_once(input.trim(), " ")?;
+    let springs = springs.trim();
+    if let Some(index) = springs.find(|c| !['.', '#', '?'].contains(&c)) {
+        return Err(ParseError::new(&springs[index..], "`.`, `#` or `?`"));
+    }
+    let damaged_clusters: Vec<usize> = d
         .trim()
-        .split_once(' ')
-        .map(|(springs, d)| {
-            if springs
-                .trim()
-                .chars()
-                .any(|c| !['.', '#', '?'].contains(&c))
-            {
-                panic!("bad spring char");
-            }
-            let damaged_clusters: Vec<usize> = d
-                .trim()
-                .split(',')
-                .map(|s| s.parse::<usize>().expect("bad input"))
-                .collect();
-            (springs.trim(), damaged_clusters)
-        })
-        .unwrap()
+        .split(',')
+        .map(parse::number::<usize>)
+        .collect::<Result<_, _>>()?;
+    Ok((springs, damaged_clusters))
 }
 
-fn unfold_springs_and_damaged_clusters(
+/// springs and damaged clusters unfolded to five copies
+pub fn unfold_springs_and_damaged_clusters(
     springs: &str,
     damaged_clusters: &[usize],
 ) -> (String, Vec<usize>) {
@@ -42,7 +41,8 @@ fn unfold_springs_and_damaged_clusters(
 // this solution is an implementation in Rust of https://www.youtube.com/watch?v=g3Ms5e7Jdqo by HyperNeutrino
 // www.youtube.com/@hyper-neutrino
 // github.com/hyper-neutrino
-fn different_arrangements(
+/// number of arrangements of springs, which match damaged clusters; cache is shared between lines
+pub fn different_arrangements(
     springs: &str,
     damaged_clusters: &[usize],
     cache: &mut HashMap<(String, Vec<usize>), usize>,
@@ -68,6 +68,12 @@ fn different_arrangements(
     if let Some(cached_num_different_arrangements) =
         cache.get(&(springs.to_string(), damaged_clusters.to_vec()))
     {
+        debug!(
+            springs,
+            ?damaged_clusters,
+            arrangements = cached_num_different_arrangements,
+            "cache hit"
+        );
         return *cached_num_different_arrangements;
     }
 
@@ -106,33 +112,72 @@ fn different_arrangements(
     num_different_arrangements
 }
 
-pub fn day_12() -> Result<()> {
-    let input = include_str!("../../assets/day_12.txt");
-    let mut sum_different_arrangements = 0;
-    let mut sum_different_arrangements_unfolded = 0;
-    let mut cache: HashMap<(String, Vec<usize>), usize> = HashMap::new();
-    for line in input.lines() {
-        let (springs, damaged_clusters) = springs_and_damaged_clusters(line);
-        sum_different_arrangements +=
-            different_arrangements(springs, &damaged_clusters[..], &mut cache);
-        let (unfolded_springs, unfolded_damaged_clusters) =
-            unfold_springs_and_damaged_clusters(springs, &damaged_clusters);
-        sum_different_arrangements_unfolded += different_arrangements(
-            unfolded_springs.as_str(),
-            &unfolded_damaged_clusters[..],
-            &mut cache,
-        );
-    }
+/// Hot Springs: sum of arrangements of damaged springs (part 1) and of unfolded records (part 2)
+pub struct Day12;
 
-    println!("result day 12 part 1: {}", sum_different_arrangements);
-    assert_eq!(sum_different_arrangements, 7_460);
-    println!(
-        "result day 12 part 2: {}",
-        sum_different_arrangements_unfolded
-    );
-    assert_eq!(sum_different_arrangements_unfolded, 6_720_660_274_964);
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+    const GEN_SIZE: (usize, &'static str) = (1_000, "number of rows of springs");
+    /// springs and damaged clusters per line
+    type Input<'a> = Vec<(&'a str, Vec<usize>)>;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-    Ok(())
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        Ok(input
+            .lines()
+            .map(springs_and_damaged_clusters)
+            .collect::<Result<_, _>>()?)
+    }
+    fn part_1(input: &Self::Input<'_>) -> Result<Self::Answer1> {
+        let mut cache: HashMap<(String, Vec<usize>), usize> = HashMap::new();
+        Ok(input
+            .iter()
+            .map(|(springs, damaged_clusters)| {
+                different_arrangements(springs, damaged_clusters, &mut cache)
+            })
+            .sum())
+    }
+    fn part_2(input: &Self::Input<'_>) -> Result<Self::Answer2> {
+        let mut cache: HashMap<(String, Vec<usize>), usize> = HashMap::new();
+        Ok(input
+            .iter()
+            .map(|(springs, damaged_clusters)| {
+                let (unfolded_springs, unfolded_damaged_clusters) =
+                    unfold_springs_and_damaged_clusters(springs, damaged_clusters);
+                different_arrangements(
+                    unfolded_springs.as_str(),
+                    &unfolded_damaged_clusters[..],
+                    &mut cache,
+                )
+            })
+            .sum())
+    }
+    fn generate(rng: &mut InputRng, size: usize) -> Result<String> {
+        let mut input = String::new();
+        for _ in 0..size {
+            let damaged_clusters: Vec<usize> = (0..rng.gen_range(1..=5))
+                .map(|_| rng.gen_range(1..=4))
+                .collect();
+            let mut springs = ".".repeat(rng.gen_range(0..3));
+            for (index, cluster) in damaged_clusters.iter().enumerate() {
+                if index > 0 {
+                    springs.push_str(&".".repeat(rng.gen_range(1..4)));
+                }
+                springs.push_str(&"#".repeat(*cluster));
+            }
+            springs.push_str(&".".repeat(rng.gen_range(0..3)));
+            // condition of about half of springs is unknown
+            let springs: String = springs
+                .chars()
+                .map(|c| if rng.gen_bool(0.5) { '?' } else { c })
+                .collect();
+            let damaged_clusters: Vec<String> =
+                damaged_clusters.iter().map(|c| c.to_string()).collect();
+            input.push_str(&format!("{} {}\n", springs, damaged_clusters.join(",")));
+        }
+        Ok(input)
+    }
 }
 
 #[cfg(test)]
@@ -140,179 +185,127 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_example() -> Result<()> {
+        let input = "???.### 1,1,3\n\
+                           .??..??...?##. 1,1,3\n\
+                           ?#?#?#?#?#?#?#? 1,3,1,6\n\
+                           ????.#...#... 4,1,1\n\
+                           ????.######..#####. 1,6,5\n\
+                           ?###???????? 3,2,1";
+        let records = Day12::parse(input)?;
+        assert_eq!(Day12::part_1(&records)?, 21);
+        assert_eq!(Day12::part_2(&records)?, 525152);
+        Ok(())
+    }
+
     #[test]
     fn test_arrangements() {
         let input = "???.### 1,1,3";
-        let (springs, damaged_clusters) = springs_and_damaged_clusters(input);
+        let (springs, damaged_clusters) = springs_and_damaged_clusters(input).unwrap();
         let mut cache: HashMap<(String, Vec<usize>), usize> = HashMap::new();
-        eprintln!("{}", springs);
-        eprintln!("{:?}", damaged_clusters);
         let test_different_arrangements =
-            different_arrangements(&springs[..], &damaged_clusters[..], &mut cache);
-        println!(
-            "test 01 result day 12 part 1: {}",
-            test_different_arrangements
-        );
+            different_arrangements(springs, &damaged_clusters[..], &mut cache);
         assert_eq!(test_different_arrangements, 1);
     }
 
     #[test]
     fn test_arrangements_02() {
         let input = ".??..??...?##. 1,1,3";
-        let (springs, damaged_clusters) = springs_and_damaged_clusters(input);
+        let (springs, damaged_clusters) = springs_and_damaged_clusters(input).unwrap();
         let mut cache: HashMap<(String, Vec<usize>), usize> = HashMap::new();
-        eprintln!("{}", springs);
-        eprintln!("{:?}", damaged_clusters);
         let test_different_arrangements =
-            different_arrangements(&springs[..], &damaged_clusters[..], &mut cache);
-        println!(
-            "test 02 result day 12 part 1: {}",
-            test_different_arrangements
-        );
+            different_arrangements(springs, &damaged_clusters[..], &mut cache);
         assert_eq!(test_different_arrangements, 4);
     }
 
     #[test]
     fn test_arrangements_03() {
         let input = "?#?#?#?#?#?#?#? 1,3,1,6";
-        let (springs, damaged_clusters) = springs_and_damaged_clusters(input);
+        let (springs, damaged_clusters) = springs_and_damaged_clusters(input).unwrap();
         let mut cache: HashMap<(String, Vec<usize>), usize> = HashMap::new();
-        eprintln!("{}", springs);
-        eprintln!("{:?}", damaged_clusters);
         let test_different_arrangements =
-            different_arrangements(&springs[..], &damaged_clusters[..], &mut cache);
-        println!(
-            "test 03 result day 12 part 1: {}",
-            test_different_arrangements
-        );
+            different_arrangements(springs, &damaged_clusters[..], &mut cache);
         assert_eq!(test_different_arrangements, 1);
     }
 
     #[test]
     fn test_arrangements_04() {
         let input = "????.#...#... 4,1,1";
-        let (springs, damaged_clusters) = springs_and_damaged_clusters(input);
+        let (springs, damaged_clusters) = springs_and_damaged_clusters(input).unwrap();
         let mut cache: HashMap<(String, Vec<usize>), usize> = HashMap::new();
-        eprintln!("{}", springs);
-        eprintln!("{:?}", damaged_clusters);
         let test_different_arrangements =
-            different_arrangements(&springs[..], &damaged_clusters[..], &mut cache);
-        println!(
-            "test 04 result day 12 part 1: {}",
-            test_different_arrangements
-        );
+            different_arrangements(springs, &damaged_clusters[..], &mut cache);
         assert_eq!(test_different_arrangements, 1);
     }
 
     #[test]
     fn test_arrangements_05() {
         let input = "????.######..#####. 1,6,5";
-        let (springs, damaged_clusters) = springs_and_damaged_clusters(input);
+        let (springs, damaged_clusters) = springs_and_damaged_clusters(input).unwrap();
         let mut cache: HashMap<(String, Vec<usize>), usize> = HashMap::new();
-        eprintln!("{}", springs);
-        eprintln!("{:?}", damaged_clusters);
         let test_different_arrangements =
-            different_arrangements(&springs[..], &damaged_clusters[..], &mut cache);
-        println!(
-            "test 05 result day 12 part 1: {}",
-            test_different_arrangements
-        );
+            different_arrangements(springs, &damaged_clusters[..], &mut cache);
         assert_eq!(test_different_arrangements, 4);
     }
 
     #[test]
     fn test_arrangements_06() {
         let input = "?###???????? 3,2,1";
-        let (springs, damaged_clusters) = springs_and_damaged_clusters(input);
+        let (springs, damaged_clusters) = springs_and_damaged_clusters(input).unwrap();
         let mut cache: HashMap<(String, Vec<usize>), usize> = HashMap::new();
-        eprintln!("{}", springs);
-        eprintln!("{:?}", damaged_clusters);
         let test_different_arrangements =
-            different_arrangements(&springs[..], &damaged_clusters[..], &mut cache);
-        println!(
-            "test 06 result day 12 part 1: {}",
-            test_different_arrangements
-        );
+            different_arrangements(springs, &damaged_clusters[..], &mut cache);
         assert_eq!(test_different_arrangements, 10);
     }
 
     #[test]
     fn test_arrangements_07() {
         let input = ".#??#..???#..?? 2,2,1";
-        let (springs, damaged_clusters) = springs_and_damaged_clusters(input);
+        let (springs, damaged_clusters) = springs_and_damaged_clusters(input).unwrap();
         let mut cache: HashMap<(String, Vec<usize>), usize> = HashMap::new();
-        eprintln!("{}", springs);
-        eprintln!("{:?}", damaged_clusters);
         let test_different_arrangements =
-            different_arrangements(&springs[..], &damaged_clusters[..], &mut cache);
-        println!(
-            "test 07 result day 12 part 1: {}",
-            test_different_arrangements
-        );
+            different_arrangements(springs, &damaged_clusters[..], &mut cache);
         assert_eq!(test_different_arrangements, 0);
     }
 
     #[test]
     fn test_arrangements_08() {
         let input = ".#??#..???#..?? 2,1,2";
-        let (springs, damaged_clusters) = springs_and_damaged_clusters(input);
+        let (springs, damaged_clusters) = springs_and_damaged_clusters(input).unwrap();
         let mut cache: HashMap<(String, Vec<usize>), usize> = HashMap::new();
-        eprintln!("{}", springs);
-        eprintln!("{:?}", damaged_clusters);
         let test_different_arrangements =
-            different_arrangements(&springs[..], &damaged_clusters[..], &mut cache);
-        println!(
-            "test 08 result day 12 part 1: {}",
-            test_different_arrangements
-        );
+            different_arrangements(springs, &damaged_clusters[..], &mut cache);
         assert_eq!(test_different_arrangements, 1);
     }
 
     #[test]
     fn test_arrangements_09() {
         let input = "????#?.??? 2,1,1";
-        let (springs, damaged_clusters) = springs_and_damaged_clusters(input);
+        let (springs, damaged_clusters) = springs_and_damaged_clusters(input).unwrap();
         let mut cache: HashMap<(String, Vec<usize>), usize> = HashMap::new();
-        eprintln!("{}", springs);
-        eprintln!("{:?}", damaged_clusters);
         let test_different_arrangements =
-            different_arrangements(&springs[..], &damaged_clusters[..], &mut cache);
-        println!(
-            "test 09 result day 12 part 1: {}",
-            test_different_arrangements
-        );
+            different_arrangements(springs, &damaged_clusters[..], &mut cache);
         assert_eq!(test_different_arrangements, 8);
     }
 
     #[test]
     fn test_arrangements_10() {
         let input = "??.??????#???#?????# 1,1,7,3,1";
-        let (springs, damaged_clusters) = springs_and_damaged_clusters(input);
+        let (springs, damaged_clusters) = springs_and_damaged_clusters(input).unwrap();
         let mut cache: HashMap<(String, Vec<usize>), usize> = HashMap::new();
-        eprintln!("{}", springs);
-        eprintln!("{:?}", damaged_clusters);
         let test_different_arrangements =
-            different_arrangements(&springs[..], &damaged_clusters[..], &mut cache);
-        println!(
-            "test 10 result day 12 part 1: {}",
-            test_different_arrangements
-        );
+            different_arrangements(springs, &damaged_clusters[..], &mut cache);
         assert_eq!(test_different_arrangements, 9);
     }
 
     #[test]
     fn test_arrangements_11() {
         let input = ".##.?#??.#.?# 2,1,1,1";
-        let (springs, damaged_clusters) = springs_and_damaged_clusters(input);
+        let (springs, damaged_clusters) = springs_and_damaged_clusters(input).unwrap();
         let mut cache: HashMap<(String, Vec<usize>), usize> = HashMap::new();
-        eprintln!("{}", springs);
-        eprintln!("{:?}", damaged_clusters);
         let test_different_arrangements =
-            different_arrangements(&springs[..], &damaged_clusters[..], &mut cache);
-        println!(
-            "test 11 result day 12 part 1: {}",
-            test_different_arrangements
-        );
+            different_arrangements(springs, &damaged_clusters[..], &mut cache);
         assert_eq!(test_different_arrangements, 1);
     }
 }
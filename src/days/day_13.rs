@@ -1,26 +1,28 @@
 //!day_13.rs
 
-use anyhow::Result;
-use my_lib::my_map_two_dim::MyMap2D;
+use crate::generate::InputRng;
+use crate::my_lib::my_map_point::{MapPoint, MapSize};
+use crate::my_lib::my_map_two_dim::MyMap2D;
+use crate::parse::{self, ParseError};
+use crate::render::{Image, Rgb};
+use crate::solution::Solution;
+use anyhow::{anyhow, Context, Result};
+use rand::Rng;
 
-// max values for X and Y over all patterns taken from ../../assets/day_13.txt
-const X: usize = 17;
-const Y: usize = 17;
-
-#[derive(PartialEq, Eq, Clone, Copy, Default)]
+#[derive(PartialEq, Eq, Clone, Copy)]
 enum Cell {
-    #[default]
-    None,
     Ash,
     Rock,
 }
 
-impl From<char> for Cell {
-    fn from(value: char) -> Self {
+impl TryFrom<char> for Cell {
+    type Error = ParseError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
-            '.' => Cell::Ash,
-            '#' => Cell::Rock,
-            _ => panic!("bad Cell char"),
+            '.' => Ok(Cell::Ash),
+            '#' => Ok(Cell::Rock),
+            _ => Err(ParseError::char(value, "`.` or `#`")),
         }
     }
 }
@@ -31,9 +33,10 @@ enum MirrorResult {
     Clean,
 }
 
-#[derive(Clone, Copy)]
-struct Pattern<const X: usize, const Y: usize> {
-    pat: MyMap2D<Cell, X, Y>,
+/// pattern of ash and rocks with its clean and smudged mirror lines
+#[derive(Clone)]
+pub struct Pattern {
+    pat: MyMap2D<Cell>,
     mirror_at: usize, // index of right column respectively bottom row, must be > 0 to be valid
     mirror_axis: bool, // false: row, true: column, valid, if mirror_at is valid
     mirror_value: usize,
@@ -42,10 +45,10 @@ struct Pattern<const X: usize, const Y: usize> {
     smudge_mirror_value: usize,
 }
 
-impl<const X: usize, const Y: usize> Pattern<X, Y> {
-    fn new(value: &str) -> Self {
+impl Pattern {
+    fn new(value: &str) -> Result<Self> {
         let mut pattern = Pattern {
-            pat: value.into(),
+            pat: parse::grid(value)?,
             mirror_at: 0,
             mirror_axis: false,
             mirror_value: 0,
@@ -53,59 +56,48 @@ impl<const X: usize, const Y: usize> Pattern<X, Y> {
             smudge_mirror_axis: false,
             smudge_mirror_value: 0,
         };
-        pattern.set_mirror_axis();
-        pattern
+        pattern.set_mirror_axis()?;
+        Ok(pattern)
     }
-    fn set_mirror_axis(&mut self) {
+    fn set_mirror_axis(&mut self) -> Result<()> {
         let mut clean = false;
         let mut smudge = false;
         // first try to find mirro axis in rows ...
-        for r1 in 0..Y - 1 {
+        for r1 in 0..self.pat.height() - 1 {
             let r2 = r1 + 1;
-            if !self.valid_row(r2) {
-                break;
-            }
             match self.check_mirror_row(r1, r2) {
                 MirrorResult::None => (),
                 MirrorResult::Clean => clean = true,
                 MirrorResult::Smudge => smudge = true,
             }
             if clean && smudge {
-                return;
+                return Ok(());
             }
         }
         // .. than try to find mirro axis in columns
-        for c1 in 0..X - 1 {
+        for c1 in 0..self.pat.width() - 1 {
             let c2 = c1 + 1;
-            if !self.valid_column(c2) {
-                break;
-            }
             match self.check_mirror_column(c1, c2) {
                 MirrorResult::None => (),
                 MirrorResult::Clean => clean = true,
                 MirrorResult::Smudge => smudge = true,
             }
             if clean && smudge {
-                return;
+                return Ok(());
             }
         }
         if !clean {
-            panic!("did not find a clean mirror axis ")
+            return Err(anyhow!("pattern has no clean mirror line"));
         }
         if !smudge {
-            panic!("did not find a smudge mirror axis ")
+            return Err(anyhow!(
+                "pattern has no mirror line with exactly one smudge"
+            ));
         }
-    }
-    fn valid_row(&self, row: usize) -> bool {
-        // check is required, since map can be greater than provided pattern
-        *self.pat.get((0, row).into()) != Cell::None
-    }
-    fn valid_column(&self, col: usize) -> bool {
-        // check is required, since map can be greater than provided pattern
-        *self.pat.get((col, 0).into()) != Cell::None
+        Ok(())
     }
     fn check_mirror_row(&mut self, r1: usize, r2: usize) -> MirrorResult {
-        let mut bottom = r2; // row[Y - 1] is bottom most row
+        let mut bottom = r2; // row[height - 1] is bottom most row
         let mut top = r1; // row[0] is top most row
         let mut smudge = false;
         loop {
@@ -126,7 +118,7 @@ impl<const X: usize, const Y: usize> Pattern<X, Y> {
                 0 => (),
                 _ => return MirrorResult::None,
             }
-            if top == 0 || bottom + 1 == Y || !self.valid_row(bottom + 1) {
+            if top == 0 || bottom + 1 == self.pat.height() {
                 break;
             }
             top -= 1;
@@ -147,7 +139,7 @@ impl<const X: usize, const Y: usize> Pattern<X, Y> {
         }
     }
     fn check_mirror_column(&mut self, c1: usize, c2: usize) -> MirrorResult {
-        let mut right = c2; // row[Y - 1] is right most column
+        let mut right = c2; // column[width - 1] is right most column
         let mut left = c1; // row[0] is left most row
         let mut smudge = false;
         loop {
@@ -168,7 +160,7 @@ impl<const X: usize, const Y: usize> Pattern<X, Y> {
                 0 => (),
                 _ => return MirrorResult::None,
             }
-            if left == 0 || right + 1 == Y || !self.valid_column(right + 1) {
+            if left == 0 || right + 1 == self.pat.width() {
                 break;
             }
             left -= 1;
@@ -177,33 +169,239 @@ impl<const X: usize, const Y: usize> Pattern<X, Y> {
         if smudge {
             // found smufge mirror row
             self.smudge_mirror_at = c2;
-            self.smudge_mirror_axis = false;
+            self.smudge_mirror_axis = true;
             self.smudge_mirror_value = c2;
             MirrorResult::Smudge
         } else {
             // found clean mirror row
             self.mirror_at = c2;
-            self.mirror_axis = false;
+            self.mirror_axis = true;
             self.mirror_value = c2;
             MirrorResult::Clean
         }
     }
 }
 
-pub fn day_13() -> Result<()> {
-    let input = include_str!("../../assets/day_13.txt");
-    let mut result_part1 = 0;
-    let mut result_part2 = 0;
-    for pat in input.split("\n\n") {
-        let pattern = Pattern::<X, Y>::new(pat);
-        result_part1 += pattern.mirror_value;
-        result_part2 += pattern.smudge_mirror_value;
+fn transpose(rows: &[Vec<bool>]) -> Vec<Vec<bool>> {
+    (0..rows[0].len())
+        .map(|x| rows.iter().map(|row| row[x]).collect())
+        .collect()
+}
+
+/// number of differing cells of each mirror axis of rows and of columns of pattern
+fn mirror_differences(pattern: &[Vec<bool>]) -> Vec<usize> {
+    let columns = transpose(pattern);
+    [pattern, &columns[..]]
+        .into_iter()
+        .flat_map(|rows| {
+            (1..rows.len()).map(move |axis| {
+                (0..axis.min(rows.len() - axis))
+                    .map(|offset| {
+                        rows[axis - 1 - offset]
+                            .iter()
+                            .zip(rows[axis + offset].iter())
+                            .filter(|(a, b)| a != b)
+                            .count()
+                    })
+                    .sum()
+            })
+        })
+        .collect()
+}
+
+/// random pattern with exactly one clean mirror axis and one mirror axis with a smudge
+fn random_pattern(rng: &mut InputRng) -> Vec<Vec<bool>> {
+    // index of mirrored cell, if it is inside of pattern
+    let mirror = |index: usize, axis: usize, len: usize| {
+        let mirrored = (2 * axis).checked_sub(index + 1)?;
+        (mirrored < len).then_some(mirrored)
+    };
+    loop {
+        let (height, width) = (rng.gen_range(5..=17), rng.gen_range(5..=17));
+        let (row_axis, column_axis) = (rng.gen_range(1..height), rng.gen_range(1..width));
+        let cells: Vec<Vec<bool>> = (0..height)
+            .map(|_| (0..width).map(|_| rng.gen_bool(0.5)).collect())
+            .collect();
+        // pattern is mirrored at both axis ...
+        let mut pattern: Vec<Vec<bool>> = (0..height)
+            .map(|y| {
+                let y = mirror(y, row_axis, height)
+                    .filter(|_| y >= row_axis)
+                    .unwrap_or(y);
+                (0..width)
+                    .map(|x| {
+                        let x = mirror(x, column_axis, width)
+                            .filter(|_| x >= column_axis)
+                            .unwrap_or(x);
+                        cells[y][x]
+                    })
+                    .collect()
+            })
+            .collect();
+        // ... until smudge of a cell, which has no mirrored row, but a mirrored column
+        let smudges: Vec<(usize, usize)> = (0..height)
+            .filter(|y| mirror(*y, row_axis, height).is_none())
+            .flat_map(|y| {
+                (0..width)
+                    .filter(|x| mirror(*x, column_axis, width).is_some())
+                    .map(move |x| (y, x))
+            })
+            .collect();
+        if smudges.is_empty() {
+            continue;
+        }
+        let (y, x) = smudges[rng.gen_range(0..smudges.len())];
+        pattern[y][x] = !pattern[y][x];
+        // random cells may add further mirror axis
+        let differences = mirror_differences(&pattern);
+        if differences.iter().filter(|d| **d == 0).count() == 1
+            && differences.iter().filter(|d| **d == 1).count() == 1
+        {
+            return if rng.gen_bool(0.5) {
+                transpose(&pattern)
+            } else {
+                pattern
+            };
+        }
     }
+}
+
+/// Point of Incidence: summary of mirror lines (part 1) and of mirror lines with a fixed smudge
+/// (part 2)
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+    const GEN_SIZE: (usize, &'static str) = (100, "number of patterns");
+    type Input<'a> = Vec<Pattern>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        input
+            .split("\n\n")
+            .enumerate()
+            .map(|(index, pattern)| {
+                Pattern::new(pattern).with_context(|| format!("pattern {}", index + 1))
+            })
+            .collect()
+    }
+    fn part_1(input: &Self::Input<'_>) -> Result<Self::Answer1> {
+        Ok(input.iter().map(|p| p.mirror_value).sum())
+    }
+    fn part_2(input: &Self::Input<'_>) -> Result<Self::Answer2> {
+        Ok(input.iter().map(|p| p.smudge_mirror_value).sum())
+    }
+    fn generate(rng: &mut InputRng, size: usize) -> Result<String> {
+        let patterns: Vec<String> = (0..size)
+            .map(|_| {
+                random_pattern(rng)
+                    .iter()
+                    .map(|row| {
+                        let mut line: String =
+                            row.iter().map(|r| if *r { '#' } else { '.' }).collect();
+                        line.push('\n');
+                        line
+                    })
+                    .collect()
+            })
+            .collect();
+        Ok(patterns.join("\n"))
+    }
+    // patterns from top to bottom, separated by an empty row; rows or columns next to clean
+    // mirror axis are tinted blue
+    fn render(input: &Self::Input<'_>) -> Result<Option<Image>> {
+        let width = input
+            .iter()
+            .map(|p| p.pat.width())
+            .max()
+            .unwrap_or_default();
+        let height = input.iter().map(|p| p.pat.height() + 1).sum::<usize>();
+        // pattern and its first row for each row of image
+        let rows: Vec<Option<(&Pattern, usize)>> = input
+            .iter()
+            .scan(0, |first_row, pattern| {
+                let rows = (0..=pattern.pat.height())
+                    .map(|r| (r < pattern.pat.height()).then_some((pattern, *first_row)))
+                    .collect::<Vec<_>>();
+                *first_row += pattern.pat.height() + 1;
+                Some(rows)
+            })
+            .flatten()
+            .collect();
+        Ok(Some(Image::new(
+            MapSize::new(width, height.saturating_sub(1)),
+            |p| match rows[p.y()] {
+                Some((pattern, first_row)) if p.x() < pattern.pat.width() => {
+                    let point = MapPoint::new(p.x(), p.y() - first_row);
+                    let index = if pattern.mirror_axis {
+                        point.x()
+                    } else {
+                        point.y()
+                    };
+                    let colour = match pattern.pat.get(point) {
+                        Cell::Rock => Rgb::WHITE,
+                        Cell::Ash => Rgb::DARK_GREY,
+                    };
+                    if index + 1 == pattern.mirror_at || index == pattern.mirror_at {
+                        colour.blend(Rgb::BLUE, 0.5)
+                    } else {
+                        colour
+                    }
+                }
+                _ => Rgb::BLACK,
+            },
+        )))
+    }
+}
 
-    println!("result day 13 part 1: {}", result_part1);
-    assert_eq!(result_part1, 33_735);
-    println!("result day 13 part 2: {}", result_part2);
-    assert_eq!(result_part2, 38_063);
+#[cfg(test)]
+mod tests {
 
-    Ok(())
+    use super::*;
+
+    #[test]
+    fn test_example() -> Result<()> {
+        let input = "#.##..##.\n\
+                           ..#.##.#.\n\
+                           ##......#\n\
+                           ##......#\n\
+                           ..#.##.#.\n\
+                           ..##..##.\n\
+                           #.#.##.#.\n\
+                           \n\
+                           #...##..#\n\
+                           #....#..#\n\
+                           ..##..###\n\
+                           #####.##.\n\
+                           #####.##.\n\
+                           ..##..###\n\
+                           #....#..#";
+        let patterns = Day13::parse(input)?;
+        assert_eq!(patterns[0].pat.size(), MapSize::new(9, 7));
+        assert_eq!(Day13::part_1(&patterns)?, 405);
+        assert_eq!(Day13::part_2(&patterns)?, 400);
+        let image = Day13::render(&patterns)?.unwrap();
+        assert_eq!(image.size(), MapSize::new(9, 15));
+        assert_eq!(image.get(MapPoint::new(0, 0)), Rgb::WHITE);
+        assert_eq!(
+            image.get(MapPoint::new(4, 0)),
+            Rgb::DARK_GREY.blend(Rgb::BLUE, 0.5)
+        );
+        assert_eq!(image.get(MapPoint::new(0, 7)), Rgb::BLACK);
+        assert_eq!(
+            image.get(MapPoint::new(0, 11)),
+            Rgb::WHITE.blend(Rgb::BLUE, 0.5)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_pattern_without_mirror_line() {
+        // no clean mirror line
+        let err = Day13::parse("#.\n#.\n##\n\n#.#\n..#").err().unwrap();
+        assert_eq!(err.to_string(), "pattern 2");
+        // clean mirror line, but no mirror line with a smudge
+        assert!(Day13::parse("##\n##\n..").is_err());
+    }
 }
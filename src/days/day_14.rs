@@ -1,19 +1,22 @@
 //!day_14.rs
 
+use crate::generate::{self, InputRng};
+use crate::my_lib::my_cycle::find_cycle;
+use crate::my_lib::my_map_point::MapSize;
+use crate::my_lib::{my_compass::Compass, my_map_two_dim::MyMap2D};
+use crate::parse::{self, ParseError};
+use crate::render::{Image, Rgb};
+use crate::solution::Solution;
+use crate::step::Simulation;
 use anyhow::{anyhow, Result};
-use my_lib::{my_compass::Compass, my_map_two_dim::MyMap2D};
+use rand::Rng;
 use std::{
     collections::HashMap,
     fmt::{Debug, Display},
 };
 
-// values for X and Y taken from ../../assets/day_14.txt
-const X: usize = 100;
-const Y: usize = 100;
-
-#[derive(PartialEq, Eq, Clone, Copy, Hash, Default)]
+#[derive(PartialEq, Eq, Clone, Copy, Hash)]
 enum Cell {
-    #[default]
     None,
     Cube,
     Round,
@@ -25,13 +28,15 @@ impl Debug for Cell {
     }
 }
 
-impl From<char> for Cell {
-    fn from(value: char) -> Self {
+impl TryFrom<char> for Cell {
+    type Error = ParseError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
-            '.' => Cell::None,
-            '#' => Cell::Cube,
-            'O' => Cell::Round,
-            _ => panic!("bad Cell char"),
+            '.' => Ok(Cell::None),
+            '#' => Ok(Cell::Cube),
+            'O' => Ok(Cell::Round),
+            _ => Err(ParseError::char(value, "`.`, `#` or `O`")),
         }
     }
 }
@@ -94,18 +99,17 @@ fn rotate_round_cells_right_until_cube_in_slice(slice: &mut [Cell]) {
     }
 }
 
-//impl Hash for MyMap2D<Cell, X, Y> {}
-
-#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
-struct Parabolic<const X: usize, const Y: usize> {
-    platform: MyMap2D<Cell, X, Y>,
+/// platform with round and cube shaped rocks
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Parabolic {
+    platform: MyMap2D<Cell>,
 }
 
-impl<const X: usize, const Y: usize> Parabolic<X, Y> {
-    fn new(value: &str) -> Self {
-        Parabolic {
-            platform: value.into(),
-        }
+impl Parabolic {
+    fn new(value: &str) -> Result<Self, ParseError> {
+        Ok(Parabolic {
+            platform: parse::grid(value)?,
+        })
     }
     fn tilt_one_cycle(&mut self) -> Result<()> {
         self.tilt_direction(Compass::N)?;
@@ -113,48 +117,20 @@ impl<const X: usize, const Y: usize> Parabolic<X, Y> {
         self.tilt_direction(Compass::S)?;
         self.tilt_direction(Compass::E)
     }
-    fn tilt_num_cycles(
-        &mut self,
-        cycles: usize,
-        cache: &mut HashMap<Parabolic<X, Y>, Parabolic<X, Y>>,
-    ) -> Result<()> {
-        let mut found_cache_first_time: Option<Self> = None;
-        let mut counter_first_time = 0;
-        let mut found_pattern_cycle = false;
-        let mut return_counter = 0;
-        for counter in 1..=cycles {
-            match cache.get(self) {
-                Some(para_plat) => {
-                    *self = *para_plat;
-                    match found_cache_first_time {
-                        Some(first) => {
-                            if !found_pattern_cycle && first == *para_plat {
-                                let pattern_cycle = counter - counter_first_time;
-                                found_pattern_cycle = true;
-                                return_counter = counter + (cycles - counter) % pattern_cycle;
-                            } else if found_pattern_cycle && counter == return_counter {
-                                return Ok(());
-                            }
-                        }
-                        None => {
-                            found_cache_first_time = Some(*para_plat);
-                            counter_first_time = counter;
-                        }
-                    }
-                }
-                None => {
-                    let pre_tilt = *self;
-                    self.tilt_one_cycle()?;
-                    cache.insert(pre_tilt, *self);
-                }
-            }
-        }
+    fn tilt_num_cycles(&mut self, cycles: usize) -> Result<()> {
+        // platform repeats after some spin cycles
+        let spin_cycles = find_cycle(self.clone(), |platform| {
+            let mut platform = platform.clone();
+            platform.tilt_one_cycle()?;
+            Ok::<_, anyhow::Error>(platform)
+        })?;
+        *self = spin_cycles.state_at(cycles).clone();
         Ok(())
     }
     fn tilt_direction(&mut self, direction: Compass) -> Result<()> {
         match direction {
             Compass::N => {
-                for col in 0..X {
+                for col in 0..self.platform.width() {
                     let mut column = self.platform.get_column(col);
                     rotate_round_cells_left_until_cube_in_slice(&mut column[..]);
                     self.platform.apply_column(col, column);
@@ -162,14 +138,14 @@ impl<const X: usize, const Y: usize> Parabolic<X, Y> {
                 Ok(())
             }
             Compass::W => {
-                for row in 0..Y {
+                for row in 0..self.platform.height() {
                     let row = self.platform.get_row_mut(row);
                     rotate_round_cells_left_until_cube_in_slice(row);
                 }
                 Ok(())
             }
             Compass::S => {
-                for col in 0..X {
+                for col in 0..self.platform.width() {
                     let mut column = self.platform.get_column(col);
                     rotate_round_cells_right_until_cube_in_slice(&mut column[..]);
                     self.platform.apply_column(col, column);
@@ -177,7 +153,7 @@ impl<const X: usize, const Y: usize> Parabolic<X, Y> {
                 Ok(())
             }
             Compass::E => {
-                for row in 0..Y {
+                for row in 0..self.platform.height() {
                     let row = self.platform.get_row_mut(row);
                     rotate_round_cells_right_until_cube_in_slice(row);
                 }
@@ -186,10 +162,12 @@ impl<const X: usize, const Y: usize> Parabolic<X, Y> {
             _ => Err(anyhow!("direction not available")),
         }
     }
-    fn calc_total_load_north(&self) -> usize {
+    /// total load of rounded rocks on north support beams
+    pub fn calc_total_load_north(&self) -> usize {
         let mut total_load_north = 0;
-        for row in 0..Y {
-            total_load_north += (Y - row)
+        let height = self.platform.height();
+        for row in 0..height {
+            total_load_north += (height - row)
                 * self
                     .platform
                     .iter_row(row)
@@ -200,36 +178,153 @@ impl<const X: usize, const Y: usize> Parabolic<X, Y> {
     }
 }
 
-pub fn day_14() -> Result<()> {
-    let input = include_str!("../../assets/day_14.txt");
-    let mut parabolic_platform: Parabolic<X, Y> = Parabolic::new(input);
-    parabolic_platform.tilt_direction(Compass::N)?;
-    let result_part1 = parabolic_platform.calc_total_load_north();
-    println!("result day 14 part 1: {}", result_part1);
-    assert_eq!(result_part1, 108_144);
+/// tilts platform in one direction per step, until platform after a spin cycle repeats
+struct TiltSimulation {
+    parabolic_platform: Parabolic,
+    tilts: u64,
+    // platforms after each spin cycle and their cycle
+    seen: HashMap<Parabolic, u64>,
+    pattern_cycle: Option<u64>,
+}
 
-    // part 2: reset platform
-    let mut cache: HashMap<Parabolic<X, Y>, Parabolic<X, Y>> = HashMap::new();
-    parabolic_platform = Parabolic::new(input);
-    parabolic_platform.tilt_num_cycles(1_000_000_000, &mut cache)?;
-    let result_part2 = parabolic_platform.calc_total_load_north();
-    println!("result day 14 part 2: {}", result_part2);
-    assert_eq!(result_part2, 108_404);
+impl TiltSimulation {
+    fn new(parabolic_platform: &Parabolic) -> Self {
+        TiltSimulation {
+            seen: HashMap::from([(parabolic_platform.clone(), 0)]),
+            parabolic_platform: parabolic_platform.clone(),
+            tilts: 0,
+            pattern_cycle: None,
+        }
+    }
+}
 
-    Ok(())
+impl Simulation for TiltSimulation {
+    fn step(&mut self) -> Result<bool> {
+        if self.pattern_cycle.is_some() {
+            return Ok(false);
+        }
+        let direction = [Compass::N, Compass::W, Compass::S, Compass::E][self.tilts as usize % 4];
+        self.parabolic_platform.tilt_direction(direction)?;
+        self.tilts += 1;
+        if self.tilts.is_multiple_of(4) {
+            let cycle = self.tilts / 4;
+            if let Some(first) = self.seen.insert(self.parabolic_platform.clone(), cycle) {
+                self.pattern_cycle = Some(cycle - first);
+            }
+        }
+        Ok(true)
+    }
+    fn frame(&self) -> String {
+        self.parabolic_platform.platform.to_string()
+    }
+    fn counters(&self) -> Vec<(&'static str, u64)> {
+        let mut counters = vec![
+            ("spin cycles", self.tilts / 4),
+            ("tilts", self.tilts),
+            (
+                "load",
+                self.parabolic_platform.calc_total_load_north() as u64,
+            ),
+        ];
+        if let Some(pattern_cycle) = self.pattern_cycle {
+            counters.push(("pattern cycle", pattern_cycle));
+        }
+        counters
+    }
+}
+
+/// Parabolic Reflector Dish: load on north beams after tilting north (part 1) and after
+/// 1_000_000_000 spin cycles (part 2)
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+    const GEN_SIZE: (usize, &'static str) = (100, "width and height of platform");
+    type Input<'a> = Parabolic;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        Ok(Parabolic::new(input)?)
+    }
+    fn part_1(parabolic_platform: &Self::Input<'_>) -> Result<Self::Answer1> {
+        let mut parabolic_platform = parabolic_platform.clone();
+        parabolic_platform.tilt_direction(Compass::N)?;
+        Ok(parabolic_platform.calc_total_load_north())
+    }
+    fn part_2(parabolic_platform: &Self::Input<'_>) -> Result<Self::Answer2> {
+        let mut parabolic_platform = parabolic_platform.clone();
+        parabolic_platform.tilt_num_cycles(1_000_000_000)?;
+        Ok(parabolic_platform.calc_total_load_north())
+    }
+    fn generate(rng: &mut InputRng, size: usize) -> Result<String> {
+        Ok(generate::grid(
+            rng,
+            MapSize::new(size, size),
+            |rng, _| match rng.gen_range(0..20) {
+                0..=3 => 'O',
+                4..=6 => '#',
+                _ => '.',
+            },
+        ))
+    }
+    fn simulation<'a>(
+        parabolic_platform: &'a Self::Input<'_>,
+    ) -> Result<Option<Box<dyn Simulation + 'a>>> {
+        Ok(Some(Box::new(TiltSimulation::new(parabolic_platform))))
+    }
+    // platform after spin cycles of part 2
+    fn render(parabolic_platform: &Self::Input<'_>) -> Result<Option<Image>> {
+        let mut parabolic_platform = parabolic_platform.clone();
+        parabolic_platform.tilt_num_cycles(1_000_000_000)?;
+        Ok(Some(Image::from_map(
+            &parabolic_platform.platform,
+            |_, c| match c {
+                Cell::None => Rgb::BLACK,
+                Cell::Cube => Rgb::GREY,
+                Cell::Round => Rgb::ORANGE,
+            },
+        )))
+    }
 }
 
 #[cfg(test)]
 mod tests {
 
     use super::*;
+    use crate::my_lib::my_map_point::MapPoint;
+
+    const TEST_INPUT: &str = "O....#....\n\
+                              O.OO#....#\n\
+                              .....##...\n\
+                              OO.#O....O\n\
+                              .O.....O#.\n\
+                              O.#..O.#.#\n\
+                              ..O..#O..O\n\
+                              .......O..\n\
+                              #....###..\n\
+                              #OO..#....";
+
+    #[test]
+    fn test_example() -> Result<()> {
+        let parabolic_platform = Day14::parse(TEST_INPUT)?;
+        assert_eq!(Day14::part_1(&parabolic_platform)?, 136);
+        assert_eq!(Day14::part_2(&parabolic_platform)?, 64);
+        // load of rendered round rocks is answer of part 2
+        let image = Day14::render(&parabolic_platform)?.unwrap();
+        let load: usize = (0..10)
+            .flat_map(|y| (0..10).map(move |x| MapPoint::new(x, y)))
+            .filter(|p| image.get(*p) == Rgb::ORANGE)
+            .map(|p| 10 - p.y())
+            .sum();
+        assert_eq!(load, 64);
+        Ok(())
+    }
 
     #[test]
     fn test_slice_rotation_with_column() {
-        let input = include_str!("../../assets/day_14.txt");
-        let mut parabolic_platform: Parabolic<X, Y> = Parabolic::new(input);
+        let mut parabolic_platform = Parabolic::new(TEST_INPUT).unwrap();
         let mut col_0 = parabolic_platform.platform.get_column(0);
-        eprintln!("before rotation: {:?}", col_0);
         let start = col_0.iter().position(|c| *c != Cell::Cube).unwrap();
         let end = start
             + col_0
@@ -238,43 +333,37 @@ mod tests {
                 .position(|c| *c == Cell::Cube)
                 .unwrap();
         let slice = &mut col_0[start..end];
-        eprintln!("start: {}, end: {}", start, end);
-        eprintln!("slice: {:?}", slice);
         slice.rotate_left(1);
-        eprintln!("slice after rotation left 1: {:?}", slice);
-        eprintln!("column after rotation left 1: {:?}", col_0);
-        eprintln!("{}", parabolic_platform.platform);
         parabolic_platform.platform.apply_column(0, col_0);
-        eprintln!(
-            "parabolic_platform after slice rotation in col 0\n{}",
-            parabolic_platform.platform
-        );
+        let col_0: String = parabolic_platform
+            .platform
+            .iter_column(0)
+            .map(|(_, c)| c.to_string())
+            .collect();
+        assert_eq!(col_0, "O.O.O..O##");
     }
 
     #[test]
     fn test_tilting() {
-        let input = include_str!("../../assets/day_14.txt");
-        let mut parabolic_platform: Parabolic<X, Y> = Parabolic::new(input);
-        let col_index = 20;
+        let mut parabolic_platform = Parabolic::new(TEST_INPUT).unwrap();
+        let col_index = 2;
         // for column only N or S
         let direction = Compass::S;
         let mut col = String::new();
         for c in parabolic_platform.platform.get_column(col_index).iter() {
             col = format!("{}{}", col, c);
         }
-        eprintln!("col {}:\n{}", col_index, col);
+        assert_eq!(col, ".O...#O..O");
         parabolic_platform.tilt_direction(direction).unwrap();
         col = "".into();
         for c in parabolic_platform.platform.get_column(col_index).iter() {
             col = format!("{}{}", col, c);
         }
-        eprintln!("col {} after tilt north\n{}", col_index, col);
+        assert_eq!(col, "....O#..OO");
     }
 
     #[test]
     fn test_ccyling() {
-        const XT: usize = 10;
-        const YT: usize = 10;
         let input = "O....#....\n\
                            O.OO#....#\n\
                            .....##...\n\
@@ -285,8 +374,7 @@ mod tests {
                            .......O..\n\
                            #....###..\n\
                            #OO..#....";
-        let mut parabolic_platform: Parabolic<XT, YT> = Parabolic::new(input);
-        eprintln!("{}", parabolic_platform.platform);
+        let mut parabolic_platform: Parabolic = Parabolic::new(input).unwrap();
         let one_cycle = ".....#....\n\
                                ....#...O#\n\
                                ...OO##...\n\
@@ -297,9 +385,8 @@ mod tests {
                                ......OOOO\n\
                                #...O###..\n\
                                #..OO#....";
-        let one_cycle: Parabolic<XT, YT> = Parabolic::new(one_cycle);
+        let one_cycle: Parabolic = Parabolic::new(one_cycle).unwrap();
         parabolic_platform.tilt_one_cycle().unwrap();
-        eprintln!("one cycle\n{}", parabolic_platform.platform);
         assert_eq!(one_cycle, parabolic_platform);
         let two_cycles = ".....#....\n\
                                 ....#...O#\n\
@@ -311,9 +398,8 @@ mod tests {
                                 .......OOO\n\
                                 #..OO###..\n\
                                 #.OOO#...O";
-        let two_cycles: Parabolic<XT, YT> = Parabolic::new(two_cycles);
+        let two_cycles: Parabolic = Parabolic::new(two_cycles).unwrap();
         parabolic_platform.tilt_one_cycle().unwrap();
-        eprintln!("two cycle\n{}", parabolic_platform.platform);
         assert_eq!(two_cycles, parabolic_platform);
         let three_cycles = ".....#....\n\
                                   ....#...O#\n\
@@ -325,16 +411,41 @@ mod tests {
                                   .......OOO\n\
                                   #...O###.O\n\
                                   #.OOO#...O";
-        let three_cycles: Parabolic<XT, YT> = Parabolic::new(three_cycles);
+        let three_cycles: Parabolic = Parabolic::new(three_cycles).unwrap();
         parabolic_platform.tilt_one_cycle().unwrap();
-        eprintln!("three cycle\n{}", parabolic_platform.platform);
         assert_eq!(three_cycles, parabolic_platform);
         // test full cycling
-        let mut cache: HashMap<Parabolic<XT, YT>, Parabolic<XT, YT>> = HashMap::new();
-        parabolic_platform = Parabolic::new(input);
-        parabolic_platform
-            .tilt_num_cycles(1_000_000_000, &mut cache)
-            .unwrap();
+        parabolic_platform = Parabolic::new(input).unwrap();
+        parabolic_platform.tilt_num_cycles(1_000_000_000).unwrap();
         assert_eq!(parabolic_platform.calc_total_load_north(), 64);
     }
+
+    #[test]
+    fn test_tilt_cycles_of_fixed_platform() {
+        // platform does not change after first cycle, therefore pattern cycle is 1
+        let mut parabolic_platform = Parabolic::new("#.\n.O").unwrap();
+        parabolic_platform.tilt_num_cycles(1_000_000_000).unwrap();
+        assert_eq!(parabolic_platform.calc_total_load_north(), 1);
+    }
+
+    #[test]
+    fn test_tilt_simulation() -> Result<()> {
+        let parabolic_platform = Day14::parse(TEST_INPUT)?;
+        let mut simulation = TiltSimulation::new(&parabolic_platform);
+        simulation.step()?;
+        // first tilt is north
+        assert_eq!(simulation.counters()[2], ("load", 136));
+        while simulation.step()? {}
+        // platform after 3 spin cycles repeats after 7 further spin cycles
+        assert_eq!(
+            simulation.counters(),
+            vec![
+                ("spin cycles", 10),
+                ("tilts", 40),
+                ("load", 69),
+                ("pattern cycle", 7)
+            ]
+        );
+        Ok(())
+    }
 }
@@ -1,6 +1,10 @@
 //!day_15.rs
 
+use crate::generate::InputRng;
+use crate::parse::{self, ParseError};
+use crate::solution::Solution;
 use anyhow::Result;
+use rand::Rng;
 use std::collections::HashMap;
 
 struct AoCHash {
@@ -8,18 +12,20 @@ struct AoCHash {
     hash: u64,
 }
 
-impl From<&str> for AoCHash {
-    fn from(value: &str) -> Self {
+impl TryFrom<&str> for AoCHash {
+    type Error = ParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
         let mut result = AoCHash { hash: 0 };
-        for c in value.chars() {
+        for (index, c) in value.char_indices() {
             if !c.is_ascii() {
-                panic!("bad AoCHash input");
+                return Err(ParseError::char(c, "ascii char").at(&value[index..]));
             }
             result.hash += c as u64;
             result.hash *= 17;
             result.hash %= 256;
         }
-        result
+        Ok(result)
     }
 }
 
@@ -40,59 +46,89 @@ impl From<(String, u64)> for Lens {
     }
 }
 
-pub fn day_15() -> Result<()> {
-    let input = include_str!("../../assets/day_15.txt");
-    let result_part1: u64 = input.split(',').map(|c| AoCHash::from(c).hash).sum();
-    println!("result day 15 part 1: {}", result_part1);
-    assert_eq!(result_part1, 512_283);
+/// Lens Library: sum of HASH values of steps (part 1) and focusing power of lenses (part 2)
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+    const GEN_SIZE: (usize, &'static str) = (4_000, "number of steps");
+    /// initialization sequence
+    type Input<'a> = Vec<&'a str>;
+    type Answer1 = u64;
+    type Answer2 = u64;
 
-    // part 2
-    let mut lens_label_box_cache: HashMap<u64, Vec<Lens>> = HashMap::new();
-    for instruction in input.split(',') {
-        if instruction.contains('-') {
-            // remove lens from box, if it exists
-            let label = instruction.split_once('-').unwrap().0.to_string();
-            let key = AoCHash::from(label.as_str()).hash;
-            if let Some(lenses) = lens_label_box_cache.get_mut(&key) {
-                if let Some(index) = lenses.iter().position(|l| l.label == label) {
-                    lenses.remove(index);
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        Ok(input.split(',').collect())
+    }
+    fn part_1(input: &Self::Input<'_>) -> Result<Self::Answer1> {
+        let mut result_part1: u64 = 0;
+        for step in input.iter() {
+            result_part1 += AoCHash::try_from(*step)?.hash;
+        }
+        Ok(result_part1)
+    }
+    fn part_2(input: &Self::Input<'_>) -> Result<Self::Answer2> {
+        let mut lens_label_box_cache: HashMap<u64, Vec<Lens>> = HashMap::new();
+        for instruction in input.iter() {
+            if instruction.contains('-') {
+                // remove lens from box, if it exists
+                let (label, _) = parse::split_once(instruction, "-")?;
+                let key = AoCHash::try_from(label)?.hash;
+                if let Some(lenses) = lens_label_box_cache.get_mut(&key) {
+                    if let Some(index) = lenses.iter().position(|l| l.label == label) {
+                        lenses.remove(index);
+                    }
                 }
-            }
-        } else if instruction.contains('=') {
-            // add lens to box
-            let lens = Lens::from(
-                instruction
-                    .split_once('=')
-                    .map(|(l, f)| (l.to_string(), f.parse::<u64>().expect("bad instruction")))
-                    .unwrap(),
-            );
-            let key = AoCHash::from(lens.label.as_str()).hash;
-            match lens_label_box_cache.get_mut(&key) {
-                Some(lenses) => match lenses.iter().position(|l| l.label == lens.label) {
-                    Some(pos) => lenses[pos] = lens,
-                    None => lenses.push(lens),
-                },
-                None => {
-                    lens_label_box_cache.insert(key, vec![lens]);
+            } else if instruction.contains('=') {
+                // add lens to box
+                let (label, focal_length) = parse::split_once(instruction, "=")?;
+                let key = AoCHash::try_from(label)?.hash;
+                let lens = Lens::from((label.to_string(), parse::number::<u64>(focal_length)?));
+                match lens_label_box_cache.get_mut(&key) {
+                    Some(lenses) => match lenses.iter().position(|l| l.label == lens.label) {
+                        Some(pos) => lenses[pos] = lens,
+                        None => lenses.push(lens),
+                    },
+                    None => {
+                        lens_label_box_cache.insert(key, vec![lens]);
+                    }
                 }
+            } else {
+                return Err(ParseError::new(instruction, "instruction with `-` or `=`").into());
             }
-        } else {
-            panic!("bad instruction");
         }
+        let mut result_part2: u64 = 0;
+        for (key, lenses) in lens_label_box_cache.iter() {
+            result_part2 += (key + 1)
+                * lenses
+                    .iter()
+                    .enumerate()
+                    .map(|(i, l)| ((i + 1) as u64) * l.focal_length)
+                    .sum::<u64>();
+        }
+        Ok(result_part2)
     }
-    let mut result_part2: u64 = 0;
-    for (key, lenses) in lens_label_box_cache.iter() {
-        result_part2 += (key + 1)
-            * lenses
-                .iter()
-                .enumerate()
-                .map(|(i, l)| ((i + 1) as u64) * l.focal_length)
-                .sum::<u64>();
+    fn generate(rng: &mut InputRng, size: usize) -> Result<String> {
+        let labels: Vec<String> = (0..size / 3 + 1)
+            .map(|_| {
+                (0..rng.gen_range(2..=6))
+                    .map(|_| rng.gen_range('a'..='z'))
+                    .collect()
+            })
+            .collect();
+        let steps: Vec<String> = (0..size)
+            .map(|_| {
+                let label = &labels[rng.gen_range(0..labels.len())];
+                if rng.gen_bool(0.3) {
+                    format!("{}-", label)
+                } else {
+                    format!("{}={}", label, rng.gen_range(1..=9))
+                }
+            })
+            .collect();
+        // initialization sequence is a single line without line break
+        Ok(steps.join(","))
     }
-    println!("result day 15 part 2: {}", result_part2);
-    assert_eq!(result_part2, 215_827);
-
-    Ok(())
 }
 
 #[cfg(test)]
@@ -108,13 +144,22 @@ mod tests {
         assert_eq!(char_codes.next().unwrap(), 65);
         assert_eq!(char_codes.next().unwrap(), 83);
         assert_eq!(char_codes.next().unwrap(), 72);
-        assert_eq!(AoCHash::from(chars).hash, 52);
+        assert_eq!(AoCHash::try_from(chars).unwrap().hash, 52);
 
-        assert_eq!(AoCHash::from("rn").hash, 0);
-        assert_eq!(AoCHash::from("qp").hash, 1);
-        assert_eq!(AoCHash::from("cm").hash, 0);
-        assert_eq!(AoCHash::from("pc").hash, 3);
-        assert_eq!(AoCHash::from("ot").hash, 3);
-        assert_eq!(AoCHash::from("ab").hash, 3);
+        assert_eq!(AoCHash::try_from("rn").unwrap().hash, 0);
+        assert_eq!(AoCHash::try_from("qp").unwrap().hash, 1);
+        assert_eq!(AoCHash::try_from("cm").unwrap().hash, 0);
+        assert_eq!(AoCHash::try_from("pc").unwrap().hash, 3);
+        assert_eq!(AoCHash::try_from("ot").unwrap().hash, 3);
+        assert_eq!(AoCHash::try_from("ab").unwrap().hash, 3);
+    }
+
+    #[test]
+    fn test_example() -> Result<()> {
+        let input = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
+        let steps = Day15::parse(input)?;
+        assert_eq!(Day15::part_1(&steps)?, 1320);
+        assert_eq!(Day15::part_2(&steps)?, 145);
+        Ok(())
     }
 }
@@ -1,11 +1,14 @@
 //!day_16.rs
 
+use crate::generate::{self, InputRng};
+use crate::my_lib::my_map_point::MapSize;
+use crate::my_lib::{my_compass::Compass, my_map_point::MapPoint, my_map_two_dim::MyMap2D};
+use crate::parse::{self, ParseError};
+use crate::render::{Image, Rgb};
+use crate::solution::Solution;
+use crate::step::Simulation;
 use anyhow::Result;
-use my_lib::{my_compass::Compass, my_map_point::MapPoint, my_map_two_dim::MyMap2D};
-
-// values taken from ../../assets/day_16.txt
-const X: usize = 110;
-const Y: usize = 110;
+use rand::Rng;
 
 #[derive(Default, Clone, Copy)]
 enum CellType {
@@ -17,15 +20,17 @@ enum CellType {
     SplitterHorizontal,
 }
 
-impl From<char> for CellType {
-    fn from(value: char) -> Self {
+impl TryFrom<char> for CellType {
+    type Error = ParseError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
-            '.' => CellType::Empty,
-            '/' => CellType::MirrorBottomLeftTopRight,
-            '\\' => CellType::MirrorTopLeftBottomRight,
-            '|' => CellType::SplitterVertical,
-            '-' => CellType::SplitterHorizontal,
-            _ => panic!("Bad cell char"),
+            '.' => Ok(CellType::Empty),
+            '/' => Ok(CellType::MirrorBottomLeftTopRight),
+            '\\' => Ok(CellType::MirrorTopLeftBottomRight),
+            '|' => Ok(CellType::SplitterVertical),
+            '-' => Ok(CellType::SplitterHorizontal),
+            _ => Err(ParseError::char(value, "`.`, `/`, `\\`, `|` or `-`")),
         }
     }
 }
@@ -78,12 +83,14 @@ struct Cell {
     beam_exit_w: bool,
 }
 
-impl From<char> for Cell {
-    fn from(value: char) -> Self {
-        Cell {
-            ctype: CellType::from(value),
+impl TryFrom<char> for Cell {
+    type Error = ParseError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        Ok(Cell {
+            ctype: CellType::try_from(value)?,
             ..Default::default()
-        }
+        })
     }
 }
 
@@ -120,55 +127,66 @@ impl Cell {
     }
 }
 
-#[derive(Default)]
-struct MirrorChamber<const X: usize, const Y: usize> {
-    map: MyMap2D<Cell, X, Y>,
+/// contraption of mirrors and splitters
+#[derive(Default, Clone)]
+pub struct MirrorChamber {
+    map: MyMap2D<Cell>,
 }
 
-impl<const X: usize, const Y: usize> From<&str> for MirrorChamber<X, Y> {
-    fn from(value: &str) -> Self {
-        MirrorChamber {
-            map: MyMap2D::from(value),
-        }
+impl TryFrom<&str> for MirrorChamber {
+    type Error = ParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Ok(MirrorChamber {
+            map: parse::grid(value)?,
+        })
     }
 }
 
-impl<const X: usize, const Y: usize> MirrorChamber<X, Y> {
+impl MirrorChamber {
     fn part1_beam_movement(&mut self) -> usize {
         self.beam_movement((0, 0).into(), Compass::E);
         self.energized_cells()
     }
-    fn beam_movement(&mut self, current_cell: MapPoint<X, Y>, beam_direction: Compass) {
-        let (beam_1, beam_2) = self.map.get_mut(current_cell).beam_movement(beam_direction);
-        if let Some(beam_1_direction) = beam_1 {
-            if let Some(next_cell) = current_cell.neighbor(beam_1_direction) {
-                self.beam_movement(next_cell, beam_1_direction);
-            }
-        }
-        if let Some(beam_2_direction) = beam_2 {
-            if let Some(next_cell) = current_cell.neighbor(beam_2_direction) {
-                self.beam_movement(next_cell, beam_2_direction);
-            }
+    fn beam_movement(&mut self, current_cell: MapPoint, beam_direction: Compass) {
+        for (next_cell, next_direction) in self.next_beams(current_cell, beam_direction) {
+            self.beam_movement(next_cell, next_direction);
         }
     }
+    // beam enters current_cell; returns cells and directions of beams leaving it, which are new
+    // and stay inside of map
+    fn next_beams(
+        &mut self,
+        current_cell: MapPoint,
+        beam_direction: Compass,
+    ) -> Vec<(MapPoint, Compass)> {
+        let (beam_1, beam_2) = self.map.get_mut(current_cell).beam_movement(beam_direction);
+        [beam_1, beam_2]
+            .into_iter()
+            .flatten()
+            .filter_map(|d| self.map.neighbor(current_cell, d).map(|n| (n, d)))
+            .collect()
+    }
     fn energized_cells(&self) -> usize {
         self.map.iter().filter(|(_, c)| c.beam_counter > 0).count()
     }
     fn part2_beam_movement(&mut self) -> usize {
         let mut max_energy = 0;
-        for point in MapPoint::<X, Y>::new(0, 0).iter_edge(false) {
+        let size = self.map.size();
+        for point in MapPoint::new(0, 0).iter_edge(false, size) {
             self.reset_beam_data();
-            match point.map_position() {
+            let map_position = point.map_position(size);
+            match map_position {
                 Compass::NE | Compass::SE | Compass::SW | Compass::NW => {
                     // corner -> check from two directions
-                    self.beam_movement(point, point.map_position().clockwise().flip());
+                    self.beam_movement(point, map_position.clockwise().flip());
                     max_energy = max_energy.max(self.energized_cells());
                     self.reset_beam_data();
-                    self.beam_movement(point, point.map_position().counterclockwise().flip());
+                    self.beam_movement(point, map_position.counterclockwise().flip());
                     max_energy = max_energy.max(self.energized_cells());
                 }
                 Compass::N | Compass::E | Compass::S | Compass::W => {
-                    self.beam_movement(point, point.map_position().flip());
+                    self.beam_movement(point, map_position.flip());
                     max_energy = max_energy.max(self.energized_cells());
                 }
                 Compass::Center => (),
@@ -187,17 +205,117 @@ impl<const X: usize, const Y: usize> MirrorChamber<X, Y> {
     }
 }
 
-pub fn day_16() -> Result<()> {
-    let input = include_str!("../../assets/day_16.txt");
-    let mut mirror_chamber = MirrorChamber::<X, Y>::from(input);
-    let result_part1 = mirror_chamber.part1_beam_movement();
-    println!("result day 16 part 1: {}", result_part1);
-    assert_eq!(result_part1, 7_498);
-    let result_part2 = mirror_chamber.part2_beam_movement();
-    println!("result day 16 part 2: {}", result_part2);
-    assert_eq!(result_part2, 7_846);
-
-    Ok(())
+/// moves all beams of part 1 by one cell per step, until no beam is left
+struct BeamSimulation {
+    mirror_chamber: MirrorChamber,
+    beams: Vec<(MapPoint, Compass)>,
+}
+
+impl Simulation for BeamSimulation {
+    fn step(&mut self) -> Result<bool> {
+        if self.beams.is_empty() {
+            return Ok(false);
+        }
+        let beams = std::mem::take(&mut self.beams);
+        for (cell, direction) in beams {
+            let next_beams = self.mirror_chamber.next_beams(cell, direction);
+            self.beams.extend(next_beams);
+        }
+        Ok(true)
+    }
+    fn frame(&self) -> String {
+        let mut frame = String::with_capacity(
+            (self.mirror_chamber.map.width() + 1) * self.mirror_chamber.map.height(),
+        );
+        for (point, cell) in self.mirror_chamber.map.iter() {
+            let beam = self.beams.iter().find(|(p, _)| *p == point);
+            frame.push(match (beam, cell.ctype) {
+                (Some((_, Compass::N)), _) => '^',
+                (Some((_, Compass::E)), _) => '>',
+                (Some((_, Compass::S)), _) => 'v',
+                (Some(_), _) => '<',
+                (None, CellType::Empty) if cell.beam_counter > 0 => '#',
+                (None, CellType::Empty) => '.',
+                (None, CellType::MirrorBottomLeftTopRight) => '/',
+                (None, CellType::MirrorTopLeftBottomRight) => '\\',
+                (None, CellType::SplitterVertical) => '|',
+                (None, CellType::SplitterHorizontal) => '-',
+            });
+            if point.x() + 1 == self.mirror_chamber.map.width() {
+                frame.push('\n');
+            }
+        }
+        frame
+    }
+    fn counters(&self) -> Vec<(&'static str, u64)> {
+        let beam_counter = self
+            .mirror_chamber
+            .map
+            .iter()
+            .map(|(_, c)| c.beam_counter as u64)
+            .sum();
+        vec![
+            ("beams", self.beams.len() as u64),
+            ("beam_counter", beam_counter),
+            (
+                "energized cells",
+                self.mirror_chamber.energized_cells() as u64,
+            ),
+        ]
+    }
+}
+
+/// The Floor Will Be Lava: energized tiles of beam from top left corner (part 1) and of best entry
+/// point (part 2)
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+    const GEN_SIZE: (usize, &'static str) = (110, "width and height of contraption");
+    type Input<'a> = MirrorChamber;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        Ok(MirrorChamber::try_from(input)?)
+    }
+    fn part_1(mirror_chamber: &Self::Input<'_>) -> Result<Self::Answer1> {
+        Ok(mirror_chamber.clone().part1_beam_movement())
+    }
+    fn part_2(mirror_chamber: &Self::Input<'_>) -> Result<Self::Answer2> {
+        Ok(mirror_chamber.clone().part2_beam_movement())
+    }
+    fn generate(rng: &mut InputRng, size: usize) -> Result<String> {
+        let devices = ['/', '\\', '|', '-'];
+        Ok(generate::grid(rng, MapSize::new(size, size), |rng, _| {
+            if rng.gen_bool(0.1) {
+                devices[rng.gen_range(0..devices.len())]
+            } else {
+                '.'
+            }
+        }))
+    }
+    fn simulation<'a>(
+        mirror_chamber: &'a Self::Input<'_>,
+    ) -> Result<Option<Box<dyn Simulation + 'a>>> {
+        Ok(Some(Box::new(BeamSimulation {
+            mirror_chamber: mirror_chamber.clone(),
+            beams: vec![((0, 0).into(), Compass::E)],
+        })))
+    }
+    // energized cells of part 1; energized devices are orange
+    fn render(mirror_chamber: &Self::Input<'_>) -> Result<Option<Image>> {
+        let mut mirror_chamber = mirror_chamber.clone();
+        mirror_chamber.part1_beam_movement();
+        Ok(Some(Image::from_map(&mirror_chamber.map, |_, c| {
+            match (c.ctype, c.beam_counter > 0) {
+                (CellType::Empty, true) => Rgb::YELLOW,
+                (CellType::Empty, false) => Rgb::DARK_GREY,
+                (_, true) => Rgb::ORANGE,
+                (_, false) => Rgb::WHITE,
+            }
+        })))
+    }
 }
 
 #[cfg(test)]
@@ -205,9 +323,6 @@ mod tests {
 
     use super::*;
 
-    const XT: usize = 10;
-    const YT: usize = 10;
-
     #[test]
     fn test_part1_example() {
         let input = ".|...\\....\n\
@@ -220,14 +335,27 @@ mod tests {
                            .-.-/..|..\n\
                            .|....-|.\\\n\
                            ..//.|....";
-        let mut mirror_chamber = MirrorChamber::<XT, YT>::from(input);
+        let mut mirror_chamber = MirrorChamber::try_from(input).unwrap();
         mirror_chamber.part1_beam_movement();
         let result_part1 = mirror_chamber
             .map
             .iter()
             .filter(|(_, c)| c.beam_counter > 0)
             .count();
-        println!("result day 16 example part 1: {}", result_part1);
         assert_eq!(result_part1, 46);
+        let mirror_chamber = Day16::parse(input).unwrap();
+        assert_eq!(Day16::part_2(&mirror_chamber).unwrap(), 51);
+        let image = Day16::render(&mirror_chamber).unwrap().unwrap();
+        let energized = (0..10)
+            .flat_map(|y| (0..10).map(move |x| MapPoint::new(x, y)))
+            .filter(|p| matches!(image.get(*p), Rgb::YELLOW | Rgb::ORANGE))
+            .count();
+        assert_eq!(energized, 46);
+        let mut simulation = Day16::simulation(&mirror_chamber).unwrap().unwrap();
+        assert_eq!(simulation.frame().lines().next(), Some(">|...\\...."));
+        while simulation.step().unwrap() {}
+        assert_eq!(simulation.counters()[0], ("beams", 0));
+        assert_eq!(simulation.counters()[2], ("energized cells", 46));
+        assert_eq!(simulation.frame().lines().next(), Some("#|###\\...."));
     }
 }
@@ -1,45 +1,39 @@
 //!day_17.rs
 
-use anyhow::Result;
-use my_lib::my_compass::Compass;
-use my_lib::my_map_point::MapPoint;
+use crate::generate::{self, InputRng};
+use crate::my_lib::my_compass::Compass;
+use crate::my_lib::my_map_point::{MapPoint, MapSize};
+use crate::my_lib::my_map_two_dim::MyMap2D;
+use crate::my_lib::my_search::astar;
+use crate::parse::{self, ParseError};
+use crate::render::{Image, Rgb};
+use crate::solution::Solution;
+use anyhow::{anyhow, Result};
+use rand::Rng;
+use std::fmt::Debug;
+use std::hash::Hash;
+use tracing::debug_span;
 
-#[cfg(feature = "long-run-time")]
-use anyhow::anyhow;
-#[cfg(feature = "long-run-time")]
-use my_lib::my_map_two_dim::MyMap2D;
-
-#[cfg(feature = "long-run-time")]
-use std::cmp::Ordering;
-#[cfg(feature = "long-run-time")]
-use std::collections::BinaryHeap;
-
-// values taken from ../../assets/day_17.txt
-#[cfg(feature = "long-run-time")]
-const X: usize = 141;
-#[cfg(feature = "long-run-time")]
-const Y: usize = 141;
-
-trait PathNode<const X: usize, const Y: usize>: Default + PartialEq + Eq + Copy + Clone {
-    fn step_forward(&self) -> Option<Self>;
-    fn step_left(&self) -> Option<Self>;
-    fn step_right(&self) -> Option<Self>;
-    fn get_city_block(&self) -> MapPoint<X, Y>;
+trait PathNode: Debug + Default + PartialEq + Eq + Hash + Copy + Clone {
+    fn step_forward(&self, size: MapSize) -> Option<Self>;
+    fn step_left(&self, size: MapSize) -> Option<Self>;
+    fn step_right(&self, size: MapSize) -> Option<Self>;
+    fn get_city_block(&self) -> MapPoint;
 }
 
-#[derive(Default, PartialEq, Eq, Copy, Clone)]
-struct NormalCrucible<const X: usize, const Y: usize> {
-    city_block: MapPoint<X, Y>,
+#[derive(Debug, Default, PartialEq, Eq, Hash, Copy, Clone)]
+struct NormalCrucible {
+    city_block: MapPoint,
     direction: Compass,
     n_steps: u8,
 }
 
-impl<const X: usize, const Y: usize> PathNode<X, Y> for NormalCrucible<X, Y> {
-    fn step_forward(&self) -> Option<Self> {
+impl PathNode for NormalCrucible {
+    fn step_forward(&self, size: MapSize) -> Option<Self> {
         if self.direction.is_center() || self.n_steps == 3 {
             return None;
         }
-        if let Some(next_city_block) = self.city_block.neighbor(self.direction) {
+        if let Some(next_city_block) = self.city_block.neighbor(self.direction, size) {
             return Some(Self {
                 city_block: next_city_block,
                 direction: self.direction,
@@ -48,17 +42,17 @@ impl<const X: usize, const Y: usize> PathNode<X, Y> for NormalCrucible<X, Y> {
         }
         None
     }
-    fn step_left(&self) -> Option<Self> {
+    fn step_left(&self, size: MapSize) -> Option<Self> {
         if self.direction.is_center() {
             // start of search: left is Compass::E
             return Some(Self {
-                city_block: self.city_block.neighbor(Compass::E).unwrap(),
+                city_block: self.city_block.neighbor(Compass::E, size).unwrap(),
                 direction: Compass::E,
                 n_steps: 1,
             });
         }
         let left = self.direction.counterclockwise().counterclockwise();
-        if let Some(next_city_block) = self.city_block.neighbor(left) {
+        if let Some(next_city_block) = self.city_block.neighbor(left, size) {
             return Some(Self {
                 city_block: next_city_block,
                 direction: left,
@@ -67,17 +61,17 @@ impl<const X: usize, const Y: usize> PathNode<X, Y> for NormalCrucible<X, Y> {
         }
         None
     }
-    fn step_right(&self) -> Option<Self> {
+    fn step_right(&self, size: MapSize) -> Option<Self> {
         if self.direction.is_center() {
             // start of search: right is Compass::S
             return Some(Self {
-                city_block: self.city_block.neighbor(Compass::S).unwrap(),
+                city_block: self.city_block.neighbor(Compass::S, size).unwrap(),
                 direction: Compass::S,
                 n_steps: 1,
             });
         }
         let right = self.direction.clockwise().clockwise();
-        if let Some(next_city_block) = self.city_block.neighbor(right) {
+        if let Some(next_city_block) = self.city_block.neighbor(right, size) {
             return Some(Self {
                 city_block: next_city_block,
                 direction: right,
@@ -86,24 +80,24 @@ impl<const X: usize, const Y: usize> PathNode<X, Y> for NormalCrucible<X, Y> {
         }
         None
     }
-    fn get_city_block(&self) -> MapPoint<X, Y> {
+    fn get_city_block(&self) -> MapPoint {
         self.city_block
     }
 }
 
-#[derive(Default, PartialEq, Eq, Copy, Clone)]
-struct UltraCrucible<const X: usize, const Y: usize> {
-    city_block: MapPoint<X, Y>,
+#[derive(Debug, Default, PartialEq, Eq, Hash, Copy, Clone)]
+struct UltraCrucible {
+    city_block: MapPoint,
     direction: Compass,
     n_steps: u8,
 }
 
-impl<const X: usize, const Y: usize> PathNode<X, Y> for UltraCrucible<X, Y> {
-    fn step_forward(&self) -> Option<Self> {
+impl PathNode for UltraCrucible {
+    fn step_forward(&self, size: MapSize) -> Option<Self> {
         if self.direction.is_center() || self.n_steps == 10 {
             return None;
         }
-        if let Some(next_city_block) = self.city_block.neighbor(self.direction) {
+        if let Some(next_city_block) = self.city_block.neighbor(self.direction, size) {
             return Some(Self {
                 city_block: next_city_block,
                 direction: self.direction,
@@ -112,11 +106,11 @@ impl<const X: usize, const Y: usize> PathNode<X, Y> for UltraCrucible<X, Y> {
         }
         None
     }
-    fn step_left(&self) -> Option<Self> {
+    fn step_left(&self, size: MapSize) -> Option<Self> {
         if self.direction.is_center() {
             // start of search: left is Compass::E
             return Some(Self {
-                city_block: self.city_block.neighbor(Compass::E).unwrap(),
+                city_block: self.city_block.neighbor(Compass::E, size).unwrap(),
                 direction: Compass::E,
                 n_steps: 1,
             });
@@ -125,7 +119,7 @@ impl<const X: usize, const Y: usize> PathNode<X, Y> for UltraCrucible<X, Y> {
             return None;
         }
         let left = self.direction.counterclockwise().counterclockwise();
-        if let Some(next_city_block) = self.city_block.neighbor(left) {
+        if let Some(next_city_block) = self.city_block.neighbor(left, size) {
             return Some(Self {
                 city_block: next_city_block,
                 direction: left,
@@ -134,11 +128,11 @@ impl<const X: usize, const Y: usize> PathNode<X, Y> for UltraCrucible<X, Y> {
         }
         None
     }
-    fn step_right(&self) -> Option<Self> {
+    fn step_right(&self, size: MapSize) -> Option<Self> {
         if self.direction.is_center() {
             // start of search: right is Compass::S
             return Some(Self {
-                city_block: self.city_block.neighbor(Compass::S).unwrap(),
+                city_block: self.city_block.neighbor(Compass::S, size).unwrap(),
                 direction: Compass::S,
                 n_steps: 1,
             });
@@ -147,7 +141,7 @@ impl<const X: usize, const Y: usize> PathNode<X, Y> for UltraCrucible<X, Y> {
             return None;
         }
         let right = self.direction.clockwise().clockwise();
-        if let Some(next_city_block) = self.city_block.neighbor(right) {
+        if let Some(next_city_block) = self.city_block.neighbor(right, size) {
             return Some(Self {
                 city_block: next_city_block,
                 direction: right,
@@ -156,167 +150,97 @@ impl<const X: usize, const Y: usize> PathNode<X, Y> for UltraCrucible<X, Y> {
         }
         None
     }
-    fn get_city_block(&self) -> MapPoint<X, Y> {
+    fn get_city_block(&self) -> MapPoint {
         self.city_block
     }
 }
 
-#[cfg(feature = "long-run-time")]
-#[derive(Default, Eq, Copy, Clone)]
-struct HeatPathNode<N: PathNode<X, Y>, const X: usize, const Y: usize> {
-    accumulated_heat_loss: u64,
-    path_node: N,
+/// heat loss of each city block
+pub struct CityMap {
+    map: MyMap2D<u64>,
 }
 
-#[cfg(feature = "long-run-time")]
-impl<N: PathNode<X, Y>, const X: usize, const Y: usize> PartialOrd for HeatPathNode<N, X, Y> {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+impl CityMap {
+    fn new(input: &str) -> Result<Self, ParseError> {
+        let map: MyMap2D<u64> = parse::grid_with(input, |c| {
+            c.to_digit(10)
+                .map(|heat_loss| heat_loss as u64)
+                .ok_or_else(|| ParseError::char(c, "digit"))
+        })?;
+        Ok(CityMap { map })
     }
-}
-
-#[cfg(feature = "long-run-time")]
-impl<N: PathNode<X, Y>, const X: usize, const Y: usize> Ord for HeatPathNode<N, X, Y> {
-    fn cmp(&self, other: &Self) -> Ordering {
-        // reverse order to use std::collections::BinaryHeap as Min-heap by switching position of self and other
-        // see https://doc.rust-lang.org/std/collections/struct.BinaryHeap.html#min-heap
-        other.accumulated_heat_loss.cmp(&self.accumulated_heat_loss)
+    fn get_minimum_heat_loss<N: PathNode>(&self) -> Result<u64> {
+        // factory is at bottom-right city block
+        let factory = MapPoint::new(self.map.width() - 1, self.map.height() - 1);
+        let size = self.map.size();
+        let crucible = std::any::type_name::<N>().rsplit("::").next();
+        let _search = debug_span!("minimum heat loss", crucible).entered();
+        // lava pool is at top-left city block, which is default value for PathNode
+        astar(
+            N::default(),
+            |path_node| {
+                [
+                    path_node.step_forward(size),
+                    path_node.step_left(size),
+                    path_node.step_right(size),
+                ]
+                .into_iter()
+                .flatten()
+                .map(|next_node| (next_node, *self.map.get(next_node.get_city_block())))
+            },
+            // each city block on the way to factory looses at least 1 heat
+            |path_node| path_node.get_city_block().distance(factory) as u64,
+            |path_node| path_node.get_city_block() == factory,
+        )
+        .map(|(_, heat_loss)| heat_loss)
+        .ok_or_else(|| anyhow!("factory is not reachable"))
     }
 }
 
-#[cfg(feature = "long-run-time")]
-impl<N: PathNode<X, Y>, const X: usize, const Y: usize> PartialEq for HeatPathNode<N, X, Y> {
-    fn eq(&self, other: &Self) -> bool {
-        self.accumulated_heat_loss == other.accumulated_heat_loss
-    }
-}
+// solution is again inspired by HyperNeutrino
+// see https://www.youtube.com/watch?v=2pDSooPLLkI
 
-#[cfg(feature = "long-run-time")]
-impl<N: PathNode<X, Y>, const X: usize, const Y: usize> HeatPathNode<N, X, Y> {
-    fn step_forward(&self, map: &MyMap2D<u64, X, Y>) -> Option<Self> {
-        if let Some(next_patch_node) = self.path_node.step_forward() {
-            return Some(Self {
-                accumulated_heat_loss: self.accumulated_heat_loss
-                    + *map.get(next_patch_node.get_city_block()),
-                path_node: next_patch_node,
-            });
-        }
-        None
-    }
-    fn step_left(&self, map: &MyMap2D<u64, X, Y>) -> Option<Self> {
-        if let Some(next_patch_node) = self.path_node.step_left() {
-            return Some(Self {
-                accumulated_heat_loss: self.accumulated_heat_loss
-                    + *map.get(next_patch_node.get_city_block()),
-                path_node: next_patch_node,
-            });
-        }
-        None
-    }
-    fn step_right(&self, map: &MyMap2D<u64, X, Y>) -> Option<Self> {
-        if let Some(next_patch_node) = self.path_node.step_right() {
-            return Some(Self {
-                accumulated_heat_loss: self.accumulated_heat_loss
-                    + *map.get(next_patch_node.get_city_block()),
-                path_node: next_patch_node,
-            });
-        }
-        None
-    }
-}
+/// Clumsy Crucible: least heat loss of crucibles (part 1) and of ultra crucibles (part 2)
+pub struct Day17;
 
-#[cfg(feature = "long-run-time")]
-struct CityMap<N: PathNode<X, Y>, const X: usize, const Y: usize> {
-    map: MyMap2D<u64, X, Y>,
-    seen_cache: Vec<N>,
-    bheap: BinaryHeap<HeatPathNode<N, X, Y>>,
-}
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+    const GEN_SIZE: (usize, &'static str) = (141, "width and height of city map");
+    const SLOW_PARTS: &'static [u8] = &[1, 2];
+    type Input<'a> = CityMap;
+    type Answer1 = u64;
+    type Answer2 = u64;
 
-#[cfg(feature = "long-run-time")]
-impl<N: PathNode<X, Y>, const X: usize, const Y: usize> CityMap<N, X, Y> {
-    fn new(input: &str) -> Self {
-        let mut map: MyMap2D<u64, X, Y> = MyMap2D::default();
-        for (y, line) in input.lines().enumerate() {
-            for (x, c) in line.chars().enumerate() {
-                map.set(
-                    (x, y).into(),
-                    c.to_digit(10).expect("bad city block char") as u64,
-                );
-            }
-        }
-        let mut city_map = CityMap {
-            map,
-            seen_cache: Vec::with_capacity(X * Y),
-            bheap: BinaryHeap::with_capacity(X),
-        };
-        // lava pool is at top-left city block, which is default value for HeatPathNode
-        city_map.bheap.push(HeatPathNode::<N, X, Y>::default());
-        city_map
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        Ok(CityMap::new(input)?)
     }
-    fn get_minimum_heat_loss(&mut self) -> Result<u64> {
-        while !self.bheap.is_empty() {
-            // unwrap is safe, since we check for empty bheap
-            let current_node = self.bheap.pop().unwrap();
-            if current_node.path_node.get_city_block() == MapPoint::<X, Y>::new(X - 1, Y - 1) {
-                return Ok(current_node.accumulated_heat_loss);
-            }
-
-            // check if we already run in this path
-            if self.seen_cache.contains(&current_node.path_node) {
-                continue;
-            }
-
-            // add path to seen cache
-            self.seen_cache.push(current_node.path_node);
-
-            // add new heat path nodes to bheap
-            if let Some(forward_node) = current_node.step_forward(&self.map) {
-                self.bheap.push(forward_node);
-            }
-            if let Some(left_node) = current_node.step_left(&self.map) {
-                self.bheap.push(left_node);
-            }
-            if let Some(right_node) = current_node.step_right(&self.map) {
-                self.bheap.push(right_node);
-            }
-        }
-        // bheap should never run empty
-        Err(anyhow!("bheap run empty"))
+    fn part_1(city_map: &Self::Input<'_>) -> Result<Self::Answer1> {
+        city_map.get_minimum_heat_loss::<NormalCrucible>()
     }
-}
-
-// solution is again inspired by HyperNeutrino
-// see https://www.youtube.com/watch?v=2pDSooPLLkI
-
-pub fn day_17() -> Result<()> {
-    #[cfg(feature = "long-run-time")]
-    {
-        let input = include_str!("../../assets/day_17.txt");
-        let mut city_map = CityMap::<NormalCrucible<X, Y>, X, Y>::new(input);
-        let result_part1 = city_map.get_minimum_heat_loss()?;
-        println!("result day 17 part 1: {}", result_part1);
-        assert_eq!(result_part1, 1099);
-        let mut city_map = CityMap::<UltraCrucible<X, Y>, X, Y>::new(input);
-        let result_part2 = city_map.get_minimum_heat_loss().unwrap();
-        println!("result day 17 part 2: {}", result_part2);
-        assert_eq!(result_part2, 1266);
+    fn part_2(city_map: &Self::Input<'_>) -> Result<Self::Answer2> {
+        city_map.get_minimum_heat_loss::<UltraCrucible>()
     }
-    #[cfg(feature = "short-run-time")]
-    {
-        println!("day 17 skipped because of long run time")
+    fn generate(rng: &mut InputRng, size: usize) -> Result<String> {
+        if size < 2 {
+            return Err(anyhow!("city map of at least 2 x 2 blocks"));
+        }
+        Ok(generate::grid(rng, MapSize::new(size, size), |rng, _| {
+            rng.gen_range('1'..='9')
+        }))
+    }
+    // heat loss of city blocks from white (1) to red (9)
+    fn render(city_map: &Self::Input<'_>) -> Result<Option<Image>> {
+        Ok(Some(Image::from_map(&city_map.map, |_, heat_loss| {
+            Rgb::WHITE.blend(Rgb::RED, heat_loss.saturating_sub(1) as f64 / 8.0)
+        })))
     }
-
-    Ok(())
 }
 
-#[cfg(all(test, feature = "long-run-time"))]
+#[cfg(test)]
 mod tests {
 
     use super::*;
 
-    const XT: usize = 13;
-    const YT: usize = 13;
-
     #[test]
     fn test_part1_example() {
         let input = "2413432311323\n\
@@ -332,14 +256,13 @@ mod tests {
                            1224686865563\n\
                            2546548887735\n\
                            4322674655533";
-        let mut city_map = CityMap::<NormalCrucible<XT, YT>, XT, YT>::new(input);
-        println!("{}", city_map.map);
-        let result_part1 = city_map.get_minimum_heat_loss().unwrap();
-        println!("result day 17 example part 1: {}", result_part1);
+        let city_map = Day17::parse(input).unwrap();
+        let result_part1 = city_map.get_minimum_heat_loss::<NormalCrucible>().unwrap();
         assert_eq!(result_part1, 102);
-        let mut city_map = CityMap::<UltraCrucible<XT, YT>, XT, YT>::new(input);
-        let result_part2 = city_map.get_minimum_heat_loss().unwrap();
-        println!("result day 17 example part 2: {}", result_part2);
+        let result_part2 = city_map.get_minimum_heat_loss::<UltraCrucible>().unwrap();
         assert_eq!(result_part2, 94);
+        let image = Day17::render(&city_map).unwrap().unwrap();
+        assert_eq!(image.get(MapPoint::new(0, 5)), Rgb::WHITE);
+        assert_eq!(image.get(MapPoint::new(5, 5)), Rgb::RED);
     }
 }
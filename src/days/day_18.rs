@@ -1,13 +1,20 @@
 //!day_18.rs
 
+use crate::generate::{self, InputRng};
+use crate::my_lib::my_map_point::{MapPoint, MapSize};
+use crate::parse::{self, ParseError};
+use crate::solution::Solution;
 use anyhow::{anyhow, Result};
+use rand::Rng;
+use std::cmp::Ordering;
 
 // https://www.youtube.com/watch?v=bGWK76_e-LM
 // https://de.wikipedia.org/wiki/Gau%C3%9Fsche_Trapezformel
 // https://de.wikipedia.org/wiki/Satz_von_Pick
 
+/// corners of trench of both dig plans
 #[derive(Default)]
-struct LavaLagoon {
+pub struct LavaLagoon {
     points_task_1: Vec<(i64, i64)>,
     boundary_points_task1: i64,
     points_task_2: Vec<(i64, i64)>,
@@ -15,26 +22,22 @@ struct LavaLagoon {
 }
 
 impl LavaLagoon {
-    fn from_input(input: &str) -> Result<Self> {
+    fn from_input(input: &str) -> Result<Self, ParseError> {
         let mut lava_lagoon = Self::default();
         let mut cp_task1 = (0, 0);
         let mut cp_task2 = (0, 0);
         for line in input.lines() {
             let mut split_line = line.split_whitespace();
-            let dir_task1: (i64, i64) = match split_line.next() {
-                Some(d) => match d {
-                    "R" => (1, 0),
-                    "L" => (-1, 0),
-                    "U" => (0, -1),
-                    "D" => (0, 1),
-                    _ => return Err(anyhow!("bad direction task 1 input")),
-                },
-                None => return Err(anyhow!("missing direction task 1 input")),
-            };
-            let ns_taks1 = match split_line.next() {
-                Some(ns) => ns.parse::<i64>()?,
-                None => return Err(anyhow!("missing number of steps task 1 input")),
+            let dir_task1: (i64, i64) = match parse::next_token(&mut split_line, line, "direction")?
+            {
+                "R" => (1, 0),
+                "L" => (-1, 0),
+                "U" => (0, -1),
+                "D" => (0, 1),
+                d => return Err(ParseError::new(d, "`R`, `L`, `U` or `D`")),
             };
+            let ns_taks1 =
+                parse::number::<i64>(parse::next_token(&mut split_line, line, "number of steps")?)?;
             cp_task1 = (
                 cp_task1.0 + ns_taks1 * dir_task1.0,
                 cp_task1.1 + ns_taks1 * dir_task1.1,
@@ -42,19 +45,26 @@ impl LavaLagoon {
             // boundary points is sum of steps
             lava_lagoon.boundary_points_task1 += ns_taks1;
             lava_lagoon.points_task_1.push(cp_task1);
-            let (ns_taks2, dir_task2) = match split_line.next() {
-                Some(rgb) => {
-                    let ns_taks2 = &rgb[2..7];
-                    let dir_task2 = match &rgb[7..8] {
+            let rgb = parse::next_token(&mut split_line, line, "color")?;
+            let (ns_taks2, dir_task2) = match rgb
+                .strip_prefix("(#")
+                .and_then(|c| c.strip_suffix(')'))
+                .filter(|c| c.len() == 6 && c.is_ascii())
+            {
+                Some(color) => {
+                    let ns_taks2 = &color[..5];
+                    let dir_task2 = match &color[5..] {
                         "0" => (1, 0),  // R
                         "2" => (-1, 0), // L
                         "3" => (0, -1), // U
                         "1" => (0, 1),  // D
-                        _ => return Err(anyhow!("bad direction task 2 input")),
+                        d => return Err(ParseError::new(d, "direction `0` to `3`")),
                     };
-                    (i64::from_str_radix(ns_taks2, 16)?, dir_task2)
+                    let ns_taks2 = i64::from_str_radix(ns_taks2, 16)
+                        .map_err(|_| ParseError::new(ns_taks2, "hexadecimal number"))?;
+                    (ns_taks2, dir_task2)
                 }
-                None => return Err(anyhow!("missing rgb input")),
+                None => return Err(ParseError::new(rgb, "color `(#rrggbb)`")),
             };
             cp_task2 = (
                 cp_task2.0 + ns_taks2 * dir_task2.0,
@@ -64,8 +74,12 @@ impl LavaLagoon {
             lava_lagoon.boundary_points_task2 += ns_taks2;
             lava_lagoon.points_task_2.push(cp_task2);
         }
-        assert_eq!(cp_task1, (0, 0));
-        assert_eq!(cp_task2, (0, 0));
+        if cp_task1 != (0, 0) || cp_task2 != (0, 0) {
+            return Err(ParseError::new(
+                &input[input.len()..],
+                "dig plan, which returns to start",
+            ));
+        }
         Ok(lava_lagoon)
     }
     fn calc_cubics(&self, task2: bool) -> i64 {
@@ -102,27 +116,176 @@ impl LavaLagoon {
     }
 }
 
-pub fn day_18() -> Result<()> {
-    let input = include_str!("../../assets/day_18.txt");
-    let lava_lagoon = LavaLagoon::from_input(input)?;
-    let cubics_task1 = lava_lagoon.calc_cubics(false);
-    println!("result day 18 part 1: {}", cubics_task1);
-    assert_eq!(cubics_task1, 42_317);
-    let cubics_task2 = lava_lagoon.calc_cubics(true);
-    println!("result day 18 part 2: {}", cubics_task2);
-    assert_eq!(cubics_task2, 83_605_563_360_288);
-    Ok(())
+/// Lavaduct Lagoon: cubic meters of lava of dig plan (part 1) and of dig plan in colors (part 2)
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u8 = 18;
+    const GEN_SIZE: (usize, &'static str) = (40, "width and height of lattice of dig plan");
+    type Input<'a> = LavaLagoon;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        Ok(LavaLagoon::from_input(input)?)
+    }
+    fn part_1(lava_lagoon: &Self::Input<'_>) -> Result<Self::Answer1> {
+        Ok(lava_lagoon.calc_cubics(false))
+    }
+    fn part_2(lava_lagoon: &Self::Input<'_>) -> Result<Self::Answer2> {
+        Ok(lava_lagoon.calc_cubics(true))
+    }
+    fn generate(rng: &mut InputRng, size: usize) -> Result<String> {
+        if size == 0 {
+            return Err(anyhow!("lattice of at least 1 x 1 squares"));
+        }
+        // dig along boundary of a region of squares; both parts dig the same shape, but squares
+        // of part 2 are much larger. Lengths of part 2 must fit into 5 hex digits. Squares are at
+        // least 2 cubes wide, therefore trench never touches itself.
+        let region = generate::simple_region(rng, MapSize::new(size, size), size * size / 2);
+        let corners = generate::region_boundary(&region);
+        let mut lengths =
+            |max: u64| -> Vec<u64> { (0..size).map(|_| rng.gen_range(2..=max)).collect() };
+        let max_length_2 = 0xF_FFFF / size as u64;
+        let (columns_1, rows_1) = (lengths(10), lengths(10));
+        let (columns_2, rows_2) = (lengths(max_length_2), lengths(max_length_2));
+        let direction =
+            |from: MapPoint, to: MapPoint| match (to.x().cmp(&from.x()), to.y().cmp(&from.y())) {
+                (Ordering::Greater, _) => ('R', 0),
+                (_, Ordering::Greater) => ('D', 1),
+                (Ordering::Less, _) => ('L', 2),
+                _ => ('U', 3),
+            };
+        let span = |lengths: &[u64], from: usize, to: usize| -> u64 {
+            lengths[from.min(to)..from.max(to)].iter().sum()
+        };
+        let corner = |index: usize| corners[index % corners.len()];
+        let mut input = String::new();
+        // first corner is top left corner of region, where boundary turns
+        let mut start = 0;
+        while start < corners.len() {
+            let (dir, hex_dir) = direction(corner(start), corner(start + 1));
+            let mut end = start + 1;
+            while direction(corner(end), corner(end + 1)).0 == dir {
+                end += 1;
+            }
+            let (from, to) = (corner(start), corner(end));
+            let (length_1, length_2) = match dir {
+                'R' | 'L' => (
+                    span(&columns_1, from.x(), to.x()),
+                    span(&columns_2, from.x(), to.x()),
+                ),
+                _ => (
+                    span(&rows_1, from.y(), to.y()),
+                    span(&rows_2, from.y(), to.y()),
+                ),
+            };
+            input.push_str(&format!(
+                "{} {} (#{:05x}{})\n",
+                dir, length_1, length_2, hex_dir
+            ));
+            start = end;
+        }
+        Ok(input)
+    }
+}
+
+#[cfg(test)]
+mod reference {
+
+    use super::*;
+    use std::collections::{HashSet, VecDeque};
+
+    /// digs trench cube by cube and fills everything, which cannot be reached from outside of it
+    pub fn part_1(input: &str) -> Result<i64> {
+        let mut trench: HashSet<(i64, i64)> = HashSet::from([(0, 0)]);
+        let mut current = (0, 0);
+        for line in input.lines() {
+            let mut split_line = line.split_whitespace();
+            let direction = match split_line.next() {
+                Some("R") => (1, 0),
+                Some("L") => (-1, 0),
+                Some("U") => (0, -1),
+                Some("D") => (0, 1),
+                _ => return Err(anyhow!("invalid direction in `{}`", line)),
+            };
+            let steps: i64 = split_line
+                .next()
+                .ok_or_else(|| anyhow!("missing steps in `{}`", line))?
+                .parse()?;
+            for _ in 0..steps {
+                current = (current.0 + direction.0, current.1 + direction.1);
+                trench.insert(current);
+            }
+        }
+        // outside is everything in a frame around trench, which is reached from a corner of frame
+        let min_x = trench.iter().map(|c| c.0).min().unwrap() - 1;
+        let max_x = trench.iter().map(|c| c.0).max().unwrap() + 1;
+        let min_y = trench.iter().map(|c| c.1).min().unwrap() - 1;
+        let max_y = trench.iter().map(|c| c.1).max().unwrap() + 1;
+        let mut outside: HashSet<(i64, i64)> = HashSet::from([(min_x, min_y)]);
+        let mut queue = VecDeque::from([(min_x, min_y)]);
+        while let Some((x, y)) = queue.pop_front() {
+            for next in [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)] {
+                if (min_x..=max_x).contains(&next.0)
+                    && (min_y..=max_y).contains(&next.1)
+                    && !trench.contains(&next)
+                    && outside.insert(next)
+                {
+                    queue.push_back(next);
+                }
+            }
+        }
+        Ok((max_x - min_x + 1) * (max_y - min_y + 1) - outside.len() as i64)
+    }
 }
 
 #[cfg(test)]
 mod tests {
 
-    //use super::*;
+    use super::*;
+    use crate::differential;
 
     #[test]
     fn test_hexa_convert() {
         let hex1 = "70c71";
         let hex1_num = u32::from_str_radix(hex1, 16).unwrap();
-        eprintln!("x{}: d{}", hex1, hex1_num);
+        assert_eq!(hex1_num, 461_937);
+    }
+
+    #[test]
+    fn test_example() -> Result<()> {
+        let input = "R 6 (#70c710)\n\
+                           D 5 (#0dc571)\n\
+                           L 2 (#5713f0)\n\
+                           D 2 (#d2c081)\n\
+                           R 2 (#59c680)\n\
+                           D 2 (#411b91)\n\
+                           L 5 (#8ceee2)\n\
+                           U 2 (#caa173)\n\
+                           L 1 (#1b58a2)\n\
+                           U 2 (#caa171)\n\
+                           R 2 (#7807d2)\n\
+                           U 3 (#a77fa3)\n\
+                           L 2 (#015232)\n\
+                           U 2 (#7a21e3)";
+        let lava_lagoon = Day18::parse(input)?;
+        assert_eq!(Day18::part_1(&lava_lagoon)?, 62);
+        assert_eq!(reference::part_1(input)?, 62);
+        assert_eq!(Day18::part_2(&lava_lagoon)?, 952_408_144_115);
+        Ok(())
+    }
+
+    #[test]
+    fn test_differential_part_1() -> Result<()> {
+        if let Some(divergence) = differential::first_divergence(
+            0..20,
+            |rng| Day18::generate(rng, 8),
+            |input| Day18::part_1(&Day18::parse(input)?),
+            reference::part_1,
+        )? {
+            panic!("day 18 part 1: {}", divergence);
+        }
+        Ok(())
     }
 }
@@ -1,9 +1,14 @@
 //!day_19.rs
 
+use crate::generate::{self, InputRng};
+use crate::my_lib::my_interval::Interval;
+use crate::parse::{self, ParseError};
+use crate::solution::Solution;
 use anyhow::{anyhow, Result};
-use std::collections::HashMap;
+use rand::{seq::SliceRandom, Rng};
+use std::collections::{HashMap, VecDeque};
 
-type RuleSet = HashMap<String, Vec<Rule>>;
+pub type RuleSet = HashMap<String, Vec<Rule>>;
 
 enum RuleResult {
     Link(String),
@@ -31,7 +36,7 @@ enum RuleType {
 }
 
 #[derive(Default)]
-struct Rule {
+pub struct Rule {
     rt: RuleType,
     mpc: RuleMachinePartCartegory,
     comparator_value: u64,
@@ -55,22 +60,22 @@ impl Rule {
             ..Default::default()
         }
     }
-    fn from_str(rule_str: &str) -> Result<Self> {
+    fn from_str(rule_str: &str) -> Result<Self, ParseError> {
         if rule_str.contains(['<', '>']) {
-            let mpc = match &rule_str[0..1] {
-                "x" => RuleMachinePartCartegory::ExtremlyCoolLooking,
-                "m" => RuleMachinePartCartegory::Musical,
-                "a" => RuleMachinePartCartegory::Aerodynamic,
-                "s" => RuleMachinePartCartegory::Shiny,
-                _ => return Err(anyhow!("bad input rule machine part cartegory")),
+            let (condition, target_rule) = parse::split_once(rule_str, ":")?;
+            let mpc = match condition.get(0..1) {
+                Some("x") => RuleMachinePartCartegory::ExtremlyCoolLooking,
+                Some("m") => RuleMachinePartCartegory::Musical,
+                Some("a") => RuleMachinePartCartegory::Aerodynamic,
+                Some("s") => RuleMachinePartCartegory::Shiny,
+                _ => return Err(ParseError::new(condition, "`x`, `m`, `a` or `s`")),
             };
-            let rt = match &rule_str[1..2] {
-                ">" => RuleType::Greater,
-                "<" => RuleType::Less,
-                _ => return Err(anyhow!("bad input rule type")),
+            let rt = match condition.get(1..2) {
+                Some(">") => RuleType::Greater,
+                Some("<") => RuleType::Less,
+                _ => return Err(ParseError::new(&condition[1..], "`>` or `<`")),
             };
-            let (comparator_value, target_rule) = &rule_str[2..].split_once(':').unwrap();
-            let comparator_value = comparator_value.parse::<u64>()?;
+            let comparator_value = parse::number::<u64>(&condition[2..])?;
             let target_rule = target_rule.to_string();
             Ok(Self {
                 rt,
@@ -114,116 +119,80 @@ impl Rule {
         }
     }
     fn check_range(&self, machine_part_range: &MachinePartRange) -> RuleRangeResult {
-        let (min_comparator, max_comparator) = match self.mpc {
-            RuleMachinePartCartegory::ExtremlyCoolLooking => {
-                (machine_part_range.min.x, machine_part_range.max.x)
-            }
-            RuleMachinePartCartegory::Musical => {
-                (machine_part_range.min.m, machine_part_range.max.m)
-            }
-            RuleMachinePartCartegory::Aerodynamic => {
-                (machine_part_range.min.a, machine_part_range.max.a)
-            }
-            RuleMachinePartCartegory::Shiny => (machine_part_range.min.s, machine_part_range.max.s),
-        };
-        match self.rt {
-            RuleType::Accepted => RuleRangeResult::Value(machine_part_range.combinations()),
-            RuleType::Rejected => RuleRangeResult::Value(0),
-            RuleType::Link => RuleRangeResult::Link(self.target_rule.clone()),
+        let (accepted_range, rejected_range) = match self.rt {
+            RuleType::Accepted => return RuleRangeResult::Value(machine_part_range.combinations()),
+            RuleType::Rejected => return RuleRangeResult::Value(0),
+            RuleType::Link => return RuleRangeResult::Link(self.target_rule.clone()),
             RuleType::Greater => {
-                if min_comparator > self.comparator_value {
-                    RuleRangeResult::Link(self.target_rule.clone())
-                } else if max_comparator <= self.comparator_value {
-                    RuleRangeResult::Next
-                } else {
-                    // split machine_part_range
-                    let (accepted_machine_part_range, rejected_machine_part_range) =
-                        machine_part_range
-                            .split_at_greater_comparator_value(&self.mpc, self.comparator_value);
-                    RuleRangeResult::Split(
-                        accepted_machine_part_range,
-                        self.target_rule.clone(),
-                        rejected_machine_part_range,
-                    )
-                }
-            }
-            RuleType::Less => {
-                if max_comparator < self.comparator_value {
-                    RuleRangeResult::Link(self.target_rule.clone())
-                } else if min_comparator >= self.comparator_value {
-                    RuleRangeResult::Next
-                } else {
-                    // split machine_part_range
-                    let (accepted_machine_part_range, rejected_machine_part_range) =
-                        machine_part_range
-                            .split_at_less_comparator_value(&self.mpc, self.comparator_value);
-                    RuleRangeResult::Split(
-                        accepted_machine_part_range,
-                        self.target_rule.clone(),
-                        rejected_machine_part_range,
-                    )
-                }
+                let (below, above) =
+                    machine_part_range.split_at(&self.mpc, self.comparator_value + 1);
+                (above, below)
             }
+            RuleType::Less => machine_part_range.split_at(&self.mpc, self.comparator_value),
+        };
+        if accepted_range.is_empty() {
+            RuleRangeResult::Next
+        } else if rejected_range.is_empty() {
+            RuleRangeResult::Link(self.target_rule.clone())
+        } else {
+            RuleRangeResult::Split(accepted_range, self.target_rule.clone(), rejected_range)
         }
     }
 }
 
-fn rule_set_from_str(rules: &str) -> Result<RuleSet> {
+/// workflows of rule lines; `A` and `R` accept and reject machine parts
+pub fn rule_set_from_str(rules: &str) -> Result<RuleSet, ParseError> {
     let mut rule_set = RuleSet::new();
     rule_set.insert(String::from("A"), vec![Rule::new_accepted()]);
     rule_set.insert(String::from("R"), vec![Rule::new_rejected()]);
     for rule_line in rules.lines() {
-        let (rule_label, rule_list) = rule_line
-            .split_once('{')
-            .map(|(label, list)| {
-                (
-                    label.to_string(),
-                    list.strip_suffix('}')
-                        .unwrap()
-                        .split(',')
-                        .collect::<Vec<&str>>(),
-                )
-            })
-            .unwrap();
+        let (rule_label, rule_list) = parse::split_once(rule_line, "{")?;
+        let rule_list = rule_list
+            .strip_suffix('}')
+            .ok_or_else(|| ParseError::new(&rule_list[rule_list.len()..], "`}`"))?;
         let mut rule_collection: Vec<Rule> = Vec::new();
-        for rule in rule_list.iter() {
+        for rule in rule_list.split(',') {
             rule_collection.push(Rule::from_str(rule)?);
         }
-        rule_set.insert(rule_label, rule_collection);
+        rule_set.insert(rule_label.to_string(), rule_collection);
     }
     Ok(rule_set)
 }
 
+/// ratings of a machine part
 #[derive(Debug, Clone, Copy)]
-struct MachinePart {
+pub struct MachinePart {
     x: u64,
     m: u64,
     a: u64,
     s: u64,
 }
 
-impl From<&str> for MachinePart {
-    fn from(value: &str) -> Self {
-        let values: Vec<u64> = value
+impl TryFrom<&str> for MachinePart {
+    type Error = ParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let ratings = value
             .strip_prefix('{')
-            .unwrap()
-            .strip_suffix('}')
-            .unwrap()
-            .split(',')
-            .map(|s| {
-                s.split_once('=')
-                    .unwrap()
-                    .1
-                    .parse::<u64>()
-                    .expect("bad machine part input")
-            })
-            .collect();
-        Self {
-            x: values[0],
-            m: values[1],
-            a: values[2],
-            s: values[3],
-        }
+            .and_then(|v| v.strip_suffix('}'))
+            .ok_or_else(|| ParseError::new(value, "machine part `{x=..,m=..,a=..,s=..}`"))?;
+        let mut ratings_iter = ratings.split(',');
+        let mut rating = |category: &str| -> Result<u64, ParseError> {
+            let rating = parse::next_token(&mut ratings_iter, ratings, category)?;
+            let (c, rating) = parse::split_once(rating, "=")?;
+            if c != category {
+                return Err(ParseError::new(c, format!("`{}`", category)));
+            }
+            parse::number::<u64>(rating)
+        };
+        let machine_part = Self {
+            x: rating("x")?,
+            m: rating("m")?,
+            a: rating("a")?,
+            s: rating("s")?,
+        };
+        parse::no_more_tokens(&mut ratings_iter, "`}`")?;
+        Ok(machine_part)
     }
 }
 
@@ -231,7 +200,8 @@ impl MachinePart {
     fn sum(&self) -> u64 {
         self.x + self.m + self.a + self.s
     }
-    fn calc_machine_part_rating(&self, rule_set: &RuleSet) -> Result<u64> {
+    /// sum of ratings, if workflows starting at `in` accept machine part, else 0
+    pub fn calc_machine_part_rating(&self, rule_set: &RuleSet) -> Result<u64> {
         self.calc_machine_part_rating_recursive(String::from("in"), rule_set)
     }
     fn calc_machine_part_rating_recursive(
@@ -268,91 +238,43 @@ enum RuleRangeResult {
     Value(u64),
 }
 
+/// number of combinations of ratings between 1 and 4000, which rule set accepts
+pub fn accepted_combinations(rule_set: &RuleSet) -> Result<u64> {
+    MachinePartRange::new().calc_machine_part_combinations(rule_set)
+}
+
+// ratings of x, m, a and s
 #[derive(Clone, Copy)]
 struct MachinePartRange {
-    min: MachinePart,
-    max: MachinePart,
+    ratings: [Interval<u64>; 4],
 }
 
 impl MachinePartRange {
     fn new() -> Self {
         Self {
-            min: MachinePart {
-                x: 1,
-                m: 1,
-                a: 1,
-                s: 1,
-            },
-            max: MachinePart {
-                x: 4000,
-                m: 4000,
-                a: 4000,
-                s: 4000,
-            },
+            ratings: [Interval::inclusive(1, 4000); 4],
         }
     }
+    fn is_empty(&self) -> bool {
+        self.ratings.iter().any(|r| r.is_empty())
+    }
     fn combinations(&self) -> u64 {
-        (self.max.x - self.min.x + 1)
-            * (self.max.m - self.min.m + 1)
-            * (self.max.a - self.min.a + 1)
-            * (self.max.s - self.min.s + 1)
+        self.ratings.iter().map(|r| r.len()).product()
     }
     fn calc_machine_part_combinations(&mut self, rule_set: &RuleSet) -> Result<u64> {
         self.calc_machine_part_combinations_recursive(String::from("in"), rule_set)
     }
-    fn split_at_greater_comparator_value(
-        &self,
-        mpc: &RuleMachinePartCartegory,
-        comparator_value: u64,
-    ) -> (Self, Self) {
-        let mut accepted_range = *self;
-        let mut rejected_range = *self;
-        match mpc {
-            RuleMachinePartCartegory::ExtremlyCoolLooking => {
-                accepted_range.min.x = comparator_value + 1;
-                rejected_range.max.x = comparator_value;
-            }
-            RuleMachinePartCartegory::Musical => {
-                accepted_range.min.m = comparator_value + 1;
-                rejected_range.max.m = comparator_value;
-            }
-            RuleMachinePartCartegory::Aerodynamic => {
-                accepted_range.min.a = comparator_value + 1;
-                rejected_range.max.a = comparator_value;
-            }
-            RuleMachinePartCartegory::Shiny => {
-                accepted_range.min.s = comparator_value + 1;
-                rejected_range.max.s = comparator_value;
-            }
-        }
-        (accepted_range, rejected_range)
-    }
-    fn split_at_less_comparator_value(
-        &self,
-        mpc: &RuleMachinePartCartegory,
-        comparator_value: u64,
-    ) -> (Self, Self) {
-        let mut accepted_range = *self;
-        let mut rejected_range = *self;
-        match mpc {
-            RuleMachinePartCartegory::ExtremlyCoolLooking => {
-                accepted_range.max.x = comparator_value - 1;
-                rejected_range.min.x = comparator_value;
-            }
-            RuleMachinePartCartegory::Musical => {
-                accepted_range.max.m = comparator_value - 1;
-                rejected_range.min.m = comparator_value;
-            }
-            RuleMachinePartCartegory::Aerodynamic => {
-                accepted_range.max.a = comparator_value - 1;
-                rejected_range.min.a = comparator_value;
-            }
-            RuleMachinePartCartegory::Shiny => {
-                accepted_range.max.s = comparator_value - 1;
-                rejected_range.min.s = comparator_value;
-            }
-        }
-        (accepted_range, rejected_range)
+    // ranges with ratings of category below point and at or above point
+    fn split_at(&self, mpc: &RuleMachinePartCartegory, point: u64) -> (Self, Self) {
+        let index = match mpc {
+            RuleMachinePartCartegory::ExtremlyCoolLooking => 0,
+            RuleMachinePartCartegory::Musical => 1,
+            RuleMachinePartCartegory::Aerodynamic => 2,
+            RuleMachinePartCartegory::Shiny => 3,
+        };
+        let (mut below, mut above) = (*self, *self);
+        (below.ratings[index], above.ratings[index]) = self.ratings[index].split_at(point);
+        (below, above)
     }
     fn calc_machine_part_combinations_recursive(
         mut self,
@@ -384,23 +306,93 @@ impl MachinePartRange {
     }
 }
 
-pub fn day_19() -> Result<()> {
-    let input = include_str!("../../assets/day_19.txt");
-    let (rules, machine_parts) = input.split_once("\n\n").unwrap();
-    let rule_set = rule_set_from_str(rules)?;
-    let machine_parts: Vec<MachinePart> = machine_parts.lines().map(MachinePart::from).collect();
-    let mut result_part1 = 0;
-    for machine_part in machine_parts.iter() {
-        result_part1 += machine_part.calc_machine_part_rating(&rule_set)?;
-    }
-    println!("result day 19 part 1: {}", result_part1);
-    assert_eq!(result_part1, 383_682);
+/// Aplenty: sum of ratings of accepted parts (part 1) and number of accepted combinations of
+/// ratings (part 2)
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: u8 = 19;
+    const GEN_SIZE: (usize, &'static str) = (500, "number of workflows and of parts");
+    /// rule set and machine parts
+    type Input<'a> = (RuleSet, Vec<MachinePart>);
+    type Answer1 = u64;
+    type Answer2 = u64;
 
-    let mut machine_part_range = MachinePartRange::new();
-    let result_part2 = machine_part_range.calc_machine_part_combinations(&rule_set)?;
-    eprintln!("result day 19 part 2: {}", result_part2);
-    assert_eq!(result_part2, 117_954_800_808_317);
-    Ok(())
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        let (rules, machine_parts) = parse::split_once(input, "\n\n")?;
+        let rule_set = rule_set_from_str(rules)?;
+        let machine_parts: Vec<MachinePart> = machine_parts
+            .lines()
+            .map(MachinePart::try_from)
+            .collect::<Result<_, _>>()?;
+        Ok((rule_set, machine_parts))
+    }
+    fn part_1((rule_set, machine_parts): &Self::Input<'_>) -> Result<Self::Answer1> {
+        let mut result_part1 = 0;
+        for machine_part in machine_parts.iter() {
+            result_part1 += machine_part.calc_machine_part_rating(rule_set)?;
+        }
+        Ok(result_part1)
+    }
+    fn part_2((rule_set, _): &Self::Input<'_>) -> Result<Self::Answer2> {
+        accepted_combinations(rule_set)
+    }
+    fn generate(rng: &mut InputRng, size: usize) -> Result<String> {
+        if size == 0 {
+            return Err(anyhow!("at least workflow `in`"));
+        }
+        let mut names = generate::names(rng, size - 1, 3, b"abcdefghijklmnopqrstuvwxyz", |n| {
+            n != "in"
+        })?
+        .into_iter();
+        // workflows form a tree with root `in` and leaves `A` or `R`
+        let mut pending = VecDeque::from([String::from("in")]);
+        let mut workflows = Vec::with_capacity(size);
+        let mut num_workflows = 1;
+        while let Some(name) = pending.pop_front() {
+            let mut targets: Vec<String> = Vec::new();
+            for index in 0..=rng.gen_range(1..=3) {
+                // keep tree growing until all workflows are created
+                let grow = (pending.is_empty() && index == 0) || rng.gen_bool(0.5);
+                let target = match names.next() {
+                    Some(child) if grow && num_workflows < size => {
+                        num_workflows += 1;
+                        pending.push_back(child.clone());
+                        child
+                    }
+                    _ if rng.gen_bool(0.5) => String::from("A"),
+                    _ => String::from("R"),
+                };
+                targets.push(target);
+            }
+            let otherwise = targets.pop().unwrap();
+            let rules: Vec<String> = targets
+                .iter()
+                .map(|target| {
+                    format!(
+                        "{}{}{}:{}",
+                        ['x', 'm', 'a', 's'][rng.gen_range(0..4)],
+                        if rng.gen_bool(0.5) { '<' } else { '>' },
+                        rng.gen_range(1..4_000),
+                        target
+                    )
+                })
+                .collect();
+            workflows.push(format!("{}{{{},{}}}", name, rules.join(","), otherwise));
+        }
+        workflows.shuffle(rng);
+        let machine_parts: Vec<String> = (0..size)
+            .map(|_| {
+                let [x, m, a, s] = [0; 4].map(|_| rng.gen_range(1..=4_000));
+                format!("{{x={},m={},a={},s={}}}", x, m, a, s)
+            })
+            .collect();
+        Ok(format!(
+            "{}\n\n{}\n",
+            workflows.join("\n"),
+            machine_parts.join("\n")
+        ))
+    }
 }
 
 #[cfg(test)]
@@ -428,18 +420,18 @@ mod tests {
                            {x=2127,m=1623,a=2188,s=1013}";
         let (rules, machine_parts) = input.split_once("\n\n").unwrap();
         let rule_set = rule_set_from_str(rules)?;
-        let machine_parts: Vec<MachinePart> =
-            machine_parts.lines().map(MachinePart::from).collect();
+        let machine_parts: Vec<MachinePart> = machine_parts
+            .lines()
+            .map(MachinePart::try_from)
+            .collect::<Result<_, _>>()?;
         let mut result_part1 = 0;
         for machine_part in machine_parts.iter() {
             result_part1 += machine_part.calc_machine_part_rating(&rule_set)?;
         }
-        eprintln!("result day 19 example part 1: {}", result_part1);
         assert_eq!(result_part1, 19_114);
 
         let mut machine_part_range = MachinePartRange::new();
         let result_part2 = machine_part_range.calc_machine_part_combinations(&rule_set)?;
-        eprintln!("result day 19 example part 2: {}", result_part2);
         assert_eq!(result_part2, 167_409_079_868_000);
 
         Ok(())
@@ -1,10 +1,16 @@
 //!day_20.rs
 
+use crate::generate::{self, InputRng};
+use crate::my_lib::{my_cycle::find_cycle, my_search::bfs};
+use crate::parse::{self, ParseError};
+use crate::solution::Solution;
+use crate::step::Simulation;
 use anyhow::{anyhow, Result};
 use num::integer::lcm;
-use std::collections::{HashMap, VecDeque};
+use rand::{seq::SliceRandom, Rng};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 struct FlipFlop {
     state: bool,
 }
@@ -19,9 +25,9 @@ impl FlipFlop {
     }
 }
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 struct Conjunction {
-    memory: HashMap<String, bool>,
+    memory: BTreeMap<String, bool>,
 }
 
 impl Conjunction {
@@ -35,7 +41,7 @@ impl Conjunction {
     }
 }
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 struct Message {
     sender: String,
     reciever: String,
@@ -59,70 +65,76 @@ impl Message {
     }
 }
 
-#[derive(Default)]
-struct Server {
+/// network of modules, which send pulses
+#[derive(Default, Clone)]
+pub struct Server {
     queue: VecDeque<Message>,
     low_pulse_count: u64,
     high_pulse_count: u64,
     recievers: HashMap<String, Vec<String>>,
-    flip_flops: HashMap<String, FlipFlop>,
-    conjunctions: HashMap<String, Conjunction>,
-    rx_input_cycles: HashMap<String, Option<u64>>,
+    flip_flops: BTreeMap<String, FlipFlop>,
+    conjunctions: BTreeMap<String, Conjunction>,
+    // conjunctions, which feed feeder of rx
+    rx_inputs: Vec<String>,
 }
 
 impl Server {
     fn from_str(input: &str) -> Result<Self> {
         let mut server = Server::default();
         for line in input.lines() {
-            let (channel, rec_list) = line
-                .split_once("->")
-                .map(|(c, r)| {
-                    let rec: Vec<String> =
-                        r.trim().split(',').map(|r| r.trim().to_string()).collect();
-                    (c.trim(), rec)
-                })
-                .unwrap();
-            match &channel[0..1] {
-                "b" => {
+            let (channel, rec_list) = parse::split_once(line, "->")?;
+            let channel = channel.trim();
+            let rec_list: Vec<String> = rec_list
+                .trim()
+                .split(',')
+                .map(|r| r.trim().to_string())
+                .collect();
+            let duplicate = |label: &str| ParseError::new(label, "unique channel name");
+            match channel.get(0..1) {
+                Some("b") => {
                     if channel == "broadcaster" {
                         if server
                             .recievers
                             .insert(channel.to_string(), rec_list)
                             .is_some()
                         {
-                            return Err(anyhow!("duplicate channel name {} in input", channel));
+                            return Err(duplicate(channel).into());
                         }
                     } else {
-                        return Err(anyhow!("bad broadcaster input"));
+                        return Err(ParseError::new(channel, "`broadcaster`").into());
                     }
                 }
-                "%" => {
+                Some("%") => {
                     let label = &channel[1..];
                     if server
                         .recievers
                         .insert(label.to_string(), rec_list)
                         .is_some()
                     {
-                        return Err(anyhow!("duplicate channel name {} in input", label));
+                        return Err(duplicate(label).into());
                     }
                     server
                         .flip_flops
                         .insert(label.to_string(), FlipFlop::default());
                 }
-                "&" => {
+                Some("&") => {
                     let label = &channel[1..];
                     if server
                         .recievers
                         .insert(label.to_string(), rec_list)
                         .is_some()
                     {
-                        return Err(anyhow!("duplicate channel name {} in input", label));
+                        return Err(duplicate(label).into());
                     }
                     server
                         .conjunctions
                         .insert(label.to_string(), Conjunction::default());
                 }
-                _ => return Err(anyhow!("bad input")),
+                _ => {
+                    return Err(
+                        ParseError::new(channel, "`broadcaster`, `%` or `&` channel").into(),
+                    )
+                }
             }
         }
         // filter recievers for input of conjunctions
@@ -176,8 +188,9 @@ impl Server {
                     forxf
                 ));
             }
-            server.rx_input_cycles.insert(forxf.to_owned(), None);
+            server.rx_inputs.push(forxf.to_owned());
         }
+        server.rx_inputs.sort();
         Ok(server)
     }
     fn send(&mut self, mes: Message) {
@@ -203,82 +216,297 @@ impl Server {
             None => Err(anyhow!("internal error unknown sender {}", sender)),
         }
     }
-    fn run(&mut self) -> Result<(u64, u64)> {
-        let max_button_pushes: u64 = 10_000;
-        let button_pushes_task1: u64 = 1_000;
-        let mut counter = 0;
-        let mut pulse_count = 0;
-        while counter < max_button_pushes {
-            // cycle count
-            counter += 1;
-            // push button
-            self.push_button();
-            while let Some(mes) = self.recieve_message() {
-                if self.rx_input_cycles.contains_key(&mes.sender) && mes.pulse {
-                    if let Some(cycle) = self.rx_input_cycles.get_mut(&mes.sender) {
-                        if cycle.is_none() {
-                            *cycle = Some(counter);
-                        }
-                    }
-                }
-                if mes.reciever == "broadcaster" {
-                    for rec_label in self.get_recievers(&mes.reciever)?.iter() {
-                        self.send(Message::new_pulse(
-                            mes.reciever.to_owned(),
-                            rec_label.to_owned(),
-                            false,
-                        ));
-                    }
-                }
-                if let Some(ff) = self.flip_flops.get_mut(&mes.reciever) {
-                    if let Some(pulse) = ff.recieve_pulse(mes.pulse) {
-                        for rec_label in self.get_recievers(&mes.reciever)?.iter() {
-                            self.send(Message::new_pulse(
-                                mes.reciever.to_owned(),
-                                rec_label.to_owned(),
-                                pulse,
-                            ));
-                        }
-                    }
-                }
-                if let Some(con) = self.conjunctions.get_mut(&mes.reciever) {
-                    let pulse = con.recieve_pulse(mes.pulse, &mes.sender)?;
-                    for rec_label in self.get_recievers(&mes.reciever)?.iter() {
-                        self.send(Message::new_pulse(
-                            mes.reciever.to_owned(),
-                            rec_label.to_owned(),
-                            pulse,
-                        ));
-                    }
-                }
+    // reciever of message sends pulses to its recievers
+    fn process_message(&mut self, mes: &Message) -> Result<()> {
+        if mes.reciever == "broadcaster" {
+            for rec_label in self.get_recievers(&mes.reciever)?.iter() {
+                self.send(Message::new_pulse(
+                    mes.reciever.to_owned(),
+                    rec_label.to_owned(),
+                    false,
+                ));
             }
-            if counter == button_pushes_task1 {
-                // count pulse after specified button pushes of task 1
-                pulse_count = self.pulse_value();
+        }
+        if let Some(ff) = self.flip_flops.get_mut(&mes.reciever) {
+            if let Some(pulse) = ff.recieve_pulse(mes.pulse) {
+                for rec_label in self.get_recievers(&mes.reciever)?.iter() {
+                    self.send(Message::new_pulse(
+                        mes.reciever.to_owned(),
+                        rec_label.to_owned(),
+                        pulse,
+                    ));
+                }
             }
-            if counter > button_pushes_task1 && self.rx_input_cycles.values().all(|c| c.is_some()) {
-                break;
+        }
+        if let Some(con) = self.conjunctions.get_mut(&mes.reciever) {
+            let pulse = con.recieve_pulse(mes.pulse, &mes.sender)?;
+            for rec_label in self.get_recievers(&mes.reciever)?.iter() {
+                self.send(Message::new_pulse(
+                    mes.reciever.to_owned(),
+                    rec_label.to_owned(),
+                    pulse,
+                ));
             }
         }
-
+        Ok(())
+    }
+    fn push_button_and_process_messages(
+        &mut self,
+        mut on_message: impl FnMut(&Message),
+    ) -> Result<()> {
+        self.push_button();
+        while let Some(mes) = self.recieve_message() {
+            on_message(&mes);
+            self.process_message(&mes)?;
+        }
+        Ok(())
+    }
+    fn pulse_value_after_button_pushes(&mut self, button_pushes: u64) -> Result<u64> {
+        for _ in 0..button_pushes {
+            self.push_button_and_process_messages(|_| ())?;
+        }
+        Ok(self.pulse_value())
+    }
+    // states of flip-flops and memories of conjunctions in order of their labels
+    fn module_states(&self) -> Vec<bool> {
+        self.flip_flops
+            .values()
+            .map(|ff| ff.state)
+            .chain(
+                self.conjunctions
+                    .values()
+                    .flat_map(|con| con.memory.values().copied()),
+            )
+            .collect()
+    }
+    // network of broadcaster and all modules, which send pulses directly or indirectly to module.
+    // Pulses to other modules are ignored.
+    fn sub_network(&self, module: &str) -> Server {
+        let senders: HashSet<String> = bfs(module.to_string(), |reciever| {
+            self.recievers
+                .iter()
+                .filter(|(_, rec_list)| rec_list.contains(reciever))
+                .map(|(sender, _)| sender.to_owned())
+                .collect::<Vec<_>>()
+        })
+        .map(|(sender, _)| sender)
+        .collect();
+        Server {
+            recievers: self
+                .recievers
+                .iter()
+                .filter(|(label, _)| senders.contains(*label))
+                .map(|(label, rec_list)| (label.to_owned(), rec_list.clone()))
+                .collect(),
+            flip_flops: self
+                .flip_flops
+                .iter()
+                .filter(|(label, _)| senders.contains(*label))
+                .map(|(label, ff)| (label.to_owned(), ff.clone()))
+                .collect(),
+            conjunctions: self
+                .conjunctions
+                .iter()
+                .filter(|(label, _)| senders.contains(*label))
+                .map(|(label, con)| (label.to_owned(), con.clone()))
+                .collect(),
+            ..Default::default()
+        }
+    }
+    // each input of rx feeder is fed by a counter, which sends a high pulse after a fixed number of
+    // button pushes. rx gets a low pulse after LCM of them.
+    fn button_pushes_for_rx(&self) -> Result<u64> {
         let mut lcm_button_push = 1;
-        for cycle in self.rx_input_cycles.values().map(|v| v.unwrap()) {
-            lcm_button_push = lcm(lcm_button_push, cycle);
+        for rx_input in self.rx_inputs.iter() {
+            // state is states of modules of counter network and if rx input sent a high pulse at
+            // last button push. States of modules determine next state, therefore history only
+            // keeps them instead of a copy of network per button push.
+            let mut network = self.sub_network(rx_input);
+            let counter = find_cycle((network.module_states(), false), |_| {
+                let mut high_pulse = false;
+                network.push_button_and_process_messages(|mes| {
+                    high_pulse |= mes.pulse && mes.sender == *rx_input
+                })?;
+                Ok::<_, anyhow::Error>((network.module_states(), high_pulse))
+            })?;
+            let button_pushes = counter
+                .periodic_step(|(_, high_pulse)| *high_pulse)
+                .ok_or_else(|| {
+                    anyhow!("{} does not send high pulses in a fixed cycle", rx_input)
+                })?;
+            lcm_button_push = lcm(lcm_button_push, button_pushes as u64);
         }
+        Ok(lcm_button_push)
+    }
+}
 
-        Ok((pulse_count, lcm_button_push))
+/// network of counters, which send a high pulse to feeder of `rx` after a prime number of button
+/// pushes. Returns network and button pushes, after which all counters sent a high pulse.
+fn generate_network(rng: &mut InputRng, counters: usize) -> Result<(String, u64)> {
+    // product of cycles must fit into u64
+    if !(1..=5).contains(&counters) {
+        return Err(anyhow!("1 to 5 counters"));
     }
+    let is_prime = |n: u64| {
+        (2..)
+            .take_while(|d| d * d <= n)
+            .all(|d| !n.is_multiple_of(d))
+    };
+    let mut cycles: Vec<u64> = Vec::with_capacity(counters);
+    while cycles.len() < counters {
+        let cycle = rng.gen_range(2_049..4_096);
+        if is_prime(cycle) && !cycles.contains(&cycle) {
+            cycles.push(cycle);
+        }
+    }
+    let mut names = generate::names(
+        rng,
+        14 * counters + 1,
+        2,
+        b"abcdefghijklmnopqrstuvwxyz",
+        |n| n != "rx",
+    )?
+    .into_iter();
+    let rx_feeder = names.next().unwrap();
+    let mut modules = vec![format!("&{} -> rx", rx_feeder)];
+    let mut first_flip_flops = Vec::with_capacity(counters);
+    for cycle in cycles.iter() {
+        // 12 flip flops count button pushes as binary number. When flip flops of all one bits of
+        // cycle are on, conjunction sends a low pulse to flip flops of zero bits and to first
+        // flip flop, which resets counter to 0, and to inverter, which sends a high pulse to rx feeder.
+        let flip_flops: Vec<String> = (0..12).map(|_| names.next().unwrap()).collect();
+        let (conjunction, inverter) = (names.next().unwrap(), names.next().unwrap());
+        let mut conjunction_outputs = vec![inverter.clone()];
+        for (bit, flip_flop) in flip_flops.iter().enumerate() {
+            let mut outputs: Vec<String> = flip_flops.get(bit + 1).cloned().into_iter().collect();
+            if cycle & (1 << bit) != 0 {
+                outputs.push(conjunction.clone());
+            }
+            if cycle & (1 << bit) == 0 || bit == 0 {
+                conjunction_outputs.push(flip_flop.clone());
+            }
+            modules.push(format!("%{} -> {}", flip_flop, outputs.join(", ")));
+        }
+        modules.push(format!(
+            "&{} -> {}",
+            conjunction,
+            conjunction_outputs.join(", ")
+        ));
+        modules.push(format!("&{} -> {}", inverter, rx_feeder));
+        first_flip_flops.push(flip_flops[0].clone());
+    }
+    modules.push(format!("broadcaster -> {}", first_flip_flops.join(", ")));
+    modules.shuffle(rng);
+    Ok((modules.join("\n") + "\n", cycles.iter().product()))
 }
 
-pub fn day_20() -> Result<()> {
-    let input = include_str!("../../assets/day_20.txt");
-    let mut server = Server::from_str(input)?;
-    let (result_part1, result_part2) = server.run()?;
-    println!("result day 20 part 1: {}", result_part1);
-    assert_eq!(result_part1, 825_167_435);
-    println!("result day 20 part 2: {}", result_part2);
-    assert_eq!(result_part2, 225_514_321_828_633);
-    Ok(())
+/// processes one pulse of queue per step; pushes button, if queue is empty, until button is
+/// pushed as often as in part 1
+struct PulseSimulation {
+    server: Server,
+    button_pushes: u64,
+    last_message: Option<Message>,
+}
+
+impl PulseSimulation {
+    const BUTTON_PUSHES: u64 = 1_000;
+}
+
+impl Simulation for PulseSimulation {
+    fn step(&mut self) -> Result<bool> {
+        match self.server.recieve_message() {
+            Some(mes) => {
+                self.server.process_message(&mes)?;
+                self.last_message = Some(mes);
+            }
+            None if self.button_pushes == Self::BUTTON_PUSHES => return Ok(false),
+            None => {
+                self.server.push_button();
+                self.button_pushes += 1;
+                self.last_message = None;
+            }
+        }
+        Ok(true)
+    }
+    fn frame(&self) -> String {
+        let pulse = |p: bool| if p { "high" } else { "low" };
+        let mut frame = match &self.last_message {
+            Some(mes) => format!(
+                "{} -{}-> {}\n",
+                if mes.sender.is_empty() {
+                    "button"
+                } else {
+                    &mes.sender
+                },
+                pulse(mes.pulse),
+                mes.reciever
+            ),
+            None => String::from("button pushed\n"),
+        };
+        let mut flip_flops: Vec<(&String, &FlipFlop)> = self.server.flip_flops.iter().collect();
+        flip_flops.sort_by_key(|(label, _)| *label);
+        for line in flip_flops.chunks(8) {
+            let line: Vec<String> = line
+                .iter()
+                .map(|(label, ff)| format!("%{}: {:3}", label, if ff.state { "on" } else { "off" }))
+                .collect();
+            frame.push_str(&line.join("  "));
+            frame.push('\n');
+        }
+        let mut conjunctions: Vec<(&String, &Conjunction)> =
+            self.server.conjunctions.iter().collect();
+        conjunctions.sort_by_key(|(label, _)| *label);
+        for (label, con) in conjunctions {
+            let mut memory: Vec<String> = con
+                .memory
+                .iter()
+                .map(|(sender, p)| format!("{}: {}", sender, pulse(*p)))
+                .collect();
+            memory.sort();
+            frame.push_str(&format!("&{} <- {}\n", label, memory.join(", ")));
+        }
+        frame
+    }
+    fn counters(&self) -> Vec<(&'static str, u64)> {
+        vec![
+            ("button pushes", self.button_pushes),
+            ("queued pulses", self.server.queue.len() as u64),
+            ("low_pulse_count", self.server.low_pulse_count),
+            ("high_pulse_count", self.server.high_pulse_count),
+        ]
+    }
+}
+
+/// Pulse Propagation: product of low and high pulses after 1_000 button pushes (part 1) and button
+/// pushes until `rx` gets a low pulse (part 2)
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u8 = 20;
+    const GEN_SIZE: (usize, &'static str) = (4, "number of counters");
+    type Input<'a> = Server;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        Server::from_str(input)
+    }
+    fn part_1(server: &Self::Input<'_>) -> Result<Self::Answer1> {
+        server.clone().pulse_value_after_button_pushes(1_000)
+    }
+    fn part_2(server: &Self::Input<'_>) -> Result<Self::Answer2> {
+        server.button_pushes_for_rx()
+    }
+    fn generate(rng: &mut InputRng, size: usize) -> Result<String> {
+        Ok(generate_network(rng, size)?.0)
+    }
+    fn simulation<'a>(server: &'a Self::Input<'_>) -> Result<Option<Box<dyn Simulation + 'a>>> {
+        Ok(Some(Box::new(PulseSimulation {
+            server: server.clone(),
+            button_pushes: 0,
+            last_message: None,
+        })))
+    }
 }
 
 #[cfg(test)]
@@ -294,8 +522,7 @@ mod tests {
                            %c -> inv\n\
                            &inv -> a";
         let mut server = Server::from_str(input)?;
-        let (result_part1, _) = server.run()?;
-        println!("result day 20 example 1 part 1: {}", result_part1);
+        let result_part1 = server.pulse_value_after_button_pushes(1_000)?;
         assert_eq!(result_part1, 32_000_000);
         Ok(())
     }
@@ -308,9 +535,50 @@ mod tests {
                            %b -> con\n\
                            &con -> output";
         let mut server = Server::from_str(input)?;
-        let (result_part1, _) = server.run()?;
-        println!("result day 20 example 1 part 1: {}", result_part1);
+        let result_part1 = server.pulse_value_after_button_pushes(1_000)?;
         assert_eq!(result_part1, 11_687_500);
         Ok(())
     }
+
+    #[test]
+    fn test_generated_network() -> Result<()> {
+        for seed in 0..3 {
+            let (input, pushes) = generate_network(&mut generate::input_rng(seed), 4)?;
+            let network = Day20::parse(&input)?;
+            assert_eq!(Day20::part_2(&network)?, pushes);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_pulse_simulation() -> Result<()> {
+        let input = "broadcaster -> a, b, c\n\
+                           %a -> b\n\
+                           %b -> c\n\
+                           %c -> inv\n\
+                           &inv -> a";
+        let server = Day20::parse(input)?;
+        let mut simulation = Day20::simulation(&server)?.unwrap();
+        // first button push sends 8 low and 4 high pulses
+        for _ in 0..13 {
+            assert!(simulation.step()?);
+        }
+        assert_eq!(
+            simulation.counters(),
+            vec![
+                ("button pushes", 1),
+                ("queued pulses", 0),
+                ("low_pulse_count", 8),
+                ("high_pulse_count", 4)
+            ]
+        );
+        assert_eq!(
+            simulation.frame(),
+            "inv -high-> a\n%a: off  %b: off  %c: off\n&inv <- c: low\n"
+        );
+        while simulation.step()? {}
+        assert_eq!(simulation.counters()[2], ("low_pulse_count", 8_000));
+        assert_eq!(simulation.counters()[3], ("high_pulse_count", 4_000));
+        Ok(())
+    }
 }
@@ -1,75 +1,114 @@
 //!day_21.rs
 
-use anyhow::Result;
-use my_lib::my_compass::Compass;
-use my_lib::my_map_point::MapPoint;
-use my_lib::my_map_two_dim::MyMap2D;
+use crate::generate::{self, InputRng};
+use crate::my_lib::my_map_point::MapPoint;
+use crate::my_lib::my_map_point::MapSize;
+use crate::my_lib::my_map_two_dim::MyMap2D;
+use crate::my_lib::my_search::bfs;
+use crate::parse::{self, ParseError};
+use crate::render::{Image, Rgb};
+use crate::solution::Solution;
+use anyhow::{anyhow, Result};
+use rand::Rng;
+use std::collections::HashSet;
 
-// values taken from ../../assets/day_21.txt
-const X: usize = 131;
-const Y: usize = 131;
-
-struct Garden<const X: usize, const Y: usize> {
-    map: MyMap2D<char, X, Y>,
+/// garden plots and rocks with start tile
+pub struct Garden {
+    map: MyMap2D<char>,
 }
 
-impl<const X: usize, const Y: usize> Garden<X, Y> {
-    fn from_str(input: &str) -> Self {
-        Self {
-            map: MyMap2D::from(input),
+impl Garden {
+    fn from_str(input: &str) -> Result<Self, ParseError> {
+        let map = parse::grid_with(input, |c| match c {
+            '.' | '#' | 'S' => Ok(c),
+            _ => Err(ParseError::char(c, "`.`, `#` or `S`")),
+        })?;
+        if !map.iter().any(|(_, c)| *c == 'S') {
+            return Err(ParseError::new(&input[input.len()..], "start tile `S`"));
         }
+        Ok(Self { map })
+    }
+    fn start_point(&self) -> Result<MapPoint> {
+        self.map
+            .iter()
+            .find(|(_, c)| **c == 'S')
+            .map(|(p, _)| p)
+            .ok_or_else(|| anyhow!("garden has no start tile `S`"))
     }
-    fn count_steps(
+    fn count_steps(&self, start_point: MapPoint, steps_to_take: usize, count_even: bool) -> usize {
+        self.reached_plots(start_point, steps_to_take, count_even)
+            .count()
+    }
+    // plots with even or odd distance from start_point, which are reached within steps_to_take
+    fn reached_plots(
         &self,
-        start_point: MapPoint<X, Y>,
+        start_point: MapPoint,
         steps_to_take: usize,
         count_even: bool,
-    ) -> usize {
-        let filter_fn = Box::new(
-            move |_: MapPoint<X, Y>,
-                  value_of_next_cell: &char,
-                  _: Compass,
-                  _: MapPoint<X, Y>,
-                  _: &char,
-                  current_distance: usize| {
-                (*value_of_next_cell == '.' || *value_of_next_cell == 'S')
-                    && current_distance <= steps_to_take
-            },
-        );
+    ) -> impl Iterator<Item = MapPoint> + '_ {
         let remainder = if count_even { 0 } else { 1 };
-        let garden_tiles = self
-            .map
-            .iter_distance(start_point, filter_fn)
-            .filter(|(.., d)| *d % 2 == remainder)
-            .count();
-        garden_tiles
+        bfs(start_point, |plot| {
+            self.map
+                .iter_neighbors(*plot)
+                .filter(|(.., c)| **c != '#')
+                .map(|(p, ..)| p)
+        })
+        .take_while(move |(_, steps)| *steps <= steps_to_take)
+        .filter(move |(_, steps)| steps % 2 == remainder)
+        .map(|(p, _)| p)
     }
-    fn count_steps_infinite_garden(&self, steps_to_take: usize) -> usize {
-        // check S is in middle of
-        let start_point = self
-            .map
-            .iter()
-            .find(|(_, c)| **c == 'S')
-            .map(|(p, _)| p)
-            .unwrap();
-        assert_eq!(start_point.x(), X / 2);
-        assert_eq!(start_point.y(), Y / 2);
+    /// number of plots reached in exactly steps_to_take steps in infinitely repeated garden
+    pub fn count_steps_infinite_garden(&self, steps_to_take: usize) -> Result<usize> {
+        // garden must be square with S in middle of it
+        let x = self.map.width();
+        let y = self.map.height();
+        if x != y || x < 3 {
+            return Err(anyhow!(
+                "garden of {} x {} tiles must be square with at least 3 tiles per side",
+                x,
+                y
+            ));
+        }
+        let start_point = self.start_point()?;
+        if start_point.x() != x / 2 || start_point.y() != y / 2 {
+            return Err(anyhow!(
+                "start tile at ({}, {}) is not in middle ({}, {}) of garden",
+                start_point.x(),
+                start_point.y(),
+                x / 2,
+                y / 2
+            ));
+        }
         // no rocks in center vertical or horizontal line, or at edge
-        assert!(!self
+        if self
             .map
             .iter_column(start_point.x())
-            .any(|(_, v)| *v == '#'));
-        assert!(!self.map.iter_row(start_point.y()).any(|(_, v)| *v == '#'));
-        assert!(!self
+            .chain(self.map.iter_row(start_point.y()))
+            .any(|(_, v)| *v == '#')
+        {
+            return Err(anyhow!(
+                "center row and column of garden must be free of rocks"
+            ));
+        }
+        if self
             .map
             .iter_edge((0, 0).into(), false)
-            .any(|(_, v)| *v == '#'));
+            .any(|(_, v)| *v == '#')
+        {
+            return Err(anyhow!("edge of garden must be free of rocks"));
+        }
         // calc grid size
-        let grid_cells_one_direction = steps_to_take / X;
-        let grid_cells_remaining_steps = steps_to_take % X;
+        let grid_cells_one_direction = steps_to_take / x;
+        let grid_cells_remaining_steps = steps_to_take % x;
         // steps you take from center garden to map edge
-        let steps_to_edge = X / 2;
-        assert_eq!(grid_cells_remaining_steps, steps_to_edge);
+        let steps_to_edge = x / 2;
+        if grid_cells_remaining_steps != steps_to_edge || grid_cells_one_direction == 0 {
+            return Err(anyhow!(
+                "{} steps must end at edge of a garden beyond first garden, e.g. {} steps",
+                steps_to_take,
+                x + steps_to_edge
+            ));
+        }
         let num_even_grids = grid_cells_one_direction.pow(2);
         let num_odd_grids = (grid_cells_one_direction - 1).pow(2);
         let steps_even_grid = self.count_steps(start_point, steps_to_take, true);
@@ -77,61 +116,161 @@ impl<const X: usize, const Y: usize> Garden<X, Y> {
         // first count full grids
         let mut garden_tiles = steps_even_grid * num_even_grids + steps_odd_grid * num_odd_grids;
         // 4 corner tiles
-        let steps_corner_n = self.count_steps((X / 2, Y - 1).into(), X - 1, true);
-        let steps_corner_e = self.count_steps((0, Y / 2).into(), X - 1, true);
-        let steps_corner_s = self.count_steps((X / 2, 0).into(), X - 1, true);
-        let steps_corner_w = self.count_steps((X - 1, Y / 2).into(), X - 1, true);
+        let steps_corner_n = self.count_steps((x / 2, y - 1).into(), x - 1, true);
+        let steps_corner_e = self.count_steps((0, y / 2).into(), x - 1, true);
+        let steps_corner_s = self.count_steps((x / 2, 0).into(), x - 1, true);
+        let steps_corner_w = self.count_steps((x - 1, y / 2).into(), x - 1, true);
         garden_tiles += steps_corner_n + steps_corner_e + steps_corner_s + steps_corner_w;
         // small side tiles
-        let steps_small_side_tile_nw = self.count_steps((X - 1, Y - 1).into(), X / 2 - 1, true);
-        let steps_small_side_tile_ne = self.count_steps((0, Y - 1).into(), X / 2 - 1, true);
-        let steps_small_side_tile_sw = self.count_steps((X - 1, 0).into(), X / 2 - 1, true);
-        let steps_small_side_tile_se = self.count_steps((0, 0).into(), X / 2 - 1, true);
+        let steps_small_side_tile_nw = self.count_steps((x - 1, y - 1).into(), x / 2 - 1, true);
+        let steps_small_side_tile_ne = self.count_steps((0, y - 1).into(), x / 2 - 1, true);
+        let steps_small_side_tile_sw = self.count_steps((x - 1, 0).into(), x / 2 - 1, true);
+        let steps_small_side_tile_se = self.count_steps((0, 0).into(), x / 2 - 1, true);
         garden_tiles += (steps_small_side_tile_ne
             + steps_small_side_tile_nw
             + steps_small_side_tile_se
             + steps_small_side_tile_sw)
             * grid_cells_one_direction;
         // big side tiles
-        let steps_big_side_tile_nw = self.count_steps((X - 1, Y - 1).into(), X + X / 2 - 1, false);
-        let steps_big_side_tile_ne = self.count_steps((0, Y - 1).into(), X + X / 2 - 1, false);
-        let steps_big_side_tile_sw = self.count_steps((X - 1, 0).into(), X + X / 2 - 1, false);
-        let steps_big_side_tile_se = self.count_steps((0, 0).into(), X + X / 2 - 1, false);
+        let steps_big_side_tile_nw = self.count_steps((x - 1, y - 1).into(), x + x / 2 - 1, false);
+        let steps_big_side_tile_ne = self.count_steps((0, y - 1).into(), x + x / 2 - 1, false);
+        let steps_big_side_tile_sw = self.count_steps((x - 1, 0).into(), x + x / 2 - 1, false);
+        let steps_big_side_tile_se = self.count_steps((0, 0).into(), x + x / 2 - 1, false);
         garden_tiles += (steps_big_side_tile_ne
             + steps_big_side_tile_nw
             + steps_big_side_tile_se
             + steps_big_side_tile_sw)
             * (grid_cells_one_direction - 1);
-        garden_tiles
+        Ok(garden_tiles)
     }
 }
 
-pub fn day_21() -> Result<()> {
-    let input = include_str!("../../assets/day_21.txt");
-    let garden = Garden::<X, Y>::from_str(input);
-    let steps_to_take = 64;
-    let start_point = garden
-        .map
-        .iter()
-        .find(|(_, c)| **c == 'S')
-        .map(|(p, _)| p)
-        .unwrap();
-    let result_part1 = garden.count_steps(start_point, steps_to_take, true);
-    println!("result day 21 part 1: {}", result_part1);
-    assert_eq!(result_part1, 3_697);
-    let steps_to_take = 26_501_365;
-    let result_part2 = garden.count_steps_infinite_garden(steps_to_take);
-    println!("result day 21 part 2: {}", result_part2);
-    assert_eq!(result_part2, 608_152_828_731_262);
-    Ok(())
+/// Step Counter: garden plots reached in 64 steps (part 1) and in 26_501_365 steps on infinite
+/// garden (part 2)
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: u8 = 21;
+    const GEN_SIZE: (usize, &'static str) = (131, "width and height of garden");
+    type Input<'a> = Garden;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        Ok(Garden::from_str(input)?)
+    }
+    fn part_1(garden: &Self::Input<'_>) -> Result<Self::Answer1> {
+        let steps_to_take = 64;
+        let start_point = garden.start_point()?;
+        Ok(garden.count_steps(start_point, steps_to_take, true))
+    }
+    fn part_2(garden: &Self::Input<'_>) -> Result<Self::Answer2> {
+        let steps_to_take = 26_501_365;
+        garden.count_steps_infinite_garden(steps_to_take)
+    }
+    // garden plots reached in 64 steps of part 1
+    fn render(garden: &Self::Input<'_>) -> Result<Option<Image>> {
+        let start_point = garden.start_point()?;
+        let reached: HashSet<MapPoint> = garden.reached_plots(start_point, 64, true).collect();
+        Ok(Some(Image::from_map(&garden.map, |p, c| {
+            if p == start_point {
+                Rgb::RED
+            } else if reached.contains(&p) {
+                Rgb::GREEN
+            } else if *c == '#' {
+                Rgb::GREY
+            } else {
+                Rgb::BLACK
+            }
+        })))
+    }
+    fn generate(rng: &mut InputRng, size: usize) -> Result<String> {
+        // edge of garden must be reached after walking through full gardens in part 2,
+        // therefore 26_501_365 % size == size / 2
+        if size < 5 || (2 * 26_501_365 + 1) % size != 0 {
+            return Err(anyhow!(
+                "size of garden must divide 53_002_731, e.g. 131 or 393"
+            ));
+        }
+        let center = MapPoint::new(size / 2, size / 2);
+        let garden_size = MapSize::new(size, size);
+        Ok(generate::grid(rng, garden_size, |rng, p| {
+            // center lines, edge and diamond between them are free of rocks
+            if p == center {
+                'S'
+            } else if p.x() == center.x()
+                || p.y() == center.y()
+                || p.is_on_edge(garden_size)
+                || p.distance(center).abs_diff(size / 2) <= 1
+                || !rng.gen_bool(0.15)
+            {
+                '.'
+            } else {
+                '#'
+            }
+        }))
+    }
+}
+
+#[cfg(test)]
+mod reference {
+
+    use super::*;
+    use std::collections::VecDeque;
+
+    /// walks through infinite garden step by step
+    pub fn count_steps_infinite_garden(input: &str, steps_to_take: usize) -> Result<usize> {
+        let garden: Vec<&[u8]> = input.lines().map(|l| l.as_bytes()).collect();
+        let (width, height) = (garden[0].len(), garden.len());
+        let (start_x, start_y) = garden
+            .iter()
+            .enumerate()
+            .find_map(|(y, row)| row.iter().position(|c| *c == b'S').map(|x| (x, y)))
+            .ok_or_else(|| anyhow!("no start tile `S`"))?;
+        // area, which can be reached, with start in its center
+        let side = 2 * steps_to_take + 1;
+        let is_rock = |x: usize, y: usize| {
+            let garden_x = (x + start_x + width - steps_to_take % width) % width;
+            let garden_y = (y + start_y + height - steps_to_take % height) % height;
+            garden[garden_y][garden_x] == b'#'
+        };
+        let mut distances = vec![usize::MAX; side * side];
+        distances[steps_to_take * side + steps_to_take] = 0;
+        let mut queue = VecDeque::from([(steps_to_take, steps_to_take)]);
+        let mut garden_tiles = 0;
+        while let Some((x, y)) = queue.pop_front() {
+            let distance = distances[y * side + x];
+            if distance % 2 == steps_to_take % 2 {
+                garden_tiles += 1;
+            }
+            if distance == steps_to_take {
+                continue;
+            }
+            for (next_x, next_y) in [
+                (x + 1, y),
+                (x.wrapping_sub(1), y),
+                (x, y + 1),
+                (x, y.wrapping_sub(1)),
+            ] {
+                if next_x < side
+                    && next_y < side
+                    && distances[next_y * side + next_x] == usize::MAX
+                    && !is_rock(next_x, next_y)
+                {
+                    distances[next_y * side + next_x] = distance + 1;
+                    queue.push_back((next_x, next_y));
+                }
+            }
+        }
+        Ok(garden_tiles)
+    }
 }
 
 #[cfg(test)]
 mod tests {
 
     use super::*;
-    const XT: usize = 11;
-    const YT: usize = 11;
+    use crate::differential;
 
     #[test]
     fn test_example_part1() -> Result<()> {
@@ -146,7 +285,7 @@ mod tests {
                            .##.#.####.\n\
                            .##..##.##.\n\
                            ...........";
-        let garden = Garden::<XT, YT>::from_str(input);
+        let garden = Garden::from_str(input)?;
         let steps_to_take = 6;
         let start_point = garden
             .map
@@ -155,27 +294,104 @@ mod tests {
             .map(|(p, _)| p)
             .unwrap();
         let result_part1 = garden.count_steps(start_point, steps_to_take, true);
-        println!("result day 21 example part 1: {}", result_part1);
         assert_eq!(result_part1, 16);
+        let image = Day21::render(&garden)?.unwrap();
+        let reached = garden
+            .map
+            .iter()
+            .filter(|(p, _)| matches!(image.get(*p), Rgb::GREEN | Rgb::RED))
+            .count();
+        assert_eq!(reached, Day21::part_1(&garden)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_infinite_garden_errors() -> Result<()> {
+        // rocks in center row and column of example
+        let example = Day21::parse(
+            "...........\n\
+             .....###.#.\n\
+             .###.##..#.\n\
+             ..#.#...#..\n\
+             ....#.#....\n\
+             .##..S####.\n\
+             .##..#...#.\n\
+             .......##..\n\
+             .##.#.####.\n\
+             .##..##.##.\n\
+             ...........",
+        )?;
+        assert!(example.count_steps_infinite_garden(5_000).is_err());
+        let non_square = Day21::parse("...\n.S.\n...\n...")?;
+        assert!(non_square.count_steps_infinite_garden(26_501_365).is_err());
+        let input = ".......\n.......\n.......\n...S...\n.......\n.......\n.......";
+        let garden = Day21::parse(input)?;
+        assert!(garden.count_steps_infinite_garden(3).is_err());
+        assert!(garden.count_steps_infinite_garden(16).is_err());
+        assert_eq!(
+            garden.count_steps_infinite_garden(17)?,
+            reference::count_steps_infinite_garden(input, 17)?
+        );
         Ok(())
     }
 
     #[test]
     fn test_calculations_part2() {
+        // size of garden in ../../assets/day_21.txt
+        const X: usize = 131;
         let num_steps: usize = 26_501_365;
         let grid_cells_one_direction = num_steps / X;
-        println!("grid_cells_one_direction: {}", grid_cells_one_direction);
         let grid_cells_remaining_steps = num_steps % X;
-        println!("grid_cells_remaining_steps: {}", grid_cells_remaining_steps);
         let steps_to_edge = X / 2;
-        println!("steps_to_edge: {}", steps_to_edge);
         let num_even_grids = grid_cells_one_direction.pow(2);
-        println!("num_even_grids: {}", num_even_grids);
         let num_odd_grids = (grid_cells_one_direction - 1).pow(2);
-        println!("num_odd_grids: {}", num_odd_grids);
+        assert_eq!(grid_cells_one_direction, 202_300);
+        // steps end at edge of garden
+        assert_eq!(grid_cells_remaining_steps, steps_to_edge);
+        assert_eq!(num_even_grids, 40_925_290_000);
+        assert_eq!(num_odd_grids, 40_924_885_401);
         let steps_along_side_from_middle_to_next_grid = X / 2 + 2;
         let remaining_steps = X - steps_along_side_from_middle_to_next_grid;
         let remaining_steps_2 = X / 2 - 1;
         assert_eq!(remaining_steps, remaining_steps_2);
     }
+
+    #[test]
+    fn test_reference_example() -> Result<()> {
+        let input = "...........\n\
+                           .....###.#.\n\
+                           .###.##..#.\n\
+                           ..#.#...#..\n\
+                           ....#.#....\n\
+                           .##..S####.\n\
+                           .##..#...#.\n\
+                           .......##..\n\
+                           .##.#.####.\n\
+                           .##..##.##.\n\
+                           ...........";
+        for (steps_to_take, garden_tiles) in [(6, 16), (10, 50), (50, 1_594), (100, 6_536)] {
+            assert_eq!(
+                reference::count_steps_infinite_garden(input, steps_to_take)?,
+                garden_tiles
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_differential_infinite_garden() -> Result<()> {
+        // like 26_501_365 steps, steps reach edge after an even number of full gardens
+        for full_gardens in [2, 4] {
+            let steps_to_take = full_gardens * 131 + 65;
+            if let Some(divergence) = differential::first_divergence(
+                0..3,
+                |rng| Day21::generate(rng, 131),
+                |input| Day21::parse(input)?.count_steps_infinite_garden(steps_to_take),
+                |input| reference::count_steps_infinite_garden(input, steps_to_take),
+            )? {
+                panic!("day 21 with {} steps: {}", steps_to_take, divergence);
+            }
+        }
+        Ok(())
+    }
 }
@@ -1,10 +1,15 @@
 //!day_22.rs
 
+use crate::generate::InputRng;
+use crate::parse::{self, ParseError};
+use crate::solution::Solution;
 use anyhow::Result;
+use rand::{seq::SliceRandom, Rng};
 use std::{
     cmp::Ordering,
     collections::{HashMap, VecDeque},
 };
+use tracing::debug;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct Point3D {
@@ -34,20 +39,18 @@ impl Ord for Point3D {
     }
 }
 
-impl From<&str> for Point3D {
-    fn from(value: &str) -> Self {
-        let mut digits_iter = value
-            .split(',')
-            .map(|d| d.parse::<i32>().expect("bad Point3D digit"));
+impl TryFrom<&str> for Point3D {
+    type Error = ParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let mut digits_iter = value.split(',');
         let point = Self {
-            x: digits_iter.next().unwrap(),
-            y: digits_iter.next().unwrap(),
-            z: digits_iter.next().unwrap(),
+            x: parse::number::<i32>(parse::next_token(&mut digits_iter, value, "x")?)?,
+            y: parse::number::<i32>(parse::next_token(&mut digits_iter, value, "y")?)?,
+            z: parse::number::<i32>(parse::next_token(&mut digits_iter, value, "z")?)?,
         };
-        if digits_iter.next().is_some() {
-            panic!("input Point3D too long");
-        }
-        point
+        parse::no_more_tokens(&mut digits_iter, "end of point")?;
+        Ok(point)
     }
 }
 
@@ -73,12 +76,12 @@ impl Ord for Brick {
     }
 }
 
-impl From<&str> for Brick {
-    fn from(value: &str) -> Self {
-        let (alpha, beta) = value
-            .split_once('~')
-            .map(|(a, b)| (Point3D::from(a), Point3D::from(b)))
-            .unwrap();
+impl TryFrom<&str> for Brick {
+    type Error = ParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let (alpha, beta) = parse::split_once(value, "~")?;
+        let (alpha, beta) = (Point3D::try_from(alpha)?, Point3D::try_from(beta)?);
         // A Brick is always a single line of cubes, therefore it can only extend in one direction.
         // In case of at least two cubes, make sure that a is always < b. In case of a single cube a == b.
         let (a, b) = if alpha <= beta {
@@ -86,7 +89,7 @@ impl From<&str> for Brick {
         } else {
             (beta, alpha)
         };
-        Brick { a, b }
+        Ok(Brick { a, b })
     }
 }
 
@@ -113,12 +116,13 @@ impl Brick {
 }
 
 fn drop_bricks(
-    bricks: &Vec<Brick>,
+    bricks: &[Brick],
     supporting_bricks: &mut HashMap<Brick, Vec<Brick>>,
     supported_bricks: &mut HashMap<Brick, Vec<Brick>>,
 ) -> Vec<Brick> {
     let mut dropped_bricks: Vec<Brick> = Vec::with_capacity(bricks.len());
     for mut brick in bricks.iter().map(|b| b.to_owned()) {
+        let start_z = brick.a.z;
         loop {
             if brick.a.z == 1 {
                 break;
@@ -150,13 +154,14 @@ fn drop_bricks(
             }
             brick = dropped_brick;
         }
+        debug!(?brick, start_z, end_z = brick.a.z, "brick dropped");
         dropped_bricks.push(brick);
     }
     dropped_bricks
 }
 
 fn bricks_to_disintegrate_without_consequence(
-    bricks: &Vec<Brick>,
+    bricks: &[Brick],
     supporting_bricks: &HashMap<Brick, Vec<Brick>>,
     supported_bricks: &HashMap<Brick, Vec<Brick>>,
 ) -> Vec<Brick> {
@@ -230,35 +235,95 @@ fn bricks_to_disintegrate_with_consequence(
     falling_bricks.values().map(|fb| fb.len()).sum()
 }
 
-pub fn day_22() -> Result<()> {
-    let input = include_str!("../../assets/day_22.txt");
-    let mut supporting_bricks: HashMap<Brick, Vec<Brick>> = HashMap::new();
-    let mut supported_bricks: HashMap<Brick, Vec<Brick>> = HashMap::new();
-    let mut falling_bricks: HashMap<Brick, Vec<Brick>> = HashMap::new();
-    let mut bricks: Vec<Brick> = input.lines().map(Brick::from).collect();
-    bricks.sort();
+/// bricks after dropping them to the ground with their supporting relations
+pub struct SettledBricks {
+    bricks: Vec<Brick>,
+    supporting_bricks: HashMap<Brick, Vec<Brick>>,
+    supported_bricks: HashMap<Brick, Vec<Brick>>,
+}
 
-    bricks = drop_bricks(&bricks, &mut supporting_bricks, &mut supported_bricks);
+/// Sand Slabs: bricks, which can be disintegrated (part 1) and sum of bricks, which would fall
+/// (part 2)
+pub struct Day22;
 
-    let bricks_save_to_disintegrate =
-        bricks_to_disintegrate_without_consequence(&bricks, &supporting_bricks, &supported_bricks);
-    println!(
-        "result day 22 part 1: {}",
-        bricks_save_to_disintegrate.len()
-    );
-    assert_eq!(bricks_save_to_disintegrate.len(), 471);
+impl Solution for Day22 {
+    const DAY: u8 = 22;
+    const GEN_SIZE: (usize, &'static str) = (1_200, "number of bricks");
+    type Input<'a> = SettledBricks;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-    let result_part2 = bricks_to_disintegrate_with_consequence(
-        &bricks,
-        &bricks_save_to_disintegrate,
-        &supporting_bricks,
-        &supported_bricks,
-        &mut falling_bricks,
-    );
-    println!("result day 22 part 2: {}", result_part2);
-    assert_eq!(result_part2, 68_525);
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        let mut supporting_bricks: HashMap<Brick, Vec<Brick>> = HashMap::new();
+        let mut supported_bricks: HashMap<Brick, Vec<Brick>> = HashMap::new();
+        let mut bricks: Vec<Brick> = input
+            .lines()
+            .map(Brick::try_from)
+            .collect::<Result<_, _>>()?;
+        bricks.sort();
 
-    Ok(())
+        bricks = drop_bricks(&bricks, &mut supporting_bricks, &mut supported_bricks);
+        Ok(SettledBricks {
+            bricks,
+            supporting_bricks,
+            supported_bricks,
+        })
+    }
+    fn part_1(settled: &Self::Input<'_>) -> Result<Self::Answer1> {
+        let bricks_save_to_disintegrate = bricks_to_disintegrate_without_consequence(
+            &settled.bricks,
+            &settled.supporting_bricks,
+            &settled.supported_bricks,
+        );
+        Ok(bricks_save_to_disintegrate.len())
+    }
+    fn part_2(settled: &Self::Input<'_>) -> Result<Self::Answer2> {
+        let mut falling_bricks: HashMap<Brick, Vec<Brick>> = HashMap::new();
+        let bricks_save_to_disintegrate = bricks_to_disintegrate_without_consequence(
+            &settled.bricks,
+            &settled.supporting_bricks,
+            &settled.supported_bricks,
+        );
+        Ok(bricks_to_disintegrate_with_consequence(
+            &settled.bricks,
+            &bricks_save_to_disintegrate,
+            &settled.supporting_bricks,
+            &settled.supported_bricks,
+            &mut falling_bricks,
+        ))
+    }
+    fn generate(rng: &mut InputRng, size: usize) -> Result<String> {
+        // bricks are stacked in order of generation on an area of 10 x 10 cubes, with gaps to fall
+        let mut heights = [[0_usize; 10]; 10];
+        let mut bricks = Vec::with_capacity(size);
+        for _ in 0..size {
+            let mut extent = [0; 3];
+            extent[rng.gen_range(0..3)] = rng.gen_range(0..4);
+            let (x, y) = (
+                rng.gen_range(0..10 - extent[0]),
+                rng.gen_range(0..10 - extent[1]),
+            );
+            let area: Vec<(usize, usize)> = (x..=x + extent[0])
+                .flat_map(|x| (y..=y + extent[1]).map(move |y| (x, y)))
+                .collect();
+            let top = area.iter().map(|(x, y)| heights[*x][*y]).max().unwrap();
+            let z = top + 1 + rng.gen_range(0..3);
+            for (x, y) in area {
+                heights[x][y] = z + extent[2];
+            }
+            bricks.push(format!(
+                "{},{},{}~{},{},{}",
+                x,
+                y,
+                z,
+                x + extent[0],
+                y + extent[1],
+                z + extent[2]
+            ));
+        }
+        bricks.shuffle(rng);
+        Ok(bricks.join("\n") + "\n")
+    }
 }
 
 #[cfg(test)]
@@ -278,54 +343,21 @@ mod tests {
         let mut supporting_bricks: HashMap<Brick, Vec<Brick>> = HashMap::new();
         let mut supported_bricks: HashMap<Brick, Vec<Brick>> = HashMap::new();
         let mut falling_bricks: HashMap<Brick, Vec<Brick>> = HashMap::new();
-        let mut bricks: Vec<Brick> = input.lines().map(|l| Brick::from(l)).collect();
+        let mut bricks: Vec<Brick> = input.lines().map(|l| Brick::try_from(l).unwrap()).collect();
         bricks.sort();
 
         assert!(bricks[0].is_overlapping(&bricks[1].drop_brick_by_one()));
         assert!(!bricks[1].is_overlapping(&bricks[2].drop_brick_by_one()));
 
-        eprintln!("sorted bricks");
-        for brick in bricks.iter() {
-            eprintln!("{:?}", brick);
-        }
-
         bricks = drop_bricks(&bricks, &mut supporting_bricks, &mut supported_bricks);
-        eprintln!("droped bricks");
-        for brick in bricks.iter() {
-            eprintln!("{:?}", brick);
-        }
 
-        eprintln!("bricks who support");
-        for (brick, bricks_to_support) in supporting_bricks.iter() {
-            eprintln!("supporting brick: {:?}", brick);
-            for bts in bricks_to_support.iter() {
-                eprintln!("    supported brick: {:?}", bts);
-            }
-        }
-        eprintln!("bricks who are supported");
-        for (brick, bricks_who_support) in supported_bricks.iter() {
-            eprintln!("supported brick: {:?}", brick);
-            for bws in bricks_who_support.iter() {
-                eprintln!("    supporting brick: {:?}", bws);
-            }
-        }
-
-        eprintln!("bricks which could be disintegrated");
         let bricks_save_to_disintegrate = bricks_to_disintegrate_without_consequence(
             &bricks,
             &supporting_bricks,
             &supported_bricks,
         );
-        for disintegrate_brick in bricks_save_to_disintegrate.iter() {
-            eprintln!("{:?}", disintegrate_brick);
-        }
-        println!(
-            "result day 22 example part 1: {}",
-            bricks_save_to_disintegrate.len()
-        );
         assert_eq!(bricks_save_to_disintegrate.len(), 5);
 
-        eprintln!("bricks which fall if one brick is disintegrated");
         let result_part2 = bricks_to_disintegrate_with_consequence(
             &bricks,
             &bricks_save_to_disintegrate,
@@ -333,13 +365,6 @@ mod tests {
             &supported_bricks,
             &mut falling_bricks,
         );
-        for (disintegrated_brick, bricks_to_fall) in falling_bricks.iter() {
-            eprintln!("disintegrated_brick: {:?}", disintegrated_brick);
-            for falling_brick in bricks_to_fall.iter() {
-                eprintln!("    falling_brick: {:?}", falling_brick);
-            }
-        }
-        println!("result day 22 example part 2: {}", result_part2);
         assert_eq!(result_part2, 7);
 
         Ok(())
@@ -1,95 +1,66 @@
 //!day_23.rs
 
-use anyhow::Result;
-use my_lib::{
-    my_compass::Compass, my_map_point::MapPoint, my_map_two_dim::MyMap2D, my_tree::TreeNode,
+use crate::generate::{self, InputRng};
+use crate::my_lib::my_map_point::MapSize;
+use crate::my_lib::{
+    my_compass::Compass, my_map_point::MapPoint, my_map_two_dim::MyMap2D, my_search::bfs,
 };
-use std::{collections::HashMap, rc::Rc};
+use crate::parse::{self, ParseError};
+use crate::render::{Image, Rgb};
+use crate::solution::Solution;
+use anyhow::{anyhow, Result};
+use rand::Rng;
+use std::collections::{HashMap, HashSet};
 
-// values taken from ../../assets/day_23.txt
-const X: usize = 141;
-const Y: usize = 141;
+// trails from each junction to neighboring junctions; a trail contains both junctions
+type HikingGraph = HashMap<MapPoint, Vec<Vec<MapPoint>>>;
 
-#[derive(PartialEq)]
-struct Node<const X: usize, const Y: usize> {
-    start_point: MapPoint<X, Y>,
-    end_point: MapPoint<X, Y>,
-    next_points: Vec<MapPoint<X, Y>>,
-    steps: usize,
-    total_steps: usize,
+/// trails and slopes of forest
+#[derive(Clone)]
+pub struct Maze {
+    maze: MyMap2D<char>,
+    start_point: MapPoint,
+    end_point: MapPoint,
+    climbing_is_possible: bool,
 }
 
-impl<const X: usize, const Y: usize> Node<X, Y> {
-    fn new(
-        start_point: MapPoint<X, Y>,
-        last_point: MapPoint<X, Y>,
-        maze: &Maze<X, Y>,
-        previous_steps: usize,
-        cache: &mut HashMap<
-            (MapPoint<X, Y>, MapPoint<X, Y>),
-            (MapPoint<X, Y>, Vec<MapPoint<X, Y>>, usize),
-        >,
-    ) -> Self {
-        if let Some((cached_end_point, cached_next_points, cached_steps)) =
-            cache.get(&(start_point, last_point))
-        {
-            return Self {
-                start_point,
-                end_point: *cached_end_point,
-                next_points: cached_next_points.clone(),
-                steps: *cached_steps,
-                total_steps: previous_steps + cached_steps,
-            };
+impl TryFrom<&str> for Maze {
+    type Error = ParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let maze = parse::grid_with(value, |c| match c {
+            '.' | '#' | '^' | '>' | 'v' | '<' => Ok(c),
+            _ => Err(ParseError::char(c, "path, forest or slope")),
+        })?;
+        // start is in top row, end in bottom row; both must be paths
+        let mut lines = value.lines().map(|l| l.trim()).filter(|l| !l.is_empty());
+        let first_line = lines.next().unwrap_or_default();
+        let last_line = lines.next_back().unwrap_or(first_line);
+        if maze.width() < 3 {
+            return Err(ParseError::new(first_line, "row of at least 3 tiles"));
         }
-        let offset = if start_point == last_point {
-            0
-        } else if start_point
-            .iter_neighbors(Compass::N, true, false, false)
-            .any(|(n, _)| n == last_point)
-        {
-            1
-        } else {
-            panic!("last_point is not start_point or neighbor of start_point");
-        };
-        let (steps, (end_point, next_points)) =
-            MazeRunner::<X, Y>::new(maze, start_point, last_point)
-                .enumerate()
-                .last()
-                .unwrap();
-        cache.insert(
-            (start_point, last_point),
-            (end_point, next_points.clone(), steps + offset),
-        );
-        Self {
-            start_point,
-            end_point,
-            next_points,
-            steps: steps + offset,
-            total_steps: steps + previous_steps + offset,
+        if *maze.get((1, 0).into()) != '.' {
+            return Err(ParseError::new(&first_line[1..2], "path `.` as entry"));
         }
-    }
-}
-
-struct Maze<const X: usize, const Y: usize> {
-    maze: MyMap2D<char, X, Y>,
-    start_point: MapPoint<X, Y>,
-    end_point: MapPoint<X, Y>,
-    climbing_is_possible: bool,
-}
-
-impl<const X: usize, const Y: usize> From<&str> for Maze<X, Y> {
-    fn from(value: &str) -> Self {
-        Maze {
-            maze: MyMap2D::from(value),
+        let end_point: MapPoint = (maze.width() - 2, maze.height() - 1).into();
+        if *maze.get(end_point) != '.' {
+            let exit = maze.width() - 2;
+            return Err(ParseError::new(
+                &last_line[exit..exit + 1],
+                "path `.` as exit",
+            ));
+        }
+        Ok(Maze {
+            maze,
             start_point: (1, 0).into(),
-            end_point: (X - 2, Y - 1).into(),
+            end_point,
             climbing_is_possible: false,
-        }
+        })
     }
 }
 
-impl<const X: usize, const Y: usize> Maze<X, Y> {
-    fn is_possible(&self, point: &MapPoint<X, Y>, orientation: &Compass) -> bool {
+impl Maze {
+    fn is_possible(&self, point: &MapPoint, orientation: &Compass) -> bool {
         match self.maze.get(*point) {
             '.' => true,
             '^' => *orientation == Compass::N || self.climbing_is_possible,
@@ -99,7 +70,7 @@ impl<const X: usize, const Y: usize> Maze<X, Y> {
             _ => false,
         }
     } /*
-      fn next_possible_points(&self, point: &MapPoint<X, Y>) -> Vec<MapPoint<X, Y>> {
+      fn next_possible_points(&self, point: &MapPoint) -> Vec<MapPoint> {
           self.maze
               .iter_neighbors(*point)
               .filter(|(p, o, v)| **v != '.' && **v != '#' && self.is_possible(p, o))
@@ -107,110 +78,221 @@ impl<const X: usize, const Y: usize> Maze<X, Y> {
               .collect()
       } */
     fn go_hiking(&self) -> usize {
-        let mut cache: HashMap<
-            (MapPoint<X, Y>, MapPoint<X, Y>),
-            (MapPoint<X, Y>, Vec<MapPoint<X, Y>>, usize),
-        > = HashMap::new();
-        let root_node = Node::<X, Y>::new(self.start_point, self.start_point, self, 0, &mut cache);
-        let hiking_tree: Rc<TreeNode<Node<X, Y>>> = TreeNode::seed_root(root_node, 3);
-        for (hiking_node, _) in hiking_tree.iter_level_order_traversal() {
-            for next_start_point in hiking_node.get_value().next_points.iter() {
-                let next_hiking_node = Node::<X, Y>::new(
-                    *next_start_point,
-                    hiking_node.get_value().end_point,
-                    self,
-                    hiking_node.get_value().total_steps,
-                    &mut cache,
-                );
-                if !hiking_node
-                    .iter_back_track()
-                    .any(|v| v.get_value().end_point == next_hiking_node.end_point)
+        self.longest_hike(&self.hiking_graph())
+            .map(|(steps, _)| steps)
+            .unwrap_or_default()
+    }
+    // start, end and all points, where trails fork
+    fn junctions(&self) -> HashSet<MapPoint> {
+        self.maze
+            .iter()
+            .filter(|(p, c)| {
+                **c != '#'
+                    && (*p == self.start_point
+                        || *p == self.end_point
+                        || self
+                            .maze
+                            .iter_neighbors(*p)
+                            .filter(|(.., n)| **n != '#')
+                            .count()
+                            > 2)
+            })
+            .map(|(p, _)| p)
+            .collect()
+    }
+    fn hiking_graph(&self) -> HikingGraph {
+        let junctions = self.junctions();
+        junctions
+            .iter()
+            .map(|junction| {
+                // states are points with their previous point, therefore parallel trails to the
+                // same junction end in different states. Search stops at each other junction.
+                let start = (*junction, *junction);
+                let trails = bfs(start, |state: &(MapPoint, MapPoint)| {
+                    let (point, previous) = *state;
+                    let stop = *state != start && junctions.contains(&point);
+                    self.maze
+                        .iter_neighbors(point)
+                        .filter(move |(p, o, _)| !stop && *p != previous && self.is_possible(p, o))
+                        .map(move |(p, ..)| (p, point))
+                })
+                .into_distance_map();
+                // of parallel trails to the same junction only the longest is part of a longest hike
+                let mut next_junctions: HashMap<MapPoint, Vec<MapPoint>> = HashMap::new();
+                for (state, steps) in trails
+                    .iter()
+                    .filter(|((p, _), _)| p != junction && junctions.contains(p))
                 {
-                    // add hiking_node, if endpoint has not already been visited
-                    hiking_node.add_child(next_hiking_node, 3);
+                    if next_junctions
+                        .get(&state.0)
+                        .is_none_or(|trail| trail.len() <= steps)
+                    {
+                        let trail = trails
+                            .path_to(state)
+                            .unwrap_or_default()
+                            .into_iter()
+                            .map(|(p, _)| p)
+                            .collect();
+                        next_junctions.insert(state.0, trail);
+                    }
                 }
+                (*junction, next_junctions.into_values().collect())
+            })
+            .collect()
+    }
+    // longest hike from start to end, which visits each junction at most once: steps and trails
+    fn longest_hike<'a>(&self, graph: &'a HikingGraph) -> Option<(usize, Vec<&'a [MapPoint]>)> {
+        let mut longest_hike = None;
+        let mut visited = HashSet::from([self.start_point]);
+        self.extend_hike(
+            graph,
+            self.start_point,
+            &mut (0, Vec::new()),
+            &mut visited,
+            &mut longest_hike,
+        );
+        longest_hike
+    }
+    fn extend_hike<'a>(
+        &self,
+        graph: &'a HikingGraph,
+        junction: MapPoint,
+        hike: &mut (usize, Vec<&'a [MapPoint]>),
+        visited: &mut HashSet<MapPoint>,
+        longest_hike: &mut Option<(usize, Vec<&'a [MapPoint]>)>,
+    ) {
+        if junction == self.end_point {
+            if longest_hike
+                .as_ref()
+                .is_none_or(|(steps, _)| hike.0 > *steps)
+            {
+                *longest_hike = Some(hike.clone());
             }
+            return;
         }
-        // longest path is hiking node with most steps
-        hiking_tree
-            .iter_pre_order_traversal()
-            .filter(|n| n.get_value().end_point == self.end_point)
-            .map(|n| n.get_value().total_steps)
-            .max()
-            .unwrap()
-    }
-}
-
-struct MazeRunner<'a, const X: usize, const Y: usize> {
-    maze: &'a Maze<X, Y>,
-    current_point: MapPoint<X, Y>,
-    last_point: MapPoint<X, Y>,
-    finished: bool,
-}
-
-impl<'a, const X: usize, const Y: usize> MazeRunner<'a, X, Y> {
-    fn new(maze: &'a Maze<X, Y>, start_point: MapPoint<X, Y>, last_point: MapPoint<X, Y>) -> Self {
-        MazeRunner {
-            maze,
-            current_point: start_point,
-            last_point,
-            finished: false,
+        for trail in graph[&junction].iter() {
+            let next_junction = trail[trail.len() - 1];
+            if visited.insert(next_junction) {
+                hike.0 += trail.len() - 1;
+                hike.1.push(trail);
+                self.extend_hike(graph, next_junction, hike, visited, longest_hike);
+                hike.1.pop();
+                hike.0 -= trail.len() - 1;
+                visited.remove(&next_junction);
+            }
         }
     }
+    // all points of longest hike
+    fn hiking_route(&self) -> HashSet<MapPoint> {
+        let graph = self.hiking_graph();
+        self.longest_hike(&graph)
+            .map(|(_, trails)| trails.concat().into_iter().collect())
+            .unwrap_or_default()
+    }
 }
 
-impl<'a, const X: usize, const Y: usize> Iterator for MazeRunner<'a, X, Y> {
-    type Item = (MapPoint<X, Y>, Vec<MapPoint<X, Y>>);
+/// A Long Walk: longest hike with slippery slopes (part 1) and with climbable slopes (part 2)
+pub struct Day23;
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.finished {
-            return None;
-        }
-        let result: Self::Item = (
-            self.current_point,
-            self.maze
-                .maze
-                .iter_neighbors(self.current_point)
-                .filter(|(p, o, _)| *p != self.last_point && self.maze.is_possible(p, o))
-                .map(|(p, ..)| p)
-                .collect(),
-        );
-        self.last_point = self.current_point;
+impl Solution for Day23 {
+    const DAY: u8 = 23;
+    const GEN_SIZE: (usize, &'static str) = (6, "number of junctions per row and column");
+    const SLOW_PARTS: &'static [u8] = &[2];
+    type Input<'a> = Maze;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-        if result.1.len() == 1 {
-            self.current_point = result.1[0];
-        } else {
-            self.finished = true;
-        }
-        Some(result)
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        Ok(Maze::try_from(input)?)
     }
-}
-
-pub fn day_23() -> Result<()> {
-    let input = include_str!("../../assets/day_23.txt");
-    let mut maze = Maze::<X, Y>::from(input);
-    let result_part1 = maze.go_hiking();
-    println!("result day 23 part 1: {}", result_part1);
-    maze.climbing_is_possible = true;
-    #[cfg(feature = "long-run-time")]
-    {
-        let result_part2 = maze.go_hiking();
-        println!("result day 23 part 2: {}", result_part2);
-        assert_eq!(result_part2, 6322);
+    fn part_1(maze: &Self::Input<'_>) -> Result<Self::Answer1> {
+        Ok(maze.go_hiking())
+    }
+    fn part_2(maze: &Self::Input<'_>) -> Result<Self::Answer2> {
+        let mut maze = maze.clone();
+        maze.climbing_is_possible = true;
+        Ok(maze.go_hiking())
+    }
+    // longest hike of part 1 on trails; slopes are light grey
+    fn render(maze: &Self::Input<'_>) -> Result<Option<Image>> {
+        let route = maze.hiking_route();
+        Ok(Some(Image::from_map(&maze.maze, |p, c| {
+            match (route.contains(&p), c) {
+                (true, _) => Rgb::RED,
+                (false, '#') => Rgb::GREEN,
+                (false, '.') => Rgb::WHITE,
+                (false, _) => Rgb::GREY.blend(Rgb::WHITE, 0.5),
+            }
+        })))
     }
-    #[cfg(feature = "short-run-time")]
-    {
-        println!("day 23 part 2 skipped because of long run time")
+    fn generate(rng: &mut InputRng, size: usize) -> Result<String> {
+        if size < 2 {
+            return Err(anyhow!("at least 2 x 2 junctions"));
+        }
+        // junctions are on a lattice with random distances between rows and columns. Trails between
+        // neighboring junctions start and end with slopes, which point east or south.
+        let lattice = |rng: &mut InputRng| -> Vec<usize> {
+            let mut position = 1;
+            (0..size)
+                .map(|index| {
+                    if index > 0 {
+                        position += rng.gen_range(4..=12);
+                    }
+                    position
+                })
+                .collect()
+        };
+        let (columns, rows) = (lattice(rng), lattice(rng));
+        let maze_size = MapSize::new(columns[size - 1] + 2, rows[size - 1] + 2);
+        let mut maze = MyMap2D::init(maze_size, '#');
+        // some trails are missing, but a random path from first to last junction is kept
+        let (mut keep_east, mut keep_south) = (HashSet::new(), HashSet::new());
+        let (mut column, mut row) = (0, 0);
+        while (column, row) != (size - 1, size - 1) {
+            if row == size - 1 || (column < size - 1 && rng.gen_bool(0.5)) {
+                keep_east.insert((column, row));
+                column += 1;
+            } else {
+                keep_south.insert((column, row));
+                row += 1;
+            }
+        }
+        for row in 0..size {
+            for column in 0..size {
+                let (x, y) = (columns[column], rows[row]);
+                maze.set(MapPoint::new(x, y), '.');
+                if column + 1 < size && (keep_east.contains(&(column, row)) || rng.gen_bool(0.8)) {
+                    let next_x = columns[column + 1];
+                    for trail_x in x + 2..next_x - 1 {
+                        maze.set(MapPoint::new(trail_x, y), '.');
+                    }
+                    maze.set(MapPoint::new(x + 1, y), '>');
+                    maze.set(MapPoint::new(next_x - 1, y), '>');
+                }
+                if row + 1 < size && (keep_south.contains(&(column, row)) || rng.gen_bool(0.8)) {
+                    let next_y = rows[row + 1];
+                    for trail_y in y + 2..next_y - 1 {
+                        maze.set(MapPoint::new(x, trail_y), '.');
+                    }
+                    maze.set(MapPoint::new(x, y + 1), 'v');
+                    maze.set(MapPoint::new(x, next_y - 1), 'v');
+                }
+            }
+        }
+        // start and end connect to first and last junction
+        maze.set(MapPoint::new(1, 0), '.');
+        maze.set(
+            MapPoint::new(maze_size.width - 2, maze_size.height - 1),
+            '.',
+        );
+        Ok(generate::grid(rng, maze_size, |_, p| *maze.get(p)))
     }
-    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
 
     use super::*;
-    const XT: usize = 23;
-    const YT: usize = 23;
 
     #[test]
     fn test_example_part1() -> Result<()> {
@@ -237,15 +319,52 @@ mod tests {
                            #.###.###.#.###.#.#v###\n\
                            #.....###...###...#...#\n\
                            #####################.#";
-        let mut maze = Maze::<XT, YT>::from(input);
+        let mut maze = Maze::try_from(input).unwrap();
         let result_part1 = maze.go_hiking();
-        println!("result day 23 example part 1: {}", result_part1);
         assert_eq!(result_part1, 94);
+        // route of 94 steps has 95 points
+        assert_eq!(maze.hiking_route().len(), 95);
+        let image = Day23::render(&maze)?.unwrap();
+        assert_eq!(image.get(maze.end_point), Rgb::RED);
         maze.climbing_is_possible = true;
         let result_part2 = maze.go_hiking();
-        println!("result day 23 example part 2: {}", result_part2);
         assert_eq!(result_part2, 154);
 
         Ok(())
     }
+
+    #[test]
+    fn test_missing_entry_or_exit() {
+        let err = Day23::parse("###\n#.#\n#.#").err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "day 00: expected path `.` as entry, found `#`"
+        );
+        assert!(Day23::parse(".").is_err());
+        let err = Day23::parse("#.#\n#.#\n###").err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "day 00: expected path `.` as exit, found `#`"
+        );
+        let maze = Day23::parse("#.#\n#.#\n#.#").unwrap();
+        assert_eq!(Day23::part_1(&maze).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_parallel_trails() {
+        // trails of 10 and 14 steps between junctions (1, 1) and (7, 5)
+        let input = "#.#######\n\
+                     #.......#\n\
+                     #.#####.#\n\
+                     #.#...#.#\n\
+                     #.#.#.#.#\n\
+                     #...#...#\n\
+                     #######.#";
+        let mut maze = Maze::try_from(input).unwrap();
+        let graph = maze.hiking_graph();
+        assert_eq!(graph[&MapPoint::new(1, 1)].len(), 2);
+        assert_eq!(maze.go_hiking(), 16);
+        maze.climbing_is_possible = true;
+        assert_eq!(maze.go_hiking(), 16);
+    }
 }
@@ -1,12 +1,21 @@
 //!day_24.rs
 
+use crate::generate::InputRng;
+use crate::parse::{self, ParseError};
+use crate::solution::Solution;
 use anyhow::{anyhow, Result};
+use rand::Rng;
 
 // for part 2 I use an equation solver
 use eqsolver::multivariable::MultiVarNewton;
 use nalgebra::{Matrix6, Vector6};
+use std::collections::HashSet;
 
-struct HailStone {
+/// number of triples of hailstones, with which a rock is searched, before search fails
+const MAX_TRIPLES: usize = 200;
+
+/// position and velocity of a hailstone
+pub struct HailStone {
     x: f64,
     y: f64,
     z: f64,
@@ -15,30 +24,36 @@ struct HailStone {
     vz: f64,
 }
 
-impl From<&str> for HailStone {
-    fn from(value: &str) -> Self {
-        let (point_str, velocity_str) = value.split_once('@').unwrap();
-        let mut point_iter = point_str
-            .split(',')
-            .map(|s| s.trim().parse::<f64>().expect("bad point input"));
-        let mut velocity_iter = velocity_str
-            .split(',')
-            .map(|s| s.trim().parse::<f64>().expect("bad point input"));
-        let result = Self {
-            x: point_iter.next().expect("not enough point input"),
-            y: point_iter.next().expect("not enough point input"),
-            z: point_iter.next().expect("not enough point input"),
-            vx: velocity_iter.next().expect("not enough velocity input"),
-            vy: velocity_iter.next().expect("not enough velocity input"),
-            vz: velocity_iter.next().expect("not enough velocity input"),
+impl TryFrom<&str> for HailStone {
+    type Error = ParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let (point_str, velocity_str) = parse::split_once(value, "@")?;
+        let mut point_iter = point_str.split(',');
+        let mut velocity_iter = velocity_str.split(',');
+        let mut point = |expected| {
+            parse::number::<f64>(parse::next_token(&mut point_iter, point_str, expected)?)
         };
-        if point_iter.next().is_some() {
-            panic!("too much point input");
-        }
-        if velocity_iter.next().is_some() {
-            panic!("too much velocity input");
-        }
-        result
+        let (x, y, z) = (point("x")?, point("y")?, point("z")?);
+        let mut velocity = |expected| {
+            let token = parse::next_token(&mut velocity_iter, velocity_str, expected)?;
+            match parse::number::<f64>(token)? {
+                // hail stones must move in all directions
+                v if v.abs() > f64::EPSILON => Ok(v),
+                _ => Err(ParseError::new(token.trim(), "non-zero velocity")),
+            }
+        };
+        let (vx, vy, vz) = (velocity("vx")?, velocity("vy")?, velocity("vz")?);
+        parse::no_more_tokens(&mut point_iter, "`@`")?;
+        parse::no_more_tokens(&mut velocity_iter, "end of line")?;
+        Ok(Self {
+            x,
+            y,
+            z,
+            vx,
+            vy,
+            vz,
+        })
     }
 }
 
@@ -46,6 +61,28 @@ impl HailStone {
     fn as_tuple(&self) -> (f64, f64, f64, f64, f64, f64) {
         (self.x, self.y, self.z, self.vx, self.vy, self.vz)
     }
+    fn is_hit_by(&self, rock: &Vector6<f64>) -> bool {
+        // rock hits hailstone, if relative position and relative velocity are parallel and
+        // hailstone moves towards rock. Check with integers, since all inputs are integers.
+        let rock: Vec<i128> = rock.iter().map(|v| v.round() as i128).collect();
+        let dp = [
+            self.x as i128 - rock[0],
+            self.y as i128 - rock[1],
+            self.z as i128 - rock[2],
+        ];
+        let dv = [
+            self.vx as i128 - rock[3],
+            self.vy as i128 - rock[4],
+            self.vz as i128 - rock[5],
+        ];
+        let cross = [
+            dp[1] * dv[2] - dp[2] * dv[1],
+            dp[2] * dv[0] - dp[0] * dv[2],
+            dp[0] * dv[1] - dp[1] * dv[0],
+        ];
+        let dot: i128 = dp.iter().zip(dv.iter()).map(|(p, v)| p * v).sum();
+        cross == [0, 0, 0] && (dot < 0 || dp == [0, 0, 0])
+    }
     fn calc_position_on_path(&self, factor: f64) -> (f64, f64, f64) {
         (
             self.x + self.vx * factor,
@@ -123,6 +160,35 @@ impl HailStone {
 }
 
 fn solve_task_2(hailstones: &[HailStone]) -> Result<u64> {
+    if hailstones.len() < 3 {
+        return Err(anyhow!("at least three hailstones required"));
+    }
+    // Newton may converge to a rock, which only fits the three selected hailstones. Therefore
+    // start with hailstones 0, 3, 6 (which worked for my input) and check each solution against all hailstones.
+    // Each failing triple costs up to 1_000 Newton iterations, therefore search is bound to the
+    // first triples of hailstones.
+    let candidates = [[0, 3, 6]]
+        .into_iter()
+        .filter(|c| c[2] < hailstones.len())
+        .chain(
+            (2..hailstones.len())
+                .flat_map(|h3| (1..h3).flat_map(move |h2| (0..h2).map(move |h1| [h1, h2, h3]))),
+        )
+        .take(MAX_TRIPLES);
+    for [h1, h2, h3] in candidates {
+        if let Ok(rock) = solve_rock(&hailstones[h1], &hailstones[h2], &hailstones[h3]) {
+            if hailstones.iter().all(|h| h.is_hit_by(&rock)) {
+                return Ok((rock[0] + rock[1] + rock[2]).round() as u64);
+            }
+        }
+    }
+    Err(anyhow!(
+        "no rock found with first {} triples of hailstones, which hits all hailstones",
+        MAX_TRIPLES
+    ))
+}
+
+fn solve_rock(h1: &HailStone, h2: &HailStone, h3: &HailStone) -> Result<Vector6<f64>> {
     // part 2: a rock thrown from postion (xs, ys, zs) with velocity (vxs, vys, vzs) has to hit
     // all hailstones. This is obviously over-determined. The question is, how many hailstones do you need to solve it?
     // The rock has 6 unknown variables. To solve this this, we try with one hailstone.
@@ -180,10 +246,9 @@ fn solve_task_2(hailstones: &[HailStone]) -> Result<u64> {
     // 0        , vz2 - vzs, vys - vy2, 0      , zs - z2, y2 - ys
     // 0        , vz3 - vzs, vys - vy3, 0      , zs - z3, y3 - ys
 
-    // I'm not sure why it worked with these hailstone (before I tried with 0, 1, 2), but now I have the correct result :)
-    let (x1, y1, z1, vx1, vy1, vz1) = hailstones[0].as_tuple();
-    let (x2, y2, z2, vx2, vy2, vz2) = hailstones[3].as_tuple();
-    let (x3, y3, z3, vx3, vy3, vz3) = hailstones[6].as_tuple();
+    let (x1, y1, z1, vx1, vy1, vz1) = h1.as_tuple();
+    let (x2, y2, z2, vx2, vy2, vz2) = h2.as_tuple();
+    let (x3, y3, z3, vx3, vy3, vz3) = h3.as_tuple();
 
     // Vector6: 0: xs, 1: ys, 2: zs, 3: vxs, 4: vys, 5: vzs
     let functions = |v: Vector6<f64>| {
@@ -260,48 +325,90 @@ fn solve_task_2(hailstones: &[HailStone]) -> Result<u64> {
 
     let x0 = Vector6::new(x_start, y_start, z_start, vx_start, vy_start, vz_start);
 
-    let solution: Vector6<f64> = match MultiVarNewton::new(functions, jacobi)
-        .with_tol(1e-6)
-        .with_itermax(500000)
+    // positions of about 1e14 limit precision of f64 to a few hundredths; since rock is rounded to
+    // integers and checked against all hailstones, a tolerance below 1 is sufficient
+    match MultiVarNewton::new(functions, jacobi)
+        .with_tol(0.1)
+        .with_itermax(1_000)
         .solve(x0)
     {
-        Ok(sol) => sol,
-        Err(err) => return Err(anyhow!("err solution: {:?}", err)),
-    };
-
-    //println!("{:?}", solution);
-
-    let solution = solution[0] + solution[1] + solution[2];
-    Ok(solution as u64)
+        Ok(sol) => Ok(sol),
+        Err(err) => Err(anyhow!("err solution: {:?}", err)),
+    }
 }
 
-pub fn day_24() -> Result<()> {
-    let input = include_str!("../../assets/day_24.txt");
-    let hailstones: Vec<HailStone> = input.lines().map(HailStone::from).collect();
-    assert!(hailstones.iter().all(|h| h.vx.abs() > f64::EPSILON
-        && h.vy.abs() > f64::EPSILON
-        && h.vz.abs() > f64::EPSILON));
-
-    let min = 200_000_000_000_000.0;
-    let max = 400_000_000_000_000.0;
-
-    let mut result_part1 = 0;
-    for (i, hailstone_1) in hailstones.iter().enumerate() {
-        for hailstone_2 in hailstones.iter().skip(i + 1) {
-            if hailstone_1.check_intersection_in_x_y_boundaries(hailstone_2, min, max) {
-                result_part1 += 1;
-            }
+/// hailstones, which are all hit by a rock thrown from an integer position with integer velocity.
+/// Returns hailstones and sum of coordinates of rock position.
+fn generate_hailstones(rng: &mut InputRng, size: usize) -> Result<(String, u64)> {
+    if size < 3 {
+        return Err(anyhow!("at least three hailstones required"));
+    }
+    let rock: [i64; 3] = [0; 3].map(|_| rng.gen_range(200_000_000_000_000..400_000_000_000_000));
+    let rock_velocity: [i64; 3] = [0; 3].map(|_| rng.gen_range(-300..=300));
+    let mut times = HashSet::with_capacity(size);
+    let mut input = String::new();
+    while times.len() < size {
+        let time: i64 = rng.gen_range(10_000_000_000..1_000_000_000_000);
+        if !times.insert(time) {
+            continue;
         }
+        // hailstones move in all directions and differ from rock in all directions
+        let velocity = rock_velocity.map(|rock_v| loop {
+            let v = rng.gen_range(-500..=500);
+            if v != 0 && v != rock_v {
+                break v;
+            }
+        });
+        // hailstone is at position of rock at time
+        let position: Vec<i64> = (0..3)
+            .map(|i| rock[i] + (rock_velocity[i] - velocity[i]) * time)
+            .collect();
+        input.push_str(&format!(
+            "{}, {}, {} @ {}, {}, {}\n",
+            position[0], position[1], position[2], velocity[0], velocity[1], velocity[2]
+        ));
     }
-    eprintln!("result day 24 part 1: {}", result_part1);
-    assert_eq!(result_part1, 17_776);
+    Ok((input, rock.iter().sum::<i64>() as u64))
+}
 
-    // task 2
-    let result_part2 = solve_task_2(&hailstones)?;
-    eprintln!("result day 24 part 2: {}", result_part2);
-    assert_eq!(result_part2, 948_978_092_202_212);
+/// Never Tell Me The Odds: crossing paths of hailstones in test area (part 1) and sum of start
+/// coordinates of rock, which hits all hailstones (part 2)
+pub struct Day24;
+
+impl Solution for Day24 {
+    const DAY: u8 = 24;
+    const GEN_SIZE: (usize, &'static str) = (300, "number of hailstones");
+    type Input<'a> = Vec<HailStone>;
+    type Answer1 = usize;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {
+        let hailstones: Vec<HailStone> = input
+            .lines()
+            .map(HailStone::try_from)
+            .collect::<Result<_, _>>()?;
+        Ok(hailstones)
+    }
+    fn part_1(hailstones: &Self::Input<'_>) -> Result<Self::Answer1> {
+        let min = 200_000_000_000_000.0;
+        let max = 400_000_000_000_000.0;
 
-    Ok(())
+        let mut result_part1 = 0;
+        for (i, hailstone_1) in hailstones.iter().enumerate() {
+            for hailstone_2 in hailstones.iter().skip(i + 1) {
+                if hailstone_1.check_intersection_in_x_y_boundaries(hailstone_2, min, max) {
+                    result_part1 += 1;
+                }
+            }
+        }
+        Ok(result_part1)
+    }
+    fn part_2(hailstones: &Self::Input<'_>) -> Result<Self::Answer2> {
+        solve_task_2(hailstones)
+    }
+    fn generate(rng: &mut InputRng, size: usize) -> Result<String> {
+        Ok(generate_hailstones(rng, size)?.0)
+    }
 }
 
 #[cfg(test)]
@@ -309,14 +416,19 @@ mod tests {
 
     use super::*;
 
+    const TEST_INPUT: &str = "19, 13, 30 @ -2,  1, -2\n\
+                              18, 19, 22 @ -1, -1, -2\n\
+                              20, 25, 34 @ -2, -2, -4\n\
+                              12, 31, 28 @ -1, -2, -1\n\
+                              20, 19, 15 @  1, -5, -3";
+
     #[test]
     fn test_example_part_1() -> Result<()> {
-        let input = "19, 13, 30 @ -2,  1, -2\n\
-                           18, 19, 22 @ -1, -1, -2\n\
-                           20, 25, 34 @ -2, -2, -4\n\
-                           12, 31, 28 @ -1, -2, -1\n\
-                           20, 19, 15 @  1, -5, -3";
-        let hailstones: Vec<HailStone> = input.lines().map(HailStone::from).collect();
+        let input = TEST_INPUT;
+        let hailstones: Vec<HailStone> = input
+            .lines()
+            .map(|l| HailStone::try_from(l).unwrap())
+            .collect();
         assert!(hailstones.iter().all(|h| h.vx.abs() > f64::EPSILON
             && h.vy.abs() > f64::EPSILON
             && h.vz.abs() > f64::EPSILON));
@@ -332,16 +444,35 @@ mod tests {
                 }
             }
         }
-        eprintln!("result day 24 example part 1: {}", result_part1);
         assert_eq!(result_part1, 2);
 
         Ok(())
     }
 
+    #[test]
+    fn test_example_part_2() -> Result<()> {
+        let hailstones = Day24::parse(TEST_INPUT)?;
+        assert_eq!(Day24::part_2(&hailstones)?, 47);
+        Ok(())
+    }
+
+    #[test]
+    fn test_inconsistent_hailstones() -> Result<()> {
+        let (mut input, _) = generate_hailstones(&mut crate::generate::input_rng(0), 300)?;
+        input.push_str("0, 0, 0 @ 1, 1, 1\n");
+        let hailstones = Day24::parse(&input)?;
+        // search gives up after MAX_TRIPLES triples
+        assert!(Day24::part_2(&hailstones).is_err());
+        Ok(())
+    }
+
     #[test]
     fn test_matrix_inversion() {
-        let input = include_str!("../../assets/day_24.txt");
-        let hailstones: Vec<HailStone> = input.lines().map(HailStone::from).collect();
+        let input = TEST_INPUT;
+        let hailstones: Vec<HailStone> = input
+            .lines()
+            .map(|l| HailStone::try_from(l).unwrap())
+            .collect();
 
         let (x1, y1, z1, vx1, vy1, vz1) = hailstones[0].as_tuple();
         let (x2, y2, z2, vx2, vy2, vz2) = hailstones[1].as_tuple();
@@ -404,8 +535,16 @@ mod tests {
 
         let x0 = Vector6::new(x_start, y_start, z_start, vx_start, vy_start, vz_start);
 
-        eprintln!("{:?}", jacobi(x0.clone()));
-        eprintln!("{:?}", jacobi(x0.clone()).try_inverse());
         assert!(jacobi(x0).try_inverse().is_some());
     }
+
+    #[test]
+    fn test_generated_hailstones() -> Result<()> {
+        for seed in 0..5 {
+            let (input, rock) = generate_hailstones(&mut crate::generate::input_rng(seed), 20)?;
+            let hailstones = Day24::parse(&input)?;
+            assert_eq!(Day24::part_2(&hailstones)?, rock);
+        }
+        Ok(())
+    }
 }
@@ -1,32 +1,38 @@
 //!day_25.rs
 
+use crate::generate::{self, InputRng};
+use crate::parse::{self, ParseError};
+use crate::solution::Solution;
 use anyhow::anyhow;
 use anyhow::Result as AnyResult;
+use rand::{seq::SliceRandom, Rng};
 use rustworkx_core::connectivity::stoer_wagner_min_cut;
 use rustworkx_core::petgraph::graph::{NodeIndex, UnGraph};
 use rustworkx_core::Result;
+use std::collections::{BTreeMap, BTreeSet};
+use std::convert::Infallible;
 
-struct WiringDiagramm<'a> {
+/// components and wires between them
+pub struct WiringDiagramm<'a> {
     graph: UnGraph<&'a str, u32>,
 }
 
-impl<'a> From<&'a str> for WiringDiagramm<'a> {
-    fn from(value: &'a str) -> Self {
+impl<'a> TryFrom<&'a str> for WiringDiagramm<'a> {
+    type Error = ParseError;
+
+    fn try_from(value: &'a str) -> std::result::Result<Self, Self::Error> {
         let mut wd = Self {
             graph: UnGraph::new_undirected(),
         };
         for line in value.lines() {
-            let (current_node, nodes_to_link) = line
-                .split_once(':')
-                .map(|(c, n)| (c.trim(), n.split_whitespace()))
-                .unwrap();
-            let current_node_index = wd.check_add_node(current_node);
-            for link_node in nodes_to_link {
+            let (current_node, nodes_to_link) = parse::split_once(line, ":")?;
+            let current_node_index = wd.check_add_node(current_node.trim());
+            for link_node in nodes_to_link.split_whitespace() {
                 let link_node_index = wd.check_add_node(link_node);
                 wd.graph.add_edge(current_node_index, link_node_index, 1);
             }
         }
-        wd
+        Ok(wd)
     }
 }
 
@@ -49,7 +55,12 @@ impl<'a> WiringDiagramm<'a> {
             Err(_) => return Err(anyhow!("min cut result in error")),
         };
 
-        assert_eq!(min_cut, 3);
+        if min_cut != 3 {
+            return Err(anyhow!(
+                "groups of components are connected by {} wires instead of 3",
+                min_cut
+            ));
+        }
         let num_half1 = partition.len();
         let num_half2 = self.graph.node_count() - num_half1;
 
@@ -57,13 +68,85 @@ impl<'a> WiringDiagramm<'a> {
     }
 }
 
-pub fn day_25() -> AnyResult<()> {
-    let input = include_str!("../../assets/day_25.txt");
-    let wd = WiringDiagramm::from(input);
-    let result_part1 = wd.calc_min_set()?;
-    eprintln!("result day 24 part 1: {}", result_part1);
-    assert_eq!(result_part1, 562_978);
-    Ok(())
+/// wiring of two groups of components, which are connected by three wires.
+/// Returns wiring diagramm and product of sizes of both groups.
+fn generate_wiring(rng: &mut InputRng, size: usize) -> AnyResult<(String, usize)> {
+    if size < 10 {
+        return Err(anyhow!("at least 10 components"));
+    }
+    let names = generate::names(rng, size, 3, b"abcdefghijklmnopqrstuvwxyz", |_| true)?;
+    let group_size = rng
+        .gen_range(size * 2 / 5..=size * 3 / 5)
+        .clamp(5, size - 5);
+    let mut wires: BTreeSet<(usize, usize)> = BTreeSet::new();
+    for group in [0..group_size, group_size..size] {
+        // two random cycles through all components of group without common wires; therefore
+        // at least four wires connect any two parts of a group
+        let mut components: Vec<usize> = group.collect();
+        let mut cycles = 0;
+        while cycles < 2 {
+            components.shuffle(rng);
+            let cycle: Vec<(usize, usize)> = (0..components.len())
+                .map(|i| {
+                    let (a, b) = (components[i], components[(i + 1) % components.len()]);
+                    (a.min(b), a.max(b))
+                })
+                .collect();
+            if cycle.iter().all(|wire| !wires.contains(wire)) {
+                wires.extend(cycle);
+                cycles += 1;
+            }
+        }
+    }
+    let mut cut_wires = BTreeSet::new();
+    while cut_wires.len() < 3 {
+        cut_wires.insert((
+            rng.gen_range(0..group_size),
+            rng.gen_range(group_size..size),
+        ));
+    }
+    wires.extend(cut_wires);
+    // each wire is listed by one of its components
+    let mut connections: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for (a, b) in wires {
+        let (a, b) = if rng.gen_bool(0.5) { (a, b) } else { (b, a) };
+        connections.entry(a).or_default().push(b);
+    }
+    let mut lines: Vec<String> = connections
+        .iter()
+        .map(|(component, connected)| {
+            let connected: Vec<&str> = connected.iter().map(|c| names[*c].as_str()).collect();
+            format!("{}: {}", names[*component], connected.join(" "))
+        })
+        .collect();
+    lines.shuffle(rng);
+    Ok((lines.join("\n") + "\n", group_size * (size - group_size)))
+}
+
+/// Snowverload: product of sizes of both groups after cutting three wires (only part)
+pub struct Day25;
+
+impl Solution for Day25 {
+    const DAY: u8 = 25;
+    // day 25 has only one part
+    const PARTS: u8 = 1;
+    const GEN_SIZE: (usize, &'static str) = (1_500, "number of components");
+    type Input<'a> = WiringDiagramm<'a>;
+    type Answer1 = usize;
+    type Answer2 = Infallible;
+
+    fn parse(input: &str) -> AnyResult<Self::Input<'_>> {
+        Ok(WiringDiagramm::try_from(input)?)
+    }
+    fn part_1(wd: &Self::Input<'_>) -> AnyResult<Self::Answer1> {
+        wd.calc_min_set()
+    }
+    fn part_2(_wd: &Self::Input<'_>) -> AnyResult<Self::Answer2> {
+        Err(anyhow!("day 25 has only one part"))
+    }
+    fn generate(rng: &mut InputRng, size: usize) -> AnyResult<String> {
+        Ok(generate_wiring(rng, size)?.0)
+    }
 }
 
 #[cfg(test)]
@@ -86,9 +169,25 @@ mod tests {
                            lsr: lhk\n\
                            rzs: qnr cmg lsr rsh\n\
                            frs: qnr lhk lsr";
-        let wd = WiringDiagramm::from(input);
+        let wd = WiringDiagramm::try_from(input).unwrap();
         let result_part1 = wd.calc_min_set().unwrap();
         assert_eq!(result_part1, 54);
         Ok(())
     }
+
+    #[test]
+    fn test_min_cut_not_three() {
+        let wd = WiringDiagramm::try_from("a: b c\nb: c d\nc: d").unwrap();
+        assert!(wd.calc_min_set().is_err());
+    }
+
+    #[test]
+    fn test_generated_wiring() -> AnyResult<()> {
+        for seed in 0..3 {
+            let (input, groups) = generate_wiring(&mut generate::input_rng(seed), 60)?;
+            let wiring = Day25::parse(&input)?;
+            assert_eq!(Day25::part_1(&wiring)?, groups);
+        }
+        Ok(())
+    }
 }
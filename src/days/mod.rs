@@ -26,3 +26,120 @@ pub mod day_22;
 pub mod day_23;
 pub mod day_24;
 pub mod day_25;
+
+pub use crate::solution::Solution;
+use crate::solution::Solver;
+
+/// registry of all days in order of advent calendar
+pub static DAYS: [&dyn Solver; 25] = [
+    &day_01::Day01,
+    &day_02::Day02,
+    &day_03::Day03,
+    &day_04::Day04,
+    &day_05::Day05,
+    &day_06::Day06,
+    &day_07::Day07,
+    &day_08::Day08,
+    &day_09::Day09,
+    &day_10::Day10,
+    &day_11::Day11,
+    &day_12::Day12,
+    &day_13::Day13,
+    &day_14::Day14,
+    &day_15::Day15,
+    &day_16::Day16,
+    &day_17::Day17,
+    &day_18::Day18,
+    &day_19::Day19,
+    &day_20::Day20,
+    &day_21::Day21,
+    &day_22::Day22,
+    &day_23::Day23,
+    &day_24::Day24,
+    &day_25::Day25,
+];
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::answers::{answers_path, fingerprint, Answers, Verification};
+    use crate::input::{input_dir, load_input};
+    use crate::parse::ParseError;
+    use crate::solution::{Parts, SOLVER_STACK_SIZE};
+
+    #[test]
+    fn test_registry() {
+        for (index, day) in DAYS.iter().enumerate() {
+            assert_eq!(day.day() as usize, index + 1);
+        }
+        assert_eq!(DAYS[24].parts(), 1);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let cases = [
+            (
+                5,
+                "seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n52 x50 48\n",
+                "day 05, line 5, column 4: expected number, found `x50`",
+            ),
+            (
+                7,
+                "32T3K 765\nT55X5 684\n",
+                "day 07, line 2, column 4: expected card, found `X`",
+            ),
+            (
+                16,
+                ".|.\n.?.\n",
+                "day 16, line 2, column 2: expected `.`, `/`, `\\`, `|` or `-`, found `?`",
+            ),
+            (
+                22,
+                "1,0,1~1,2,1\n0,0,2~2,0\n",
+                "day 22, line 2, column 10: expected z, found end of line",
+            ),
+            (
+                24,
+                "19, 13, 30 @ -2,  1, -2\n18, 19, 22 @ -1, -1\n",
+                "day 24, line 2, column 20: expected vz, found end of line",
+            ),
+        ];
+        for (day, input, error) in cases {
+            let day = DAYS[day - 1];
+            let solved = std::thread::Builder::new()
+                .stack_size(SOLVER_STACK_SIZE)
+                .spawn(move || day.solve(input, &[1]))
+                .unwrap()
+                .join()
+                .unwrap();
+            let answer_error = match solved.answers {
+                Err(err) => err,
+                Ok(mut answers) => answers.remove(0).value.unwrap_err(),
+            };
+            let parse_error = answer_error.downcast_ref::<ParseError>().unwrap();
+            assert_eq!(parse_error.day, day.day());
+            assert_eq!(parse_error.to_string(), error);
+        }
+    }
+
+    #[test]
+    #[ignore = "solves all days; run with `cargo test --release -- --ignored`"]
+    fn test_all_days() {
+        let answers = Answers::load(&answers_path(&input_dir())).unwrap();
+        for day in DAYS.iter() {
+            let (parts, _) = day.select_parts(Parts::Both, false);
+            let input = load_input(day.day(), &input_dir(), None).unwrap();
+            let fingerprint = fingerprint(&input);
+            for answer in day.solve(&input, &parts).answers.unwrap() {
+                assert_eq!(
+                    answers.verify(day.day(), answer.part, &fingerprint, &answer.value.unwrap()),
+                    Verification::Match,
+                    "day {:02} part {}",
+                    day.day(),
+                    answer.part
+                );
+            }
+        }
+    }
+}
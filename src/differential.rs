@@ -0,0 +1,89 @@
+//!differential.rs
+
+use crate::generate::{input_rng, InputRng};
+use anyhow::Result;
+use std::fmt::Display;
+use std::ops::Range;
+
+/// generated input, for which solver and naive reference solver disagree
+#[derive(Debug)]
+pub struct Divergence {
+    pub seed: u64,
+    pub input: String,
+    pub solver: String,
+    pub reference: String,
+}
+
+impl Display for Divergence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "seed {}: solver answered `{}`, reference answered `{}`",
+            self.seed, self.solver, self.reference
+        )?;
+        write!(f, "input:\n{}", self.input)
+    }
+}
+
+/// answer or error message; an error of only one side is a divergence, too
+fn answer<T: Display>(result: Result<T>) -> String {
+    match result {
+        Ok(answer) => answer.to_string(),
+        Err(err) => format!("error: {:#}", err),
+    }
+}
+
+/// generates an input for each seed and compares answers of solver and reference.
+/// Returns first seed, for which answers differ.
+pub fn first_divergence<T: Display>(
+    seeds: Range<u64>,
+    generate: impl Fn(&mut InputRng) -> Result<String>,
+    solver: impl Fn(&str) -> Result<T>,
+    reference: impl Fn(&str) -> Result<T>,
+) -> Result<Option<Divergence>> {
+    for seed in seeds {
+        let input = generate(&mut input_rng(seed))?;
+        let (solver, reference) = (answer(solver(&input)), answer(reference(&input)));
+        if solver != reference {
+            return Ok(Some(Divergence {
+                seed,
+                input,
+                solver,
+                reference,
+            }));
+        }
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use rand::Rng;
+
+    #[test]
+    fn test_first_divergence() -> Result<()> {
+        let generate = |rng: &mut InputRng| Ok(rng.gen_range(0..10_u8).to_string());
+        let solver = |input: &str| Ok(input.parse::<u8>()?);
+        // reference disagrees for numbers from 5 on
+        let reference = |input: &str| {
+            let number = input.parse::<u8>()?;
+            Ok(if number < 5 { number } else { number + 1 })
+        };
+        let first_seed = (0..)
+            .find(|seed| generate(&mut input_rng(*seed)).unwrap() >= "5".into())
+            .unwrap();
+        let divergence = first_divergence(0..100, generate, solver, reference)?.unwrap();
+        assert_eq!(divergence.seed, first_seed);
+        assert_eq!(
+            divergence.reference,
+            (divergence.input.parse::<u8>()? + 1).to_string()
+        );
+        assert!(first_divergence(0..100, generate, solver, solver)?.is_none());
+        // error of solver is reported as answer
+        let divergence = first_divergence(0..1, generate, |_| Err(anyhow::anyhow!("no")), solver)?;
+        assert_eq!(divergence.unwrap().solver, "error: no");
+        Ok(())
+    }
+}
@@ -0,0 +1,249 @@
+//!generate.rs
+
+use crate::cli::GenArgs;
+use crate::my_lib::my_map_point::{MapPoint, MapSize};
+use crate::my_lib::my_map_two_dim::MyMap2D;
+use crate::years;
+use anyhow::{anyhow, Context, Result};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::collections::{HashMap, HashSet};
+
+/// random generator of puzzle inputs; seeded generators produce the same input on every platform
+pub type InputRng = ChaCha8Rng;
+
+pub fn input_rng(seed: u64) -> InputRng {
+    InputRng::seed_from_u64(seed)
+}
+
+pub fn run_generate(args: &GenArgs) -> Result<()> {
+    let day = years::day_of_year(args.year, args.day)?;
+    let (default_size, size_info) = day.gen_size();
+    let size = args.size.unwrap_or(default_size);
+    let seed = args.seed.unwrap_or_else(rand::random);
+    let input = day.generate(&mut input_rng(seed), size)?;
+    // stdout may be puzzle input, therefore replay info goes to stderr
+    eprintln!(
+        "day {:02}: seed {}, size {} ({})",
+        args.day, seed, size, size_info
+    );
+    match args.output.as_ref() {
+        Some(path) => std::fs::write(path, input)
+            .with_context(|| format!("cannot write input `{}`", path.display())),
+        None => {
+            print!("{}", input);
+            Ok(())
+        }
+    }
+}
+
+/// grid of given size with one line per row; cell returns char of each cell
+pub fn grid(
+    rng: &mut InputRng,
+    size: MapSize,
+    mut cell: impl FnMut(&mut InputRng, MapPoint) -> char,
+) -> String {
+    let mut grid = String::with_capacity((size.width + 1) * size.height);
+    for y in 0..size.height {
+        for x in 0..size.width {
+            grid.push(cell(rng, MapPoint::new(x, y)));
+        }
+        grid.push('\n');
+    }
+    grid
+}
+
+/// count unique names of len chars of alphabet, which are accepted by valid
+pub fn names(
+    rng: &mut InputRng,
+    count: usize,
+    len: usize,
+    alphabet: &[u8],
+    valid: impl Fn(&str) -> bool,
+) -> Result<Vec<String>> {
+    let mut seen: HashSet<String> = HashSet::with_capacity(count);
+    let mut names = Vec::with_capacity(count);
+    let mut attempts = 0;
+    while names.len() < count {
+        attempts += 1;
+        if attempts > 100 * count + 1_000 {
+            return Err(anyhow!(
+                "cannot find {} unique names of length {}",
+                count,
+                len
+            ));
+        }
+        let name: String = (0..len)
+            .map(|_| alphabet[rng.gen_range(0..alphabet.len())] as char)
+            .collect();
+        if valid(&name) && seen.insert(name.clone()) {
+            names.push(name);
+        }
+    }
+    Ok(names)
+}
+
+/// random region of up to cells squares of a lattice of given size. The region is grown square by
+/// square, only adding squares which neither close a hole nor touch the region at a corner only.
+/// Therefore the boundary of the region is a simple loop.
+pub fn simple_region(rng: &mut InputRng, size: MapSize, cells: usize) -> MyMap2D<bool> {
+    let mut region = MyMap2D::init(size, false);
+    let start = MapPoint::new(rng.gen_range(0..size.width), rng.gen_range(0..size.height));
+    region.set(start, true);
+    let mut squares = vec![start];
+    let mut attempts = 0;
+    while squares.len() < cells.min(size.len()) && attempts < 100 * cells {
+        attempts += 1;
+        let square = squares[rng.gen_range(0..squares.len())];
+        let (dx, dy) = [(0, -1), (1, 0), (0, 1), (-1, 0)][rng.gen_range(0..4)];
+        let Some(candidate) = offset(square, dx, dy, size) else {
+            continue;
+        };
+        if *region.get(candidate) {
+            continue;
+        }
+        // region squares around candidate in clockwise order must form a single run,
+        // which contains a side neighbor
+        let ring: Vec<bool> = [
+            (0, -1),
+            (1, -1),
+            (1, 0),
+            (1, 1),
+            (0, 1),
+            (-1, 1),
+            (-1, 0),
+            (-1, -1),
+        ]
+        .iter()
+        .map(|(dx, dy)| offset(candidate, *dx, *dy, size).is_some_and(|p| *region.get(p)))
+        .collect();
+        let runs = (0..8).filter(|i| ring[*i] && !ring[(i + 7) % 8]).count();
+        let side_neighbor = (0..8).step_by(2).any(|i| ring[i]);
+        if runs == 1 && side_neighbor {
+            region.set(candidate, true);
+            squares.push(candidate);
+        }
+    }
+    region
+}
+
+fn offset(point: MapPoint, dx: isize, dy: isize, size: MapSize) -> Option<MapPoint> {
+    let point = MapPoint::new(
+        point.x().checked_add_signed(dx)?,
+        point.y().checked_add_signed(dy)?,
+    );
+    size.contains(point).then_some(point)
+}
+
+/// corners of squares along boundary of region in order of loop, starting at top left corner of
+/// first square of region. Corner (x, y) is top left corner of square (x, y).
+pub fn region_boundary(region: &MyMap2D<bool>) -> Vec<MapPoint> {
+    let inside = |x: usize, y: usize, dx: isize, dy: isize| {
+        offset(MapPoint::new(x, y), dx, dy, region.size()).is_some_and(|p| *region.get(p))
+    };
+    let mut corners: HashMap<MapPoint, Vec<MapPoint>> = HashMap::new();
+    let mut add_side = |a: (usize, usize), b: (usize, usize)| {
+        let (a, b) = (MapPoint::new(a.0, a.1), MapPoint::new(b.0, b.1));
+        corners.entry(a).or_default().push(b);
+        corners.entry(b).or_default().push(a);
+    };
+    let mut start = None;
+    for (square, _) in region.iter().filter(|(_, r)| **r) {
+        let (x, y) = (square.x(), square.y());
+        start.get_or_insert(MapPoint::new(x, y));
+        if !inside(x, y, 0, -1) {
+            add_side((x, y), (x + 1, y));
+        }
+        if !inside(x, y, 1, 0) {
+            add_side((x + 1, y), (x + 1, y + 1));
+        }
+        if !inside(x, y, 0, 1) {
+            add_side((x, y + 1), (x + 1, y + 1));
+        }
+        if !inside(x, y, -1, 0) {
+            add_side((x, y), (x, y + 1));
+        }
+    }
+    let Some(start) = start else {
+        return Vec::new();
+    };
+    let mut boundary = vec![start];
+    let (mut previous, mut current) = (start, corners[&start][0]);
+    while current != start {
+        boundary.push(current);
+        let next = corners[&current]
+            .iter()
+            .copied()
+            .find(|c| *c != previous)
+            .expect("boundary of region is a simple loop");
+        (previous, current) = (current, next);
+    }
+    debug_assert_eq!(
+        2 * boundary.len(),
+        corners.values().map(|c| c.len()).sum::<usize>()
+    );
+    boundary
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::cli::{Cli, Command};
+    use crate::days::DAYS;
+    use clap::Parser;
+
+    #[test]
+    fn test_region_boundary() {
+        let mut rng = input_rng(23);
+        for _ in 0..20 {
+            let region = simple_region(&mut rng, MapSize::new(12, 9), 50);
+            let boundary = region_boundary(&region);
+            // each corner is visited once and consecutive corners are neighbors
+            let unique: HashSet<MapPoint> = boundary.iter().copied().collect();
+            assert_eq!(unique.len(), boundary.len());
+            for (i, corner) in boundary.iter().enumerate() {
+                assert_eq!(corner.distance(boundary[(i + 1) % boundary.len()]), 1);
+            }
+        }
+    }
+
+    #[test]
+    fn test_generated_inputs() {
+        // small sizes, which all days solve quickly
+        let sizes = [
+            20, 10, 20, 20, 5, 3, 50, 3, 20, 15, 20, 20, 5, 10, 100, 15, 12, 10, 20, 2, 131, 50, 3,
+            10, 30,
+        ];
+        for (day, size) in DAYS.iter().zip(sizes) {
+            for seed in [1, 2] {
+                let input = day.generate(&mut input_rng(seed), size).unwrap();
+                assert_eq!(input, day.generate(&mut input_rng(seed), size).unwrap());
+                let solved = day.solve(&input, &[1, 2]);
+                let answers = solved
+                    .answers
+                    .unwrap_or_else(|err| panic!("day {:02}: {:#}", day.day(), err));
+                for answer in answers {
+                    if let Err(err) = answer.value {
+                        panic!("day {:02} part {}: {:#}", day.day(), answer.part, err);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_gen_args() {
+        let cli = Cli::parse_from(["aoc", "gen", "5", "--size", "8", "--seed", "42"]);
+        match cli.command {
+            Some(Command::Gen(gen)) => {
+                assert_eq!(gen.day, 5);
+                assert_eq!(gen.size, Some(8));
+                assert_eq!(gen.seed, Some(42));
+                assert_eq!(gen.output, None);
+            }
+            _ => panic!("expected gen command"),
+        }
+        assert!(Cli::try_parse_from(["aoc", "gen", "0"]).is_err());
+    }
+}
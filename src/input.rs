@@ -0,0 +1,141 @@
+//!input.rs
+
+use crate::years::DEFAULT_YEAR;
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+/// environment variable to override directory of puzzle inputs
+pub const INPUT_DIR_ENV: &str = "AOC_INPUT_DIR";
+/// default directory of puzzle inputs, relative to working directory
+pub const DEFAULT_INPUT_DIR: &str = "assets";
+
+/// input directory from environment variable or default directory
+pub fn input_dir() -> PathBuf {
+    std::env::var_os(INPUT_DIR_ENV)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_INPUT_DIR))
+}
+
+/// input directory of year: input directory itself for default year, else its sub directory `YYYY`
+pub fn year_input_dir(input_dir: &Path, year: u16) -> PathBuf {
+    if year == DEFAULT_YEAR {
+        input_dir.to_path_buf()
+    } else {
+        input_dir.join(year.to_string())
+    }
+}
+
+/// input directory of profile: its sub directory of input directory, e.g. `assets/alice`
+pub fn profile_input_dir(input_dir: &Path, profile: &str) -> PathBuf {
+    input_dir.join(profile)
+}
+
+/// sub directories of input directory with inputs of a year are named `YYYY`
+fn is_year_dir_name(name: &str) -> bool {
+    !name.is_empty() && name.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// names of profiles in alphabetical order: all sub directories of input directory, which do not
+/// contain inputs of a year
+pub fn profiles(input_dir: &Path) -> Result<Vec<String>> {
+    let entries = std::fs::read_dir(input_dir)
+        .with_context(|| format!("cannot read input directory `{}`", input_dir.display()))?;
+    let mut profiles = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+        if let Some(name) = entry.file_name().to_str() {
+            if !is_year_dir_name(name) {
+                profiles.push(name.to_string());
+            }
+        }
+    }
+    profiles.sort();
+    Ok(profiles)
+}
+
+/// check name of profile given on command line
+pub fn parse_profile(name: &str) -> Result<String, String> {
+    if name.is_empty() || name.contains(['/', '\\']) || name.starts_with('.') {
+        Err(format!("`{}` is not a name of a sub directory", name))
+    } else if is_year_dir_name(name) {
+        Err(format!("`{}` is a year; use --year instead", name))
+    } else {
+        Ok(name.to_string())
+    }
+}
+
+/// path of puzzle input of day in input directory, e.g. `assets/day_05.txt`
+pub fn input_path(input_dir: &Path, day: u8) -> PathBuf {
+    input_dir.join(format!("day_{:02}.txt", day))
+}
+
+/// read puzzle input of day from input_file, if given, or else from input directory
+pub fn load_input(day: u8, input_dir: &Path, input_file: Option<&Path>) -> Result<String> {
+    let path = match input_file {
+        Some(file) => file.to_path_buf(),
+        None => input_path(input_dir, day),
+    };
+    std::fs::read_to_string(&path).with_context(|| {
+        format!(
+            "day {:02}: cannot read input file `{}`",
+            day,
+            path.display()
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_load_input() {
+        let input_dir = std::env::temp_dir().join(format!("aoc_input_{}", std::process::id()));
+        std::fs::create_dir_all(&input_dir).unwrap();
+        std::fs::write(input_path(&input_dir, 1), "1abc2\n").unwrap();
+        let input = load_input(1, &input_dir, None);
+        std::fs::remove_dir_all(&input_dir).unwrap();
+        assert_eq!(input.unwrap(), "1abc2\n");
+        let error = load_input(5, Path::new("no_such_dir"), None).unwrap_err();
+        assert!(format!("{}", error).starts_with("day 05: cannot read input file"));
+        let error = load_input(
+            7,
+            Path::new(DEFAULT_INPUT_DIR),
+            Some(Path::new("missing.txt")),
+        )
+        .unwrap_err();
+        assert_eq!(
+            format!("{}", error),
+            "day 07: cannot read input file `missing.txt`"
+        );
+        assert_eq!(
+            year_input_dir(Path::new(DEFAULT_INPUT_DIR), DEFAULT_YEAR),
+            PathBuf::from("assets")
+        );
+        assert_eq!(
+            year_input_dir(Path::new(DEFAULT_INPUT_DIR), 2024),
+            Path::new("assets").join("2024")
+        );
+    }
+
+    #[test]
+    fn test_profiles() {
+        let input_dir = std::env::temp_dir().join(format!("aoc_profiles_{}", std::process::id()));
+        for dir in ["bob", "alice", "2024", "alice/2024"] {
+            std::fs::create_dir_all(input_dir.join(dir)).unwrap();
+        }
+        std::fs::write(input_path(&input_dir, 1), "input").unwrap();
+        let found = profiles(&input_dir);
+        std::fs::remove_dir_all(&input_dir).unwrap();
+        assert_eq!(found.unwrap(), vec!["alice", "bob"]);
+        assert!(profiles(Path::new("no_such_dir")).is_err());
+        assert_eq!(parse_profile("alice"), Ok("alice".into()));
+        assert!(parse_profile("2024").is_err());
+        assert!(parse_profile("../alice").is_err());
+        assert!(parse_profile("").is_err());
+    }
+}
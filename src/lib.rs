@@ -1,35 +1,475 @@
 //!lib.rs
 
+pub mod answers;
+pub mod bench;
+pub mod cache;
+pub mod cli;
+/// solvers of all days. Each day parses puzzle input into its model and solves parts with typed
+/// answers; reusable helpers of a day are public functions of its module.
+///
+/// ```
+/// use advent_of_code_2023::days::{day_11::Day11, day_12, Solution};
+/// use std::collections::HashMap;
+///
+/// let cosmos = Day11::parse("#..\n...\n..#")?;
+/// assert_eq!(Day11::part_1(&cosmos)?, 6);
+/// assert_eq!(cosmos.calc_sum_galaxy_distances(10), 22);
+///
+/// let (springs, clusters) = day_12::springs_and_damaged_clusters("???.### 1,1,3")?;
+/// assert_eq!(day_12::different_arrangements(springs, &clusters, &mut HashMap::new()), 1);
+/// # Ok::<(), anyhow::Error>(())
+/// ```
 pub mod days;
+#[cfg(test)]
+mod differential;
+pub mod generate;
+pub mod input;
+pub mod matrix;
+pub mod mem;
+pub mod my_lib;
+pub mod parse;
+pub mod render;
+pub mod report;
+pub mod scaffold;
+pub mod solution;
+pub mod step;
+pub mod years;
 
-use anyhow::Result;
+use answers::Answers;
+use anyhow::{anyhow, Result};
+use cache::ResultCache;
+use clap::Parser;
+use cli::{Cli, Command, RunArgs};
+use mem::MemStats;
+use report::{DayTime, Format, PartReport, Report, Status};
+use solution::{Parts, Progress, Solver, Tier};
+use std::collections::BTreeMap;
+use std::io::IsTerminal;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+use tracing::{warn, Level};
 
 pub fn run() -> Result<()> {
-    days::day_01::day_01()?;
-    days::day_02::day_02()?;
-    days::day_03::day_03()?;
-    days::day_04::day_04()?;
-    days::day_05::day_05()?;
-    days::day_06::day_06()?;
-    days::day_07::day_07()?;
-    days::day_08::day_08()?;
-    days::day_09::day_09()?;
-    days::day_10::day_10()?;
-    days::day_11::day_11()?;
-    days::day_12::day_12()?;
-    days::day_13::day_13()?;
-    days::day_14::day_14()?;
-    days::day_15::day_15()?;
-    days::day_16::day_16()?;
-    days::day_17::day_17()?;
-    days::day_18::day_18()?;
-    days::day_19::day_19()?;
-    days::day_20::day_20()?;
-    days::day_21::day_21()?;
-    days::day_22::day_22()?;
-    days::day_23::day_23()?;
-    days::day_24::day_24()?;
-    days::day_25::day_25()?;
+    let cli = Cli::parse();
+    init_logging(cli.verbose);
+    match cli.command {
+        Some(Command::List(list)) => list_days(list.year),
+        Some(Command::Verify(run)) => run_days(&run, true),
+        Some(Command::Matrix(matrix)) => matrix::run_matrix(&matrix),
+        Some(Command::Bench(bench)) => bench::run_bench(&bench),
+        Some(Command::Gen(gen)) => generate::run_generate(&gen),
+        Some(Command::New(new)) => scaffold::run_new(&new),
+        Some(Command::Step(step)) => step::run_step(&step),
+        None => run_days(&cli.run, false),
+    }
+}
+
+/// log events to stderr; level rises from warnings to info, debug and trace with number of `-v`
+fn init_logging(verbose: u8) {
+    let level = match verbose {
+        0 => Level::WARN,
+        1 => Level::INFO,
+        2 => Level::DEBUG,
+        _ => Level::TRACE,
+    };
+    // fails only, if a subscriber has already been set
+    let _ = tracing_subscriber::fmt()
+        .with_max_level(level)
+        .with_writer(std::io::stderr)
+        .with_ansi(std::io::stderr().is_terminal())
+        .try_init();
+}
+
+fn run_days(run: &RunArgs, verify: bool) -> Result<()> {
+    let selected_days = run.select.selected_days();
+    let parts = Parts::from(run.select.part);
+    let input_file = run.select.input.as_deref();
+    if input_file.is_some() && selected_days.len() != 1 {
+        return Err(anyhow!("--input requires exactly one selected day"));
+    }
+    if run.render.is_some() && selected_days.len() != 1 {
+        return Err(anyhow!("--render requires exactly one selected day"));
+    }
+    let input_dir = run.select.input_dir()?;
+    let answers = Answers::load(&answers::answers_path(&input_dir))?;
+    if run.profile_mem && !mem::enable() {
+        return Err(anyhow!(
+            "--profile-mem requires counting allocator as global allocator"
+        ));
+    }
+    let cache = load_cache();
+    let options = SolveOptions {
+        parts,
+        include_slow: run.select.include_slow,
+        time_budget: run.time_budget,
+        answers: &answers,
+        cache: &cache,
+        use_cache: !run.no_cache && !run.profile_mem,
+        profile_mem: run.profile_mem,
+    };
+    let days: Vec<&'static dyn Solver> = years::days_of_year(run.select.year)?
+        .iter()
+        .copied()
+        .filter(|d| selected_days.contains(&d.day()))
+        .collect();
+    let start = Instant::now();
+    let mut report = Report::default();
+    run_ordered(
+        &days,
+        run.jobs as usize,
+        |day| {
+            let start = Instant::now();
+            let (day_reports, memory) = solve_day(*day, &options, &input_dir, input_file);
+            let mut day_time = DayTime::new(day.day(), start.elapsed());
+            day_time.memory = memory;
+            (day_reports, day_time)
+        },
+        |_, (day_reports, day_time)| {
+            if run.format == Format::Text {
+                for part_report in day_reports.iter() {
+                    println!("{}", part_report.text_line(verify));
+                }
+                println!("{}", day_time.text_line());
+            }
+            day_reports.into_iter().for_each(|p| report.push(p));
+            report.push_day(day_time);
+        },
+    );
+    report.set_elapsed(start.elapsed());
+    save_cache(&cache);
+    if run.format == Format::Text {
+        println!("{}", report.elapsed_line());
+    } else {
+        print!("{}", report.render(run.format, verify)?);
+    }
+    if let (Some(path), Some(day)) = (run.render.as_deref(), days.first()) {
+        render_day(*day, &input_dir, input_file, path)?;
+    }
+    let errors = report.count(Status::Error);
+    if errors > 0 {
+        return Err(anyhow!("{} part(s) failed", errors));
+    }
+    let mismatches = report.count(Status::Mismatch);
+    if verify && mismatches > 0 {
+        return Err(anyhow!(
+            "{} answer(s) do not match expected answers",
+            mismatches
+        ));
+    }
+    Ok(())
+}
+
+/// result cache from cache file; a bad cache file is replaced
+fn load_cache() -> ResultCache {
+    ResultCache::load(&cache::cache_path()).unwrap_or_else(|err| {
+        warn!("{:#}; starting with empty result cache", err);
+        ResultCache::default()
+    })
+}
+
+/// a cache, which cannot be written, does not fail the run
+fn save_cache(cache: &ResultCache) {
+    if let Err(err) = cache.save(&cache::cache_path()) {
+        warn!("{:#}", err);
+    }
+}
+
+/// selection of parts, time budget and expected and cached answers of solving days
+struct SolveOptions<'a> {
+    parts: Parts,
+    include_slow: bool,
+    time_budget: Option<Duration>,
+    answers: &'a Answers,
+    /// new answers are always added to cache, cached answers are only used, if use_cache is set
+    cache: &'a ResultCache,
+    use_cache: bool,
+    /// count allocations of solver thread, see `mem`
+    profile_mem: bool,
+}
+
+/// apply work to items on jobs threads and hand over results to done in order of items,
+/// as soon as all previous items are done
+fn run_ordered<T: Sync, R: Send>(
+    items: &[T],
+    jobs: usize,
+    work: impl Fn(&T) -> R + Sync,
+    mut done: impl FnMut(&T, R),
+) {
+    let next_item = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+    thread::scope(|s| {
+        for _ in 0..jobs.clamp(1, items.len().max(1)) {
+            let tx = tx.clone();
+            let (next_item, work) = (&next_item, &work);
+            s.spawn(move || loop {
+                let index = next_item.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(index) else {
+                    break;
+                };
+                if tx.send((index, work(item))).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(tx);
+        let mut pending = BTreeMap::new();
+        let mut next_done = 0;
+        for (index, result) in rx {
+            pending.insert(index, result);
+            while let Some(result) = pending.remove(&next_done) {
+                done(&items[next_done], result);
+                next_done += 1;
+            }
+        }
+    });
+}
 
+/// solve selected parts of day and verify answers. Day is solved in its own thread; if time budget
+/// is exceeded, pending parts are reported as timed out and the thread is left behind. With
+/// `--profile-mem` allocations of the whole day are returned, too.
+fn solve_day(
+    day: &'static dyn Solver,
+    options: &SolveOptions,
+    input_dir: &Path,
+    input_file: Option<&Path>,
+) -> (Vec<PartReport>, Option<MemStats>) {
+    let (mut selected_parts, skipped_parts) = day.select_parts(options.parts, options.include_slow);
+    let mut reports: Vec<PartReport> = skipped_parts
+        .into_iter()
+        .map(|part| PartReport::skipped(day.day(), part))
+        .collect();
+    if selected_parts.is_empty() {
+        return (reports, None);
+    }
+    // a missing input only fails its day
+    let input = match input::load_input(day.day(), input_dir, input_file) {
+        Ok(input) => input,
+        Err(err) => {
+            for part in selected_parts {
+                reports.push(PartReport::error(
+                    day.day(),
+                    part,
+                    &err,
+                    Duration::ZERO,
+                    Duration::ZERO,
+                ));
+            }
+            return (reports, None);
+        }
+    };
+    let fingerprint = answers::fingerprint(&input);
+    let cache_key = cache::cache_key(&input);
+    if options.use_cache {
+        selected_parts.retain(|&part| {
+            let Some(cached) = options.cache.get(day.day(), part, &cache_key) else {
+                return true;
+            };
+            let verification =
+                options
+                    .answers
+                    .verify(day.day(), part, &fingerprint, &cached.answer);
+            let mut report = PartReport::answer(
+                day.day(),
+                part,
+                cached.answer.clone(),
+                verification,
+                cached.parse_time(),
+                cached.solve_time(),
+            );
+            report.cached = true;
+            reports.push(report);
+            false
+        });
+        if selected_parts.is_empty() {
+            reports.sort_by_key(|r| r.part);
+            return (reports, None);
+        }
+    }
+    let (tx, rx) = mpsc::channel();
+    let thread_parts = selected_parts.clone();
+    let profile_mem = options.profile_mem;
+    let spawned = thread::Builder::new()
+        .name(format!("day {:02}", day.day()))
+        .stack_size(solution::SOLVER_STACK_SIZE)
+        .spawn(move || {
+            if profile_mem {
+                mem::start_day();
+            }
+            // progress is reported after parsing and after each part, which ends counting of a part
+            day.solve_with(&input, &thread_parts, &mut |p| {
+                let memory = profile_mem.then(mem::finish_part);
+                // receiver is gone, if day timed out
+                let _ = tx.send((p, memory));
+            })
+        });
+    if let Err(err) = spawned {
+        let err = anyhow!(err).context("cannot spawn solver thread");
+        for part in selected_parts {
+            reports.push(PartReport::error(
+                day.day(),
+                part,
+                &err,
+                Duration::ZERO,
+                Duration::ZERO,
+            ));
+        }
+        return (reports, None);
+    }
+    let deadline = options.time_budget.map(|budget| Instant::now() + budget);
+    let mut parse_time = Duration::ZERO;
+    let mut day_memory = None;
+    while !selected_parts.is_empty() {
+        let progress = match deadline {
+            Some(deadline) => rx.recv_timeout(deadline.saturating_duration_since(Instant::now())),
+            None => rx.recv().map_err(RecvTimeoutError::from),
+        };
+        let progress = progress.map(|(progress, memory)| {
+            if let Some((_, day)) = memory {
+                day_memory = Some(day);
+            }
+            (progress, memory.map(|(part, _)| part))
+        });
+        match progress {
+            Ok((Progress::Parsed(time), _)) => parse_time = time,
+            Ok((Progress::ParseFailed(time, err), _)) => {
+                for part in selected_parts.drain(..) {
+                    reports.push(PartReport::error(
+                        day.day(),
+                        part,
+                        &err,
+                        time,
+                        Duration::ZERO,
+                    ));
+                }
+            }
+            Ok((Progress::Solved(answer), memory)) => {
+                selected_parts.retain(|p| *p != answer.part);
+                reports.push(match answer.value {
+                    Ok(value) => {
+                        options.cache.insert(
+                            day.day(),
+                            answer.part,
+                            &cache_key,
+                            &value,
+                            parse_time,
+                            answer.solve_time,
+                        );
+                        let verification =
+                            options
+                                .answers
+                                .verify(day.day(), answer.part, &fingerprint, &value);
+                        let mut report = PartReport::answer(
+                            day.day(),
+                            answer.part,
+                            value,
+                            verification,
+                            parse_time,
+                            answer.solve_time,
+                        );
+                        report.memory = memory;
+                        report
+                    }
+                    Err(err) => PartReport::error(
+                        day.day(),
+                        answer.part,
+                        &err,
+                        parse_time,
+                        answer.solve_time,
+                    ),
+                });
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                let budget = options.time_budget.unwrap_or_default();
+                for part in selected_parts.drain(..) {
+                    reports.push(PartReport::timed_out(day.day(), part, budget, parse_time));
+                }
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                let err = anyhow!("solver panicked");
+                for part in selected_parts.drain(..) {
+                    reports.push(PartReport::error(
+                        day.day(),
+                        part,
+                        &err,
+                        parse_time,
+                        Duration::ZERO,
+                    ));
+                }
+            }
+        }
+    }
+    reports.sort_by_key(|r| r.part);
+    (reports, day_memory)
+}
+
+/// render grid of day to file; day is parsed again in its own thread, because some days recurse deeply
+fn render_day(
+    day: &'static dyn Solver,
+    input_dir: &Path,
+    input_file: Option<&Path>,
+    path: &Path,
+) -> Result<()> {
+    let input = input::load_input(day.day(), input_dir, input_file)?;
+    let image = thread::Builder::new()
+        .name(format!("render day {:02}", day.day()))
+        .stack_size(solution::SOLVER_STACK_SIZE)
+        .spawn(move || day.render(&input))?
+        .join()
+        .map_err(|_| anyhow!("rendering of day {:02} panicked", day.day()))??;
+    match image {
+        Some(image) => image.write(path),
+        None => Err(anyhow!("day {:02} has no grid to render", day.day())),
+    }
+}
+
+fn list_days(year: u16) -> Result<()> {
+    for day in years::days_of_year(year)?.iter() {
+        let slow_parts: Vec<String> = (1..=day.parts())
+            .filter(|p| day.tier(*p) == Tier::Slow)
+            .map(|p| p.to_string())
+            .collect();
+        match slow_parts.len() {
+            0 => println!("day {:02}", day.day()),
+            1 => println!(
+                "day {:02}: part {} is slow (use --include-slow)",
+                day.day(),
+                slow_parts[0]
+            ),
+            _ => println!(
+                "day {:02}: parts {} are slow (use --include-slow)",
+                day.day(),
+                slow_parts.join(", ")
+            ),
+        }
+    }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_run_ordered() {
+        let items: Vec<u64> = (0..10).collect();
+        for jobs in [1, 4, 20] {
+            let mut results = Vec::new();
+            run_ordered(
+                &items,
+                jobs,
+                |item| {
+                    // later items finish first
+                    thread::sleep(Duration::from_millis(10 - item));
+                    item * 2
+                },
+                |item, result| results.push((*item, result)),
+            );
+            let expected: Vec<(u64, u64)> = items.iter().map(|i| (*i, i * 2)).collect();
+            assert_eq!(results, expected);
+        }
+    }
+}
@@ -1,14 +1,20 @@
 //!main.rs
 
-use advent_of_code_2023::run;
+use advent_of_code_2023::{mem::CountingAlloc, run};
+
+// counts allocations only with `--profile-mem`
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
 
 fn main() {
     if let Err(err) = run() {
-        println!("Error occured: {}", err);
+        eprintln!("Error occured: {}", err);
 
         // look for source
         if let Some(source) = err.source() {
-            println!("Source of error: {:?}", source);
+            eprintln!("Source of error: {:?}", source);
         }
+        // failed runs, e.g. mismatching answers in verify mode, must fail CI jobs
+        std::process::exit(1);
     }
 }
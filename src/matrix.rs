@@ -0,0 +1,361 @@
+//!matrix.rs
+
+use crate::answers::{self, Answers};
+use crate::cli::MatrixArgs;
+use crate::input;
+use crate::report::{csv_field, Format, PartReport, Status};
+use crate::solution::{Parts, Solver};
+use crate::years;
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// reports of parts solved with inputs of one profile
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfileReport {
+    pub profile: String,
+    pub parts: Vec<PartReport>,
+}
+
+/// reports of all profiles; days without input in a profile have no reports
+#[derive(Debug, Default, Serialize)]
+pub struct Matrix {
+    pub profiles: Vec<ProfileReport>,
+    /// wall clock time of whole run
+    pub elapsed_ms: f64,
+}
+
+impl Matrix {
+    /// add profile without reports; reports of parts are pushed in order of profiles
+    pub fn add_profile(&mut self, profile: &str) {
+        self.profiles.push(ProfileReport {
+            profile: profile.to_string(),
+            parts: Vec::new(),
+        });
+    }
+    pub fn push(&mut self, profile: usize, part_report: PartReport) {
+        self.profiles[profile].parts.push(part_report);
+    }
+    pub fn set_elapsed(&mut self, elapsed: Duration) {
+        self.elapsed_ms = elapsed.as_secs_f64() * 1_000.0;
+    }
+    pub fn count(&self, status: Status) -> usize {
+        self.profiles
+            .iter()
+            .flat_map(|p| p.parts.iter())
+            .filter(|p| p.status == status)
+            .count()
+    }
+    /// day and part of all rows of matrix in order
+    fn rows(&self) -> BTreeSet<(u8, u8)> {
+        self.profiles
+            .iter()
+            .flat_map(|p| p.parts.iter())
+            .map(|p| (p.day, p.part))
+            .collect()
+    }
+    fn cells(&self, day: u8, part: u8) -> impl Iterator<Item = String> + '_ {
+        self.profiles.iter().map(move |profile| {
+            cell(
+                profile
+                    .parts
+                    .iter()
+                    .find(|p| p.day == day && p.part == part),
+            )
+        })
+    }
+    pub fn render(&self, format: Format) -> Result<String> {
+        match format {
+            Format::Text => Ok(self.render_text()),
+            Format::Json => Ok(serde_json::to_string_pretty(self)? + "\n"),
+            Format::Csv => Ok(self.render_csv()),
+            Format::Markdown => Ok(self.render_markdown()),
+        }
+    }
+    fn render_text(&self) -> String {
+        let header = ["day".to_string(), "part".to_string()]
+            .into_iter()
+            .chain(self.profiles.iter().map(|p| p.profile.clone()));
+        let mut lines: Vec<Vec<String>> = vec![header.collect()];
+        for (day, part) in self.rows() {
+            let row = [format!("{:02}", day), part.to_string()]
+                .into_iter()
+                .chain(self.cells(day, part));
+            lines.push(row.collect());
+        }
+        let widths: Vec<usize> = (0..lines[0].len())
+            .map(|column| {
+                lines
+                    .iter()
+                    .map(|l| l[column].chars().count())
+                    .max()
+                    .unwrap_or_default()
+            })
+            .collect();
+        let mut text = String::new();
+        for line in lines.iter() {
+            let columns: Vec<String> = line
+                .iter()
+                .zip(widths.iter())
+                .map(|(column, width)| format!("{:<width$}", column, width = *width))
+                .collect();
+            writeln!(text, "{}", columns.join("  ").trim_end()).unwrap();
+        }
+        for profile in self.profiles.iter() {
+            let counts: Vec<String> = [
+                Status::Match,
+                Status::Mismatch,
+                Status::Unknown,
+                Status::Error,
+                Status::TimedOut,
+                Status::Skipped,
+            ]
+            .into_iter()
+            .filter_map(|status| {
+                let count = profile.parts.iter().filter(|p| p.status == status).count();
+                (count > 0).then(|| format!("{} {}", count, status.as_str()))
+            })
+            .collect();
+            writeln!(text, "{}: {}", profile.profile, counts.join(", ")).unwrap();
+        }
+        for profile in self.profiles.iter() {
+            for p in profile
+                .parts
+                .iter()
+                .filter(|p| matches!(p.status, Status::Error | Status::TimedOut))
+            {
+                writeln!(text, "{}: {}", profile.profile, p.text_line(true)).unwrap();
+            }
+        }
+        writeln!(text, "total elapsed time {:.3} ms", self.elapsed_ms).unwrap();
+        text
+    }
+    fn render_csv(&self) -> String {
+        let mut csv = String::from("profile,day,part,answer,status,expected,error,cached\n");
+        for profile in self.profiles.iter() {
+            for p in profile.parts.iter() {
+                writeln!(
+                    csv,
+                    "{},{},{},{},{},{},{},{}",
+                    csv_field(Some(&profile.profile)),
+                    p.day,
+                    p.part,
+                    csv_field(p.answer.as_deref()),
+                    p.status.as_str(),
+                    csv_field(p.expected.as_deref()),
+                    csv_field(p.error.as_deref()),
+                    p.cached
+                )
+                .unwrap();
+            }
+        }
+        csv
+    }
+    fn render_markdown(&self) -> String {
+        let mut md = String::from("| day | part |");
+        for profile in self.profiles.iter() {
+            write!(md, " {} |", profile.profile).unwrap();
+        }
+        md += "\n|----:|-----:|";
+        md += &":---|".repeat(self.profiles.len());
+        md += "\n";
+        for (day, part) in self.rows() {
+            write!(md, "| {} | {} |", day, part).unwrap();
+            for cell in self.cells(day, part) {
+                write!(md, " {} |", cell.replace('|', "\\|")).unwrap();
+            }
+            md += "\n";
+        }
+        writeln!(md, "\ntotal elapsed time {:.3} ms", self.elapsed_ms).unwrap();
+        md
+    }
+}
+
+/// answer of part with its verification or status of part; `-`, if profile has no input of day
+fn cell(part_report: Option<&PartReport>) -> String {
+    let Some(p) = part_report else {
+        return "-".to_string();
+    };
+    let answer = p.answer_with_mark();
+    match p.status {
+        Status::Match => format!("{} pass", answer),
+        Status::Mismatch => format!(
+            "{} FAIL (expected {})",
+            answer,
+            p.expected.as_deref().unwrap_or_default()
+        ),
+        Status::Unknown => format!("{} unknown", answer),
+        Status::Skipped => "skipped".to_string(),
+        Status::TimedOut => "timed out".to_string(),
+        Status::Error => "error".to_string(),
+    }
+}
+
+/// solve selected days with inputs of all profiles and print matrix of answers. Days without input
+/// in a profile are left out of its column.
+pub fn run_matrix(args: &MatrixArgs) -> Result<()> {
+    if args.select.input.is_some() || args.select.profile.is_some() {
+        return Err(anyhow!(
+            "matrix uses inputs of all profiles; --input and --profile are not supported"
+        ));
+    }
+    let selected_days = args.select.selected_days();
+    let parts = Parts::from(args.select.part);
+    let input_dir = input::input_dir();
+    let profiles = input::profiles(&input_dir)?;
+    if profiles.is_empty() {
+        return Err(anyhow!(
+            "no profiles in input directory `{}`; add inputs of a profile to a sub directory, e.g. `{}`",
+            input_dir.display(),
+            input::input_path(&input::profile_input_dir(&input_dir, "alice"), 1).display()
+        ));
+    }
+    let days: Vec<&'static dyn Solver> = years::days_of_year(args.select.year)?
+        .iter()
+        .copied()
+        .filter(|d| selected_days.contains(&d.day()))
+        .collect();
+    let mut matrix = Matrix::default();
+    let mut profile_dirs: Vec<(PathBuf, Answers)> = Vec::with_capacity(profiles.len());
+    for profile in profiles.iter() {
+        let profile_dir = input::year_input_dir(
+            &input::profile_input_dir(&input_dir, profile),
+            args.select.year,
+        );
+        let answers = Answers::load(&answers::answers_path(&profile_dir))?;
+        matrix.add_profile(profile);
+        profile_dirs.push((profile_dir, answers));
+    }
+    // days of all profiles are solved in parallel
+    let items: Vec<(usize, &'static dyn Solver)> = profile_dirs
+        .iter()
+        .enumerate()
+        .flat_map(|(profile, (profile_dir, _))| {
+            days.iter()
+                .filter(|d| input::input_path(profile_dir, d.day()).exists())
+                .map(move |d| (profile, *d))
+        })
+        .collect();
+    let cache = crate::load_cache();
+    let start = Instant::now();
+    crate::run_ordered(
+        &items,
+        args.jobs as usize,
+        |(profile, day)| {
+            let (profile_dir, answers) = &profile_dirs[*profile];
+            let options = crate::SolveOptions {
+                parts,
+                include_slow: args.select.include_slow,
+                time_budget: args.time_budget,
+                answers,
+                cache: &cache,
+                use_cache: !args.no_cache,
+                profile_mem: false,
+            };
+            crate::solve_day(*day, &options, profile_dir, None).0
+        },
+        |(profile, _), day_reports| {
+            day_reports
+                .into_iter()
+                .for_each(|p| matrix.push(*profile, p))
+        },
+    );
+    matrix.set_elapsed(start.elapsed());
+    crate::save_cache(&cache);
+    print!("{}", matrix.render(args.format)?);
+    let errors = matrix.count(Status::Error);
+    if errors > 0 {
+        return Err(anyhow!("{} part(s) failed", errors));
+    }
+    let mismatches = matrix.count(Status::Mismatch);
+    if mismatches > 0 {
+        return Err(anyhow!(
+            "{} answer(s) do not match expected answers",
+            mismatches
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::answers::Verification;
+    use crate::cli::{Cli, Command};
+    use anyhow::anyhow;
+    use clap::Parser;
+
+    #[test]
+    fn test_render() {
+        let mut matrix = Matrix::default();
+        matrix.add_profile("alice");
+        matrix.add_profile("bob");
+        let answer = |answer: &str, verification| {
+            PartReport::answer(
+                1,
+                1,
+                answer.into(),
+                verification,
+                Duration::ZERO,
+                Duration::ZERO,
+            )
+        };
+        matrix.push(0, answer("42", Verification::Match));
+        matrix.push(
+            1,
+            answer(
+                "41",
+                Verification::Mismatch {
+                    expected: "43".into(),
+                },
+            ),
+        );
+        matrix.push(
+            1,
+            PartReport::error(2, 2, &anyhow!("bad input"), Duration::ZERO, Duration::ZERO),
+        );
+        matrix.set_elapsed(Duration::from_millis(3));
+        assert_eq!(matrix.count(Status::Mismatch), 1);
+
+        let text = matrix.render(Format::Text).unwrap();
+        let mut lines = text.lines();
+        assert_eq!(lines.next(), Some("day  part  alice    bob"));
+        assert_eq!(
+            lines.next(),
+            Some("01   1     42 pass  41 FAIL (expected 43)")
+        );
+        assert_eq!(lines.next(), Some("02   2     -        error"));
+        assert_eq!(lines.next(), Some("alice: 1 match"));
+        assert_eq!(lines.next(), Some("bob: 1 mismatch, 1 error"));
+        assert_eq!(lines.next(), Some("bob: day 02 part 2 failed: bad input"));
+        assert_eq!(lines.next(), Some("total elapsed time 3.000 ms"));
+
+        let markdown = matrix.render(Format::Markdown).unwrap();
+        assert!(markdown.starts_with("| day | part | alice | bob |\n|----:|-----:|:---|:---|\n"));
+        assert!(markdown.contains("| 2 | 2 | - | error |"));
+
+        let csv = matrix.render(Format::Csv).unwrap();
+        assert_eq!(csv.lines().nth(2), Some("bob,1,1,41,mismatch,43,,false"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&matrix.render(Format::Json).unwrap()).unwrap();
+        assert_eq!(json["profiles"][1]["profile"], "bob");
+        assert_eq!(json["profiles"][1]["parts"][1]["status"], "error");
+    }
+
+    #[test]
+    fn test_matrix_args() {
+        let cli = Cli::parse_from(["aoc", "matrix", "1-5", "--profile", "alice"]);
+        match cli.command {
+            Some(Command::Matrix(matrix)) => {
+                assert_eq!(matrix.select.selected_days(), vec![1, 2, 3, 4, 5]);
+                assert_eq!(matrix.select.profile.as_deref(), Some("alice"));
+            }
+            _ => panic!("expected matrix command"),
+        }
+    }
+}
@@ -0,0 +1,220 @@
+//!mem.rs
+
+use serde::Serialize;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fmt::Display;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// counting is switched on by `--profile-mem`
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// allocator, which counts allocations of each thread while counting is enabled. It only counts,
+/// if it is installed with `#[global_allocator]`, which the binary does.
+pub struct CountingAlloc;
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            record(layout.size(), 0);
+        }
+        ptr
+    }
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            record(layout.size(), 0);
+        }
+        ptr
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        record(0, layout.size());
+    }
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            record(new_size, layout.size());
+        }
+        new_ptr
+    }
+}
+
+/// allocations since start of a segment; live bytes are counted from start of thread
+#[derive(Clone, Copy)]
+struct Segment {
+    base_live: isize,
+    count: u64,
+    bytes: u64,
+    peak_live: isize,
+}
+
+impl Segment {
+    const fn new(live: isize) -> Self {
+        Segment {
+            base_live: live,
+            count: 0,
+            bytes: 0,
+            peak_live: live,
+        }
+    }
+    fn stats(&self) -> MemStats {
+        MemStats {
+            allocations: self.count,
+            allocated_bytes: self.bytes,
+            peak_bytes: (self.peak_live - self.base_live).max(0) as u64,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Counters {
+    live: isize,
+    day: Segment,
+    part: Segment,
+}
+
+thread_local! {
+    // const initialized Cell of Copy type neither allocates nor needs a destructor
+    static COUNTERS: Cell<Counters> = const {
+        Cell::new(Counters {
+            live: 0,
+            day: Segment::new(0),
+            part: Segment::new(0),
+        })
+    };
+}
+
+fn record(allocated: usize, freed: usize) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    // thread local storage is not available while thread is torn down
+    let _ = COUNTERS.try_with(|counters| {
+        let mut c = counters.get();
+        c.live += allocated as isize - freed as isize;
+        if allocated > 0 {
+            for segment in [&mut c.day, &mut c.part] {
+                segment.count += 1;
+                segment.bytes += allocated as u64;
+                segment.peak_live = segment.peak_live.max(c.live);
+            }
+        }
+        counters.set(c);
+    });
+}
+
+/// allocations of a day or part of a day
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct MemStats {
+    /// number of allocations including reallocations
+    pub allocations: u64,
+    /// sum of bytes of all allocations
+    pub allocated_bytes: u64,
+    /// maximum of bytes allocated at the same time above level at start
+    pub peak_bytes: u64,
+}
+
+impl Display for MemStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} allocations, {} allocated, peak {}",
+            self.allocations,
+            format_bytes(self.allocated_bytes),
+            format_bytes(self.peak_bytes)
+        )
+    }
+}
+
+/// bytes in binary units, e.g. `1.5 MiB`
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+/// switch on counting of allocations; false, if CountingAlloc is not the global allocator
+pub fn enable() -> bool {
+    ENABLED.store(true, Ordering::Relaxed);
+    start_day();
+    drop(std::hint::black_box(Box::new(0_u64)));
+    COUNTERS.with(|counters| counters.get().day.count) > 0
+}
+
+/// start counting allocations of a day and of its first part on current thread
+pub fn start_day() {
+    COUNTERS.with(|counters| {
+        let live = counters.get().live;
+        counters.set(Counters {
+            live,
+            day: Segment::new(live),
+            part: Segment::new(live),
+        });
+    });
+}
+
+/// allocations of current thread since start of part and since start of day; starts next part
+pub fn finish_part() -> (MemStats, MemStats) {
+    COUNTERS.with(|counters| {
+        let mut c = counters.get();
+        let stats = (c.part.stats(), c.day.stats());
+        c.part = Segment::new(c.live);
+        counters.set(c);
+        stats
+    })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::cli::Cli;
+    use clap::Parser;
+
+    #[global_allocator]
+    static ALLOC: CountingAlloc = CountingAlloc;
+
+    #[test]
+    fn test_counting_alloc() {
+        assert!(enable());
+        start_day();
+        let v: Vec<u64> = Vec::with_capacity(1_000);
+        drop(v);
+        let (part, day) = finish_part();
+        assert_eq!(part.allocations, 1);
+        assert_eq!(part.allocated_bytes, 8_000);
+        assert_eq!(part.peak_bytes, 8_000);
+        let s = String::from("counted");
+        let (part, day_2) = finish_part();
+        assert_eq!(part.allocations, 1);
+        assert_eq!(part.peak_bytes, 7);
+        assert_eq!(day_2.allocations, day.allocations + 1);
+        assert_eq!(day_2.peak_bytes, 8_000);
+        drop(s);
+
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1_536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+        assert_eq!(part.to_string(), "1 allocations, 7 B allocated, peak 7 B");
+    }
+
+    #[test]
+    fn test_profile_mem_flag() {
+        assert!(!Cli::parse_from(["aoc", "12"]).run.profile_mem);
+        assert!(
+            Cli::parse_from(["aoc", "12", "--profile-mem"])
+                .run
+                .profile_mem
+        );
+    }
+}
@@ -0,0 +1,10 @@
+//!mod.rs
+//pub mod my_lib: grid, compass, cycle, interval, search and array utilities shared by the days
+
+pub mod my_array;
+pub mod my_compass;
+pub mod my_cycle;
+pub mod my_interval;
+pub mod my_map_point;
+pub mod my_map_two_dim;
+pub mod my_search;
@@ -0,0 +1,102 @@
+//!my_array.rs
+
+use std::ops::{Index, IndexMut};
+
+/// fixed capacity array, which tracks number of used elements
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MyArray<T, const N: usize> {
+    items: [T; N],
+    n_items: usize,
+}
+
+impl<T: Copy + Default, const N: usize> Default for MyArray<T, N> {
+    fn default() -> Self {
+        MyArray {
+            items: [T::default(); N],
+            n_items: 0,
+        }
+    }
+}
+
+impl<T, const N: usize> Index<usize> for MyArray<T, N> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.items[..self.n_items][index]
+    }
+}
+
+impl<T, const N: usize> IndexMut<usize> for MyArray<T, N> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.items[..self.n_items][index]
+    }
+}
+
+impl<T: Copy + Default, const N: usize> MyArray<T, N> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// fill first n_items elements with init_item
+    pub fn init(init_item: T, n_items: usize) -> Self {
+        let mut result = Self::default();
+        for _ in 0..n_items.min(N) {
+            result.push(init_item);
+        }
+        result
+    }
+    pub fn push(&mut self, item: T) {
+        if self.n_items == N {
+            panic!("MyArray is full");
+        }
+        self.items[self.n_items] = item;
+        self.n_items += 1;
+    }
+    pub fn pop(&mut self) -> Option<T> {
+        if self.n_items == 0 {
+            return None;
+        }
+        self.n_items -= 1;
+        Some(self.items[self.n_items])
+    }
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.items[..self.n_items].get(index)
+    }
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.items[..self.n_items].get_mut(index)
+    }
+    pub fn len(&self) -> usize {
+        self.n_items
+    }
+    pub fn is_empty(&self) -> bool {
+        self.n_items == 0
+    }
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.items[..self.n_items].iter()
+    }
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.items[..self.n_items].iter_mut()
+    }
+    pub fn as_slice(&self) -> &[T] {
+        &self.items[..self.n_items]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_my_array() {
+        let mut array: MyArray<u8, 3> = MyArray::init(7, 2);
+        assert_eq!(array.len(), 2);
+        array.push(9);
+        assert_eq!(array.as_slice(), &[7, 7, 9]);
+        array[0] = 1;
+        assert_eq!(array.get(0), Some(&1));
+        assert_eq!(array.get(3), None);
+        assert_eq!(array.pop(), Some(9));
+        assert_eq!(array.iter().sum::<u8>(), 8);
+        assert!(!array.is_empty());
+    }
+}
@@ -0,0 +1,140 @@
+//!my_compass.rs
+
+use std::fmt::Display;
+
+/// Orientation on a two dimensional map. N points to row 0, E points to the last column.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Compass {
+    #[default]
+    Center,
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Display for Compass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let c = match self {
+            Compass::Center => "Center",
+            Compass::N => "N",
+            Compass::NE => "NE",
+            Compass::E => "E",
+            Compass::SE => "SE",
+            Compass::S => "S",
+            Compass::SW => "SW",
+            Compass::W => "W",
+            Compass::NW => "NW",
+        };
+        write!(f, "{}", c)
+    }
+}
+
+impl Compass {
+    /// cardinal orientations in clockwise order, starting with N
+    pub const CARDINALS: [Compass; 4] = [Compass::N, Compass::E, Compass::S, Compass::W];
+    /// cardinal and ordinal orientations in clockwise order, starting with N
+    pub const ALL: [Compass; 8] = [
+        Compass::N,
+        Compass::NE,
+        Compass::E,
+        Compass::SE,
+        Compass::S,
+        Compass::SW,
+        Compass::W,
+        Compass::NW,
+    ];
+
+    pub fn is_center(&self) -> bool {
+        *self == Compass::Center
+    }
+    pub fn is_cardinal(&self) -> bool {
+        matches!(self, Compass::N | Compass::E | Compass::S | Compass::W)
+    }
+    pub fn is_ordinal(&self) -> bool {
+        matches!(self, Compass::NE | Compass::SE | Compass::SW | Compass::NW)
+    }
+    /// opposite orientation, Center stays Center
+    pub fn flip(&self) -> Self {
+        match self {
+            Compass::Center => Compass::Center,
+            Compass::N => Compass::S,
+            Compass::NE => Compass::SW,
+            Compass::E => Compass::W,
+            Compass::SE => Compass::NW,
+            Compass::S => Compass::N,
+            Compass::SW => Compass::NE,
+            Compass::W => Compass::E,
+            Compass::NW => Compass::SE,
+        }
+    }
+    /// rotate by 45° clockwise, Center stays Center
+    pub fn clockwise(&self) -> Self {
+        match self {
+            Compass::Center => Compass::Center,
+            Compass::N => Compass::NE,
+            Compass::NE => Compass::E,
+            Compass::E => Compass::SE,
+            Compass::SE => Compass::S,
+            Compass::S => Compass::SW,
+            Compass::SW => Compass::W,
+            Compass::W => Compass::NW,
+            Compass::NW => Compass::N,
+        }
+    }
+    /// rotate by 45° counterclockwise, Center stays Center
+    pub fn counterclockwise(&self) -> Self {
+        match self {
+            Compass::Center => Compass::Center,
+            Compass::N => Compass::NW,
+            Compass::NE => Compass::N,
+            Compass::E => Compass::NE,
+            Compass::SE => Compass::E,
+            Compass::S => Compass::SE,
+            Compass::SW => Compass::S,
+            Compass::W => Compass::SW,
+            Compass::NW => Compass::W,
+        }
+    }
+    /// offset in x and y of one step in orientation
+    pub fn delta(&self) -> (i64, i64) {
+        match self {
+            Compass::Center => (0, 0),
+            Compass::N => (0, -1),
+            Compass::NE => (1, -1),
+            Compass::E => (1, 0),
+            Compass::SE => (1, 1),
+            Compass::S => (0, 1),
+            Compass::SW => (-1, 1),
+            Compass::W => (-1, 0),
+            Compass::NW => (-1, -1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_rotation() {
+        for orientation in Compass::ALL {
+            assert_eq!(orientation.clockwise().counterclockwise(), orientation);
+            assert_eq!(
+                orientation.clockwise().clockwise().clockwise().clockwise(),
+                orientation.flip()
+            );
+            let (dx, dy) = orientation.delta();
+            assert_eq!(orientation.flip().delta(), (-dx, -dy));
+        }
+        assert_eq!(Compass::Center.flip(), Compass::Center);
+        assert!(Compass::CARDINALS
+            .iter()
+            .all(|c| c.is_cardinal() && !c.is_ordinal()));
+    }
+}
@@ -0,0 +1,129 @@
+//!my_cycle.rs
+
+use std::collections::{hash_map::Entry, HashMap};
+use std::hash::Hash;
+
+/// states of a sequence, which repeats every length steps from step start onwards
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<S> {
+    start: usize,
+    length: usize,
+    // states of steps 0..start + length
+    states: Vec<S>,
+}
+
+impl<S> Cycle<S> {
+    /// first step of cycle
+    pub fn start(&self) -> usize {
+        self.start
+    }
+    pub fn length(&self) -> usize {
+        self.length
+    }
+    /// state after n steps
+    pub fn state_at(&self, n: usize) -> &S {
+        if n < self.states.len() {
+            &self.states[n]
+        } else {
+            &self.states[self.start + (n - self.start) % self.length]
+        }
+    }
+    /// states of steps 0..start + length, which are all distinct
+    pub fn history(&self) -> &[S] {
+        &self.states
+    }
+    /// first step n > 0, for which predicate is true, if predicate is true exactly for all
+    /// multiples of n
+    pub fn periodic_step(&self, predicate: impl Fn(&S) -> bool) -> Option<usize> {
+        let n = (1..self.states.len()).find(|s| predicate(&self.states[*s]))?;
+        // history and one cycle decide about all further steps
+        (self.length.is_multiple_of(n)
+            && self
+                .states
+                .iter()
+                .enumerate()
+                .all(|(s, state)| predicate(state) == (s > 0 && s.is_multiple_of(n))))
+        .then_some(n)
+    }
+}
+
+/// applies step to states beginning with start, until a state repeats
+pub fn find_cycle<S, E, F>(start: S, step: F) -> Result<Cycle<S>, E>
+where
+    S: Eq + Hash + Clone,
+    F: FnMut(&S) -> Result<S, E>,
+{
+    find_cycle_by_key(start, step, |state| state.clone())
+}
+
+/// like find_cycle, but states are compared by key, which must determine next state
+pub fn find_cycle_by_key<S, K, E, F, G>(start: S, mut step: F, mut key: G) -> Result<Cycle<S>, E>
+where
+    K: Eq + Hash,
+    F: FnMut(&S) -> Result<S, E>,
+    G: FnMut(&S) -> K,
+{
+    // history of keys with their step
+    let mut seen: HashMap<K, usize> = HashMap::new();
+    let mut states = Vec::new();
+    let mut state = start;
+    loop {
+        match seen.entry(key(&state)) {
+            Entry::Occupied(first) => {
+                let start = *first.get();
+                return Ok(Cycle {
+                    start,
+                    length: states.len() - start,
+                    states,
+                });
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(states.len());
+            }
+        }
+        let next_state = step(&state)?;
+        states.push(state);
+        state = next_state;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use std::convert::Infallible;
+
+    #[test]
+    fn test_find_cycle() {
+        // 0 -> 1 -> 2 -> 3 -> 4 -> 2
+        let cycle =
+            find_cycle(0, |n| Ok::<_, Infallible>(if *n == 4 { 2 } else { n + 1 })).unwrap();
+        assert_eq!(cycle.start(), 2);
+        assert_eq!(cycle.length(), 3);
+        assert_eq!(cycle.history(), &[0, 1, 2, 3, 4]);
+        assert_eq!(*cycle.state_at(1), 1);
+        assert_eq!(*cycle.state_at(5), 2);
+        assert_eq!(*cycle.state_at(1_000_000_000), 4);
+
+        // key ignores counter of steps
+        let cycle = find_cycle_by_key(
+            (0, 0),
+            |(n, steps)| Ok::<_, Infallible>(((n + 3) % 7, steps + 1)),
+            |(n, _)| *n,
+        )
+        .unwrap();
+        assert_eq!((cycle.start(), cycle.length()), (0, 7));
+        assert_eq!(*cycle.state_at(9), (6, 2));
+
+        let cycle = find_cycle(0, |n| Ok::<_, Infallible>((n + 1) % 6)).unwrap();
+        assert_eq!(cycle.periodic_step(|n| *n == 3), Some(3));
+        // 5 is reached after 5 steps, which is no multiple of 3
+        assert_eq!(cycle.periodic_step(|n| *n == 3 || *n == 5), None);
+        // cycle length is no multiple of 4
+        assert_eq!(cycle.periodic_step(|n| *n == 4), None);
+        assert_eq!(cycle.periodic_step(|n| *n == 6), None);
+
+        let failing = find_cycle(0, |n| if *n < 3 { Ok(n + 1) } else { Err(*n) });
+        assert_eq!(failing, Err(3));
+    }
+}
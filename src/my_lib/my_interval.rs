@@ -0,0 +1,362 @@
+//!my_interval.rs
+
+use num::PrimInt;
+
+/// half open interval start..end of integers; interval is empty, if start >= end
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Interval<T> {
+    start: T,
+    end: T,
+}
+
+impl<T: PrimInt> Interval<T> {
+    pub fn new(start: T, end: T) -> Self {
+        Self { start, end }
+    }
+    /// interval of len values starting at start
+    pub fn with_len(start: T, len: T) -> Self {
+        Self::new(start, start + len)
+    }
+    /// interval of a single value
+    pub fn single(value: T) -> Self {
+        Self::new(value, value + T::one())
+    }
+    /// interval of all values between first and last, both included
+    pub fn inclusive(first: T, last: T) -> Self {
+        Self::new(first, last + T::one())
+    }
+    pub fn start(&self) -> T {
+        self.start
+    }
+    pub fn end(&self) -> T {
+        self.end
+    }
+    /// number of values in interval
+    pub fn len(&self) -> T {
+        if self.is_empty() {
+            T::zero()
+        } else {
+            self.end - self.start
+        }
+    }
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value < self.end
+    }
+    /// values in both intervals; may be empty
+    pub fn intersection(&self, other: &Self) -> Self {
+        Self::new(self.start.max(other.start), self.end.min(other.end))
+    }
+    /// values below point and values at or above point; both may be empty
+    pub fn split_at(&self, point: T) -> (Self, Self) {
+        let point = point.clamp(self.start, self.end.max(self.start));
+        (Self::new(self.start, point), Self::new(point, self.end))
+    }
+}
+
+/// sorted set of disjoint, non adjacent and non empty intervals
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self {
+            intervals: Vec::new(),
+        }
+    }
+}
+
+impl<T: PrimInt> FromIterator<Interval<T>> for IntervalSet<T> {
+    /// adjacent and overlapping intervals are merged
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut intervals: Vec<Interval<T>> = iter.into_iter().filter(|i| !i.is_empty()).collect();
+        intervals.sort_by_key(|i| i.start);
+        let mut merged: Vec<Interval<T>> = Vec::with_capacity(intervals.len());
+        for interval in intervals {
+            match merged.last_mut() {
+                Some(last) if interval.start <= last.end => last.end = last.end.max(interval.end),
+                _ => merged.push(interval),
+            }
+        }
+        Self { intervals: merged }
+    }
+}
+
+impl<T: PrimInt> From<Interval<T>> for IntervalSet<T> {
+    fn from(interval: Interval<T>) -> Self {
+        Self::from_iter([interval])
+    }
+}
+
+impl<T: PrimInt> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn iter(&self) -> impl Iterator<Item = &Interval<T>> {
+        self.intervals.iter()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+    /// number of values in all intervals
+    pub fn size(&self) -> T {
+        self.intervals
+            .iter()
+            .fold(T::zero(), |size, interval| size + interval.len())
+    }
+    pub fn min(&self) -> Option<T> {
+        self.intervals.first().map(|i| i.start)
+    }
+    pub fn contains(&self, value: T) -> bool {
+        // index of first interval, which ends after value
+        let index = self.intervals.partition_point(|i| i.end <= value);
+        self.intervals.get(index).is_some_and(|i| i.contains(value))
+    }
+    pub fn insert(&mut self, interval: Interval<T>) {
+        *self = self.union(&Self::from(interval));
+    }
+    pub fn union(&self, other: &Self) -> Self {
+        self.intervals
+            .iter()
+            .chain(other.intervals.iter())
+            .copied()
+            .collect()
+    }
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intervals = Vec::new();
+        let (mut a, mut b) = (self.intervals.iter(), other.intervals.iter());
+        let (mut next_a, mut next_b) = (a.next(), b.next());
+        while let (Some(ia), Some(ib)) = (next_a, next_b) {
+            let intersection = ia.intersection(ib);
+            if !intersection.is_empty() {
+                intervals.push(intersection);
+            }
+            // drop interval, which ends first
+            if ia.end <= ib.end {
+                next_a = a.next();
+            } else {
+                next_b = b.next();
+            }
+        }
+        Self { intervals }
+    }
+    /// values of self, which are not in other
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut intervals = Vec::new();
+        let mut others = other.intervals.iter().peekable();
+        for interval in self.intervals.iter() {
+            let mut rest = *interval;
+            // skip other intervals, which end before rest
+            while others.next_if(|o| o.end <= rest.start).is_some() {}
+            while let Some(o) = others.peek() {
+                if o.start >= rest.end {
+                    break;
+                }
+                let (below, _) = rest.split_at(o.start);
+                if !below.is_empty() {
+                    intervals.push(below);
+                }
+                rest = rest.split_at(o.end).1;
+                if rest.is_empty() {
+                    break;
+                }
+                others.next();
+            }
+            if !rest.is_empty() {
+                intervals.push(rest);
+            }
+        }
+        Self { intervals }
+    }
+    /// values below point and values at or above point
+    pub fn split_at(&self, point: T) -> (Self, Self) {
+        let (below, above): (Vec<_>, Vec<_>) =
+            self.intervals.iter().map(|i| i.split_at(point)).unzip();
+        (below.into_iter().collect(), above.into_iter().collect())
+    }
+}
+
+/// function, which shifts values of source intervals to destination; values, which are not in
+/// a source interval, map to themselves. If source intervals overlap, first one is used.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PiecewiseLinear<T> {
+    pieces: Vec<(Interval<T>, T)>,
+}
+
+impl<T> Default for PiecewiseLinear<T> {
+    fn default() -> Self {
+        Self { pieces: Vec::new() }
+    }
+}
+
+impl<T: PrimInt> PiecewiseLinear<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// values of source map to destination_start + (value - source.start())
+    pub fn add_piece(&mut self, source: Interval<T>, destination_start: T) {
+        self.pieces.push((source, destination_start));
+    }
+    /// source intervals with their destination start in order of adding them
+    pub fn pieces(&self) -> impl Iterator<Item = (&Interval<T>, T)> {
+        self.pieces.iter().map(|(source, d)| (source, *d))
+    }
+    pub fn apply(&self, value: T) -> T {
+        match self
+            .pieces
+            .iter()
+            .find(|(source, _)| source.contains(value))
+        {
+            Some((source, destination_start)) => value - source.start + *destination_start,
+            None => value,
+        }
+    }
+    /// image of all values of set
+    pub fn map(&self, set: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut remaining = set.clone();
+        let mut mapped = Vec::new();
+        for (source, destination_start) in self.pieces.iter() {
+            let source_set = IntervalSet::from(*source);
+            for hit in remaining.intersection(&source_set).iter() {
+                mapped.push(Interval::new(
+                    hit.start - source.start + *destination_start,
+                    hit.end - source.start + *destination_start,
+                ));
+            }
+            remaining = remaining.difference(&source_set);
+        }
+        mapped.extend(remaining.intervals);
+        mapped.into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use proptest::prelude::*;
+
+    // values of interval set in domain 0..64 as bits
+    fn bits(set: &IntervalSet<u32>) -> u64 {
+        (0..64)
+            .filter(|v| set.contains(*v))
+            .fold(0, |bits, v| bits | 1 << v)
+    }
+
+    fn interval_set() -> impl Strategy<Value = IntervalSet<u32>> {
+        prop::collection::vec((0..64_u32, 0..64_u32), 0..6).prop_map(|bounds| {
+            bounds
+                .into_iter()
+                .map(|(s, e)| Interval::new(s, e))
+                .collect()
+        })
+    }
+
+    fn is_normalized(set: &IntervalSet<u32>) -> bool {
+        set.iter().all(|i| !i.is_empty())
+            && set.intervals.windows(2).all(|w| w[0].end() < w[1].start())
+    }
+
+    #[test]
+    fn test_interval() {
+        let interval = Interval::inclusive(3_u64, 7);
+        assert_eq!(interval, Interval::with_len(3, 5));
+        assert_eq!(interval.len(), 5);
+        assert!(interval.contains(7) && !interval.contains(8));
+        assert_eq!(
+            interval.split_at(5),
+            (Interval::new(3, 5), Interval::new(5, 8))
+        );
+        assert!(interval.split_at(9).1.is_empty());
+        assert!(interval.split_at(1).0.is_empty());
+        assert!(interval.intersection(&Interval::new(8, 10)).is_empty());
+        assert_eq!(Interval::new(5_u64, 2).len(), 0);
+    }
+
+    #[test]
+    fn test_interval_set() {
+        let set: IntervalSet<u64> = [
+            Interval::new(5, 8),
+            Interval::new(0, 2),
+            Interval::new(2, 4),
+            Interval::new(6, 10),
+        ]
+        .into_iter()
+        .collect();
+        // adjacent and overlapping intervals are merged
+        assert_eq!(
+            set.iter().copied().collect::<Vec<_>>(),
+            vec![Interval::new(0, 4), Interval::new(5, 10)]
+        );
+        assert_eq!(set.size(), 9);
+        assert_eq!(set.min(), Some(0));
+        let hole = IntervalSet::from(Interval::new(3, 6));
+        assert_eq!(
+            set.difference(&hole),
+            [Interval::new(0, 3), Interval::new(6, 10)]
+                .into_iter()
+                .collect()
+        );
+        let mut map = PiecewiseLinear::new();
+        map.add_piece(Interval::new(0, 2), 50);
+        map.add_piece(Interval::new(1, 3), 20);
+        assert_eq!(map.apply(1), 51);
+        assert_eq!(map.apply(2), 21);
+        assert_eq!(map.apply(7), 7);
+        assert_eq!(
+            map.map(&set),
+            [
+                Interval::new(3, 4),
+                Interval::new(5, 10),
+                Interval::new(21, 22),
+                Interval::new(50, 52)
+            ]
+            .into_iter()
+            .collect()
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_set_algebra(a in interval_set(), b in interval_set()) {
+            prop_assert!(is_normalized(&a));
+            let union = a.union(&b);
+            let intersection = a.intersection(&b);
+            let difference = a.difference(&b);
+            prop_assert!(is_normalized(&union));
+            prop_assert!(is_normalized(&intersection));
+            prop_assert!(is_normalized(&difference));
+            prop_assert_eq!(bits(&union), bits(&a) | bits(&b));
+            prop_assert_eq!(bits(&intersection), bits(&a) & bits(&b));
+            prop_assert_eq!(bits(&difference), bits(&a) & !bits(&b));
+            prop_assert_eq!(union.size(), bits(&union).count_ones());
+        }
+
+        #[test]
+        fn test_split_at(a in interval_set(), point in 0..64_u32) {
+            let (below, above) = a.split_at(point);
+            prop_assert!(is_normalized(&below) && is_normalized(&above));
+            prop_assert_eq!(bits(&below), bits(&a) & ((1 << point) - 1));
+            prop_assert_eq!(bits(&above), bits(&a) & !((1 << point) - 1));
+        }
+
+        #[test]
+        fn test_piecewise_linear(
+            a in interval_set(),
+            pieces in prop::collection::vec((0..32_u32, 0..32_u32, 0..32_u32), 0..4),
+        ) {
+            let mut map = PiecewiseLinear::new();
+            for (source_start, len, destination_start) in pieces {
+                map.add_piece(Interval::with_len(source_start, len), destination_start);
+            }
+            let mapped = map.map(&a);
+            prop_assert!(is_normalized(&mapped));
+            let expected = (0..64).filter(|v| a.contains(*v)).map(|v| map.apply(v));
+            prop_assert_eq!(bits(&mapped), expected.fold(0, |bits, v| bits | 1 << v));
+        }
+    }
+}
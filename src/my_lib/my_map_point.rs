@@ -0,0 +1,295 @@
+//!my_map_point.rs
+
+use super::my_compass::Compass;
+use std::fmt::Display;
+
+/// Size of a map: number of columns (width) and rows (height).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MapSize {
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Display for MapSize {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} x {}", self.width, self.height)
+    }
+}
+
+impl MapSize {
+    pub fn new(width: usize, height: usize) -> Self {
+        MapSize { width, height }
+    }
+    /// number of cells
+    pub fn len(&self) -> usize {
+        self.width * self.height
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    pub fn contains(&self, point: MapPoint) -> bool {
+        point.x < self.width && point.y < self.height
+    }
+}
+
+/// Point on a map. x is the column, y is the row, (0, 0) is top left.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct MapPoint {
+    // y first to sort points row by row
+    y: usize,
+    x: usize,
+}
+
+impl Display for MapPoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl From<(usize, usize)> for MapPoint {
+    fn from(value: (usize, usize)) -> Self {
+        MapPoint::new(value.0, value.1)
+    }
+}
+
+impl MapPoint {
+    pub fn new(x: usize, y: usize) -> Self {
+        MapPoint { x, y }
+    }
+    pub fn x(&self) -> usize {
+        self.x
+    }
+    pub fn y(&self) -> usize {
+        self.y
+    }
+    /// manhattan distance
+    pub fn distance(&self, other: MapPoint) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+    /// position of point on map of size: Center for inner points, cardinal for points at a side
+    /// and ordinal for corner points
+    pub fn map_position(&self, size: MapSize) -> Compass {
+        let west = self.x == 0;
+        let east = self.x + 1 == size.width;
+        let north = self.y == 0;
+        let south = self.y + 1 == size.height;
+        match (north, east, south, west) {
+            (true, true, _, _) => Compass::NE,
+            (_, true, true, _) => Compass::SE,
+            (_, _, true, true) => Compass::SW,
+            (true, _, _, true) => Compass::NW,
+            (true, ..) => Compass::N,
+            (_, true, ..) => Compass::E,
+            (_, _, true, _) => Compass::S,
+            (.., true) => Compass::W,
+            _ => Compass::Center,
+        }
+    }
+    pub fn is_on_edge(&self, size: MapSize) -> bool {
+        self.map_position(size) != Compass::Center
+    }
+    /// neighbor in orientation, None if neighbor is outside of map of size
+    pub fn neighbor(&self, orientation: Compass, size: MapSize) -> Option<MapPoint> {
+        let (dx, dy) = orientation.delta();
+        let x = self.x.checked_add_signed(dx as isize)?;
+        let y = self.y.checked_add_signed(dy as isize)?;
+        let neighbor = MapPoint { x, y };
+        size.contains(neighbor).then_some(neighbor)
+    }
+    /// iterate neighbors on map of size, starting at orientation, turning clockwise or counterclockwise
+    pub fn iter_neighbors(
+        &self,
+        orientation: Compass,
+        rotation_clockwise: bool,
+        include_center: bool,
+        include_corners: bool,
+        size: MapSize,
+    ) -> impl Iterator<Item = (MapPoint, Compass)> {
+        NeighborIter::new(
+            *self,
+            orientation,
+            rotation_clockwise,
+            include_center,
+            include_corners,
+            size,
+        )
+    }
+    /// iterate all points at edge of map of size once, starting at self, which must be on the edge
+    pub fn iter_edge(&self, clockwise: bool, size: MapSize) -> impl Iterator<Item = MapPoint> {
+        EdgeIter::new(*self, clockwise, size)
+    }
+}
+
+struct NeighborIter {
+    center: MapPoint,
+    size: MapSize,
+    orientations: Vec<Compass>,
+    index: usize,
+}
+
+impl NeighborIter {
+    fn new(
+        center: MapPoint,
+        orientation: Compass,
+        rotation_clockwise: bool,
+        include_center: bool,
+        include_corners: bool,
+        size: MapSize,
+    ) -> Self {
+        let mut orientations: Vec<Compass> = Vec::with_capacity(9);
+        if include_center {
+            orientations.push(Compass::Center);
+        }
+        let mut current = if orientation.is_center() {
+            Compass::N
+        } else if !include_corners && orientation.is_ordinal() {
+            // start at next cardinal orientation
+            if rotation_clockwise {
+                orientation.clockwise()
+            } else {
+                orientation.counterclockwise()
+            }
+        } else {
+            orientation
+        };
+        let num_steps = if include_corners { 8 } else { 4 };
+        for _ in 0..num_steps {
+            orientations.push(current);
+            for _ in 0..(if include_corners { 1 } else { 2 }) {
+                current = if rotation_clockwise {
+                    current.clockwise()
+                } else {
+                    current.counterclockwise()
+                };
+            }
+        }
+        NeighborIter {
+            center,
+            size,
+            orientations,
+            index: 0,
+        }
+    }
+}
+
+impl Iterator for NeighborIter {
+    type Item = (MapPoint, Compass);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.index < self.orientations.len() {
+            let orientation = self.orientations[self.index];
+            self.index += 1;
+            if let Some(neighbor) = self.center.neighbor(orientation, self.size) {
+                return Some((neighbor, orientation));
+            }
+        }
+        None
+    }
+}
+
+struct EdgeIter {
+    current: Option<MapPoint>,
+    start: MapPoint,
+    clockwise: bool,
+    size: MapSize,
+}
+
+impl EdgeIter {
+    fn new(start: MapPoint, clockwise: bool, size: MapSize) -> Self {
+        if !size.contains(start) || !start.is_on_edge(size) {
+            panic!("start point {} of edge iteration is not on edge", start);
+        }
+        EdgeIter {
+            current: Some(start),
+            start,
+            clockwise,
+            size,
+        }
+    }
+    fn step(&self, point: MapPoint) -> Option<MapPoint> {
+        // walking clockwise along the edge: top row east, right column south,
+        // bottom row west, left column north
+        let direction = match (self.clockwise, point.map_position(self.size)) {
+            (true, Compass::N | Compass::NW) => Compass::E,
+            (true, Compass::E | Compass::NE) => Compass::S,
+            (true, Compass::S | Compass::SE) => Compass::W,
+            (true, Compass::W | Compass::SW) => Compass::N,
+            (false, Compass::N | Compass::NE) => Compass::W,
+            (false, Compass::W | Compass::NW) => Compass::S,
+            (false, Compass::S | Compass::SW) => Compass::E,
+            (false, Compass::E | Compass::SE) => Compass::N,
+            _ => return None,
+        };
+        point.neighbor(direction, self.size)
+    }
+}
+
+impl Iterator for EdgeIter {
+    type Item = MapPoint;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.current?;
+        self.current = match self.step(current) {
+            Some(next) if next != self.start => Some(next),
+            _ => None,
+        };
+        Some(current)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_neighbors() {
+        let size = MapSize::new(3, 3);
+        let corner = MapPoint::new(0, 0);
+        let neighbors: Vec<(MapPoint, Compass)> = corner
+            .iter_neighbors(Compass::N, true, false, false, size)
+            .collect();
+        assert_eq!(
+            neighbors,
+            vec![
+                (MapPoint::new(1, 0), Compass::E),
+                (MapPoint::new(0, 1), Compass::S)
+            ]
+        );
+        let center = MapPoint::new(1, 1);
+        assert_eq!(
+            center
+                .iter_neighbors(Compass::N, true, true, true, size)
+                .count(),
+            9
+        );
+        let orientations: Vec<Compass> = center
+            .iter_neighbors(Compass::NE, false, false, false, size)
+            .map(|(_, o)| o)
+            .collect();
+        assert_eq!(
+            orientations,
+            vec![Compass::N, Compass::W, Compass::S, Compass::E]
+        );
+        assert_eq!(center.map_position(size), Compass::Center);
+        assert_eq!(MapPoint::new(2, 0).map_position(size), Compass::NE);
+        assert_eq!(MapPoint::new(2, 0).neighbor(Compass::E, size), None);
+        assert_eq!(center.distance(MapPoint::new(0, 2)), 2);
+    }
+
+    #[test]
+    fn test_edge() {
+        let size = MapSize::new(3, 2);
+        let start = MapPoint::new(1, 0);
+        let edge: Vec<(usize, usize)> = start
+            .iter_edge(true, size)
+            .map(|p| (p.x(), p.y()))
+            .collect();
+        assert_eq!(edge, vec![(1, 0), (2, 0), (2, 1), (1, 1), (0, 1), (0, 0)]);
+        let edge: Vec<(usize, usize)> = start
+            .iter_edge(false, size)
+            .map(|p| (p.x(), p.y()))
+            .collect();
+        assert_eq!(edge, vec![(1, 0), (0, 0), (0, 1), (1, 1), (2, 1), (2, 0)]);
+    }
+}
@@ -0,0 +1,300 @@
+//!my_map_two_dim.rs
+
+use super::my_compass::Compass;
+use super::my_map_point::{MapPoint, MapSize};
+use std::collections::{HashSet, VecDeque};
+use std::fmt::Display;
+
+/// Two dimensional map, which size is set at run time. Cells are stored row by row.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct MyMap2D<T> {
+    size: MapSize,
+    items: Vec<T>,
+}
+
+impl<T: Clone + Default + From<char>> From<&str> for MyMap2D<T> {
+    // size is taken from input; shorter lines are filled with default value
+    fn from(value: &str) -> Self {
+        let lines: Vec<&str> = value
+            .lines()
+            .map(|l| l.trim())
+            .filter(|l| !l.is_empty())
+            .collect();
+        let width = lines
+            .iter()
+            .map(|l| l.chars().count())
+            .max()
+            .unwrap_or_default();
+        let mut map = MyMap2D::init(MapSize::new(width, lines.len()), T::default());
+        for (y, line) in lines.iter().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                map.set(MapPoint::new(x, y), T::from(c));
+            }
+        }
+        map
+    }
+}
+
+impl<T: Display> Display for MyMap2D<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.items.chunks(self.size.width.max(1)) {
+            for item in row.iter() {
+                write!(f, "{}", item)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T> MyMap2D<T> {
+    /// map of size with items row by row
+    pub fn from_vec(size: MapSize, items: Vec<T>) -> Self {
+        if items.len() != size.len() {
+            panic!("{} items do not fit map of size {}", items.len(), size);
+        }
+        MyMap2D { size, items }
+    }
+    pub fn size(&self) -> MapSize {
+        self.size
+    }
+    pub fn width(&self) -> usize {
+        self.size.width
+    }
+    pub fn height(&self) -> usize {
+        self.size.height
+    }
+    fn index(&self, coordinates: MapPoint) -> usize {
+        if !self.size.contains(coordinates) {
+            panic!(
+                "MapPoint {} outside of map of size {}",
+                coordinates, self.size
+            );
+        }
+        coordinates.y() * self.size.width + coordinates.x()
+    }
+    pub fn get(&self, coordinates: MapPoint) -> &T {
+        &self.items[self.index(coordinates)]
+    }
+    pub fn get_mut(&mut self, coordinates: MapPoint) -> &mut T {
+        let index = self.index(coordinates);
+        &mut self.items[index]
+    }
+    /// set value at coordinates and return reference to new value
+    pub fn set(&mut self, coordinates: MapPoint, value: T) -> &T {
+        let index = self.index(coordinates);
+        self.items[index] = value;
+        &self.items[index]
+    }
+    pub fn get_row(&self, row: usize) -> &[T] {
+        &self.items[row * self.size.width..(row + 1) * self.size.width]
+    }
+    pub fn get_row_mut(&mut self, row: usize) -> &mut [T] {
+        &mut self.items[row * self.size.width..(row + 1) * self.size.width]
+    }
+    pub fn apply_column(&mut self, column: usize, values: Vec<T>) {
+        let width = self.size.width;
+        for (item, value) in self.items[column..].iter_mut().step_by(width).zip(values) {
+            *item = value;
+        }
+    }
+    /// iterate all cells row by row
+    pub fn iter(&self) -> impl Iterator<Item = (MapPoint, &T)> {
+        let width = self.size.width;
+        self.items
+            .iter()
+            .enumerate()
+            .map(move |(i, v)| (MapPoint::new(i % width, i / width), v))
+    }
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (MapPoint, &mut T)> {
+        let width = self.size.width;
+        self.items
+            .iter_mut()
+            .enumerate()
+            .map(move |(i, v)| (MapPoint::new(i % width, i / width), v))
+    }
+    pub fn iter_row(&self, row: usize) -> impl Iterator<Item = (MapPoint, &T)> {
+        self.get_row(row)
+            .iter()
+            .enumerate()
+            .map(move |(x, v)| (MapPoint::new(x, row), v))
+    }
+    pub fn iter_column(&self, column: usize) -> impl Iterator<Item = (MapPoint, &T)> {
+        self.items[column..]
+            .iter()
+            .step_by(self.size.width)
+            .enumerate()
+            .map(move |(y, v)| (MapPoint::new(column, y), v))
+    }
+    /// neighbor of point in orientation, None if neighbor is outside of map
+    pub fn neighbor(&self, point: MapPoint, orientation: Compass) -> Option<MapPoint> {
+        point.neighbor(orientation, self.size)
+    }
+    /// position of point on map: Center for inner points, cardinal for points at a side
+    /// and ordinal for corner points
+    pub fn map_position(&self, point: MapPoint) -> Compass {
+        point.map_position(self.size)
+    }
+    /// iterate cardinal neighbors clockwise, starting at N
+    pub fn iter_neighbors(
+        &self,
+        center_point: MapPoint,
+    ) -> impl Iterator<Item = (MapPoint, Compass, &T)> {
+        center_point
+            .iter_neighbors(Compass::N, true, false, false, self.size)
+            .map(move |(p, o)| (p, o, self.get(p)))
+    }
+    /// iterate cardinal and ordinal neighbors clockwise, starting at N
+    pub fn iter_neighbors_with_corners(
+        &self,
+        center_point: MapPoint,
+    ) -> impl Iterator<Item = (MapPoint, Compass, &T)> {
+        center_point
+            .iter_neighbors(Compass::N, true, false, true, self.size)
+            .map(move |(p, o)| (p, o, self.get(p)))
+    }
+    /// iterate all cells at edge of map once, starting at start_point
+    pub fn iter_edge(
+        &self,
+        start_point: MapPoint,
+        clockwise: bool,
+    ) -> impl Iterator<Item = (MapPoint, &T)> {
+        start_point
+            .iter_edge(clockwise, self.size)
+            .map(move |p| (p, self.get(p)))
+    }
+    /// breadth first search from start_point, returning each reached cell with its distance.
+    /// filter_fn decides, if next cell is reachable. Arguments of filter_fn are
+    /// (next point, value of next cell, orientation from current to next cell,
+    /// current point, value of current cell, distance of next cell)
+    pub fn iter_distance<F>(
+        &self,
+        start_point: MapPoint,
+        filter_fn: F,
+    ) -> impl Iterator<Item = (MapPoint, &T, usize)>
+    where
+        F: Fn(MapPoint, &T, Compass, MapPoint, &T, usize) -> bool,
+    {
+        DistanceIter::new(self, start_point, filter_fn)
+    }
+}
+
+impl<T: Clone> MyMap2D<T> {
+    pub fn init(size: MapSize, init_element: T) -> Self {
+        MyMap2D {
+            size,
+            items: vec![init_element; size.len()],
+        }
+    }
+    pub fn get_column(&self, column: usize) -> Vec<T> {
+        self.iter_column(column).map(|(_, v)| v.clone()).collect()
+    }
+}
+
+struct DistanceIter<'a, T, F> {
+    map: &'a MyMap2D<T>,
+    filter_fn: F,
+    next_cells: VecDeque<(MapPoint, usize)>,
+    seen: HashSet<MapPoint>,
+}
+
+impl<'a, T, F> DistanceIter<'a, T, F>
+where
+    F: Fn(MapPoint, &T, Compass, MapPoint, &T, usize) -> bool,
+{
+    fn new(map: &'a MyMap2D<T>, start_point: MapPoint, filter_fn: F) -> Self {
+        let mut next_cells = VecDeque::new();
+        next_cells.push_back((start_point, 0));
+        let mut seen = HashSet::new();
+        seen.insert(start_point);
+        DistanceIter {
+            map,
+            filter_fn,
+            next_cells,
+            seen,
+        }
+    }
+}
+
+impl<'a, T, F> Iterator for DistanceIter<'a, T, F>
+where
+    F: Fn(MapPoint, &T, Compass, MapPoint, &T, usize) -> bool,
+{
+    type Item = (MapPoint, &'a T, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (current_point, distance) = self.next_cells.pop_front()?;
+        let current_value = self.map.get(current_point);
+        for (next_point, orientation, next_value) in self.map.iter_neighbors(current_point) {
+            if self.seen.contains(&next_point) {
+                continue;
+            }
+            if (self.filter_fn)(
+                next_point,
+                next_value,
+                orientation,
+                current_point,
+                current_value,
+                distance + 1,
+            ) {
+                self.seen.insert(next_point);
+                self.next_cells.push_back((next_point, distance + 1));
+            }
+        }
+        Some((current_point, current_value, distance))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_rows_and_columns() {
+        let mut map: MyMap2D<u8> = MyMap2D::init(MapSize::new(3, 2), 0);
+        map.set(MapPoint::new(1, 0), 1);
+        map.set(MapPoint::new(1, 1), 2);
+        assert_eq!(map.get_column(1), vec![1, 2]);
+        map.apply_column(2, vec![3, 4]);
+        assert_eq!(map.get_row(1), &[0, 2, 4]);
+        map.get_row_mut(0)[0] = 5;
+        let row: Vec<u8> = map.iter_row(0).map(|(_, v)| *v).collect();
+        assert_eq!(row, vec![5, 1, 3]);
+        let column: Vec<u8> = map.iter_column(2).map(|(_, v)| *v).collect();
+        assert_eq!(column, vec![3, 4]);
+        assert_eq!(map.iter().nth(4).map(|(p, _)| p), Some(MapPoint::new(1, 1)));
+        assert_eq!(map.to_string(), "513\n024\n");
+        assert_eq!(MyMap2D::from_vec(map.size(), vec![5, 1, 3, 0, 2, 4]), map);
+    }
+
+    #[test]
+    fn test_neighbors_and_edge() {
+        let map: MyMap2D<u8> = MyMap2D::init(MapSize::new(4, 3), 1);
+        let center = MapPoint::new(1, 1);
+        assert_eq!(map.iter_neighbors(center).count(), 4);
+        assert_eq!(map.iter_neighbors_with_corners(center).count(), 8);
+        assert_eq!(map.iter_neighbors(MapPoint::new(0, 0)).count(), 2);
+        assert_eq!(map.iter_edge(MapPoint::new(0, 0), true).count(), 10);
+        assert_eq!(map.map_position(MapPoint::new(3, 1)), Compass::E);
+        assert_eq!(map.neighbor(MapPoint::new(3, 1), Compass::E), None);
+    }
+
+    #[test]
+    fn test_iter_distance() {
+        // walls block the direct way from top left to bottom left
+        let mut map: MyMap2D<bool> = MyMap2D::init(MapSize::new(3, 3), false);
+        map.set(MapPoint::new(0, 1), true);
+        map.set(MapPoint::new(1, 1), true);
+        let distances: Vec<(MapPoint, usize)> = map
+            .iter_distance(MapPoint::new(0, 0), |_, wall, _, _, _, _| !*wall)
+            .map(|(p, _, d)| (p, d))
+            .collect();
+        assert_eq!(distances.len(), 7);
+        assert_eq!(distances.last(), Some(&(MapPoint::new(0, 2), 6)));
+        let limited = map
+            .iter_distance(MapPoint::new(0, 0), |_, wall, _, _, _, d| !*wall && d <= 2)
+            .count();
+        assert_eq!(limited, 3);
+    }
+}
@@ -0,0 +1,302 @@
+//!my_search.rs
+
+use num::Zero;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::fmt::Debug;
+use std::hash::Hash;
+use tracing::debug;
+
+/// distances of visited states from start with predecessor on a shortest path to them
+#[derive(Debug, Clone)]
+pub struct DistanceMap<S, C> {
+    visited: HashMap<S, (C, Option<S>)>,
+}
+
+impl<S: Eq + Hash + Clone, C: Copy> DistanceMap<S, C> {
+    fn new(start: S, zero: C) -> Self {
+        let mut visited = HashMap::new();
+        visited.insert(start, (zero, None));
+        Self { visited }
+    }
+    pub fn distance(&self, state: &S) -> Option<C> {
+        self.visited.get(state).map(|(distance, _)| *distance)
+    }
+    pub fn contains(&self, state: &S) -> bool {
+        self.visited.contains_key(state)
+    }
+    pub fn len(&self) -> usize {
+        self.visited.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.visited.is_empty()
+    }
+    /// iterate visited states with their distance in arbitrary order
+    pub fn iter(&self) -> impl Iterator<Item = (&S, C)> {
+        self.visited
+            .iter()
+            .map(|(state, (distance, _))| (state, *distance))
+    }
+    /// states of shortest path from start to state, if state has been visited
+    pub fn path_to(&self, state: &S) -> Option<Vec<S>> {
+        let mut path = vec![state.clone()];
+        let mut predecessor = self.visited.get(state)?.1.as_ref();
+        while let Some(state) = predecessor {
+            path.push(state.clone());
+            predecessor = self.visited[state].1.as_ref();
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// breadth first search from start. Iterating yields each reached state once with its number of
+/// steps from start in order of steps; neighbors are only requested for yielded states.
+pub fn bfs<S, F, I>(start: S, neighbors: F) -> Bfs<S, F>
+where
+    S: Eq + Hash + Clone,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = S>,
+{
+    let mut queue = VecDeque::new();
+    queue.push_back((start.clone(), 0));
+    Bfs {
+        neighbors,
+        queue,
+        distances: DistanceMap::new(start, 0),
+    }
+}
+
+pub struct Bfs<S, F> {
+    neighbors: F,
+    queue: VecDeque<(S, usize)>,
+    distances: DistanceMap<S, usize>,
+}
+
+impl<S, F, I> Bfs<S, F>
+where
+    S: Eq + Hash + Clone,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = S>,
+{
+    /// finish search and return steps to all reached states
+    pub fn into_distance_map(mut self) -> DistanceMap<S, usize> {
+        while self.next().is_some() {}
+        self.distances
+    }
+}
+
+impl<S, F, I> Iterator for Bfs<S, F>
+where
+    S: Eq + Hash + Clone,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = S>,
+{
+    type Item = (S, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (state, steps) = self.queue.pop_front()?;
+        for next_state in (self.neighbors)(&state) {
+            if !self.distances.contains(&next_state) {
+                self.distances
+                    .visited
+                    .insert(next_state.clone(), (steps + 1, Some(state.clone())));
+                self.queue.push_back((next_state, steps + 1));
+            }
+        }
+        Some((state, steps))
+    }
+}
+
+/// shortest path from start to first state, which is goal, with its cost. Neighbors of a state
+/// are pairs of next state and cost of step. Each state popped from heap is logged as debug event.
+pub fn dijkstra<S, C, F, I, G>(start: S, neighbors: F, is_goal: G) -> Option<(Vec<S>, C)>
+where
+    S: Eq + Hash + Clone + Debug,
+    C: Zero + Ord + Copy + Debug,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, C)>,
+    G: FnMut(&S) -> bool,
+{
+    astar(start, neighbors, |_| C::zero(), is_goal)
+}
+
+/// costs of shortest paths from start to all reachable states
+pub fn dijkstra_all<S, C, F, I>(start: S, neighbors: F) -> DistanceMap<S, C>
+where
+    S: Eq + Hash + Clone + Debug,
+    C: Zero + Ord + Copy + Debug,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, C)>,
+{
+    best_first_search(start, neighbors, |_| C::zero(), |_| false).0
+}
+
+/// like dijkstra, but states are explored in order of cost plus heuristic. Heuristic must never
+/// overestimate remaining cost to a goal and must not decrease by more than cost of a step.
+pub fn astar<S, C, F, I, H, G>(
+    start: S,
+    neighbors: F,
+    heuristic: H,
+    is_goal: G,
+) -> Option<(Vec<S>, C)>
+where
+    S: Eq + Hash + Clone + Debug,
+    C: Zero + Ord + Copy + Debug,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, C)>,
+    H: FnMut(&S) -> C,
+    G: FnMut(&S) -> bool,
+{
+    let (distances, goal) = best_first_search(start, neighbors, heuristic, is_goal);
+    let goal = goal?;
+    Some((distances.path_to(&goal)?, distances.distance(&goal)?))
+}
+
+fn best_first_search<S, C, F, I, H, G>(
+    start: S,
+    mut neighbors: F,
+    mut heuristic: H,
+    mut is_goal: G,
+) -> (DistanceMap<S, C>, Option<S>)
+where
+    S: Eq + Hash + Clone + Debug,
+    C: Zero + Ord + Copy + Debug,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, C)>,
+    H: FnMut(&S) -> C,
+    G: FnMut(&S) -> bool,
+{
+    let mut distances = DistanceMap::new(start.clone(), C::zero());
+    let mut heap = BinaryHeap::new();
+    heap.push(Candidate {
+        estimate: heuristic(&start),
+        cost: C::zero(),
+        state: start,
+    });
+    while let Some(Candidate { cost, state, .. }) = heap.pop() {
+        debug!(?state, ?cost, "heap pop");
+        if is_goal(&state) {
+            return (distances, Some(state));
+        }
+        // a cheaper path to state has been found after pushing this candidate
+        if distances.distance(&state).is_some_and(|d| d < cost) {
+            continue;
+        }
+        for (next_state, step_cost) in neighbors(&state) {
+            let next_cost = cost + step_cost;
+            if distances
+                .distance(&next_state)
+                .is_some_and(|d| d <= next_cost)
+            {
+                continue;
+            }
+            distances
+                .visited
+                .insert(next_state.clone(), (next_cost, Some(state.clone())));
+            heap.push(Candidate {
+                estimate: next_cost + heuristic(&next_state),
+                cost: next_cost,
+                state: next_state,
+            });
+        }
+    }
+    (distances, None)
+}
+
+struct Candidate<S, C> {
+    estimate: C,
+    cost: C,
+    state: S,
+}
+
+impl<S, C: Ord> Ord for Candidate<S, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // reverse order to use std::collections::BinaryHeap as Min-heap by switching position of self and other
+        // see https://doc.rust-lang.org/std/collections/struct.BinaryHeap.html#min-heap
+        // On equal estimate candidates with higher cost are closer to goal.
+        other
+            .estimate
+            .cmp(&self.estimate)
+            .then_with(|| self.cost.cmp(&other.cost))
+    }
+}
+
+impl<S, C: Ord> PartialOrd for Candidate<S, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S, C: Ord> PartialEq for Candidate<S, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<S, C: Ord> Eq for Candidate<S, C> {}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    // weighted edges of a small directed graph
+    fn edges(node: &char) -> Vec<(char, u32)> {
+        match node {
+            'a' => vec![('b', 7), ('c', 2)],
+            'b' => vec![('d', 1)],
+            'c' => vec![('b', 3), ('d', 8)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn test_bfs() {
+        // walk on number line with steps +1 and *2
+        let neighbors = |n: &u32| [n + 1, n * 2].into_iter().filter(|n| *n <= 20);
+        let reached: Vec<(u32, usize)> = bfs(1, neighbors).take_while(|(_, s)| *s <= 2).collect();
+        assert_eq!(reached, vec![(1, 0), (2, 1), (3, 2), (4, 2)]);
+        let distances = bfs(1, neighbors).into_distance_map();
+        assert_eq!(distances.len(), 20);
+        assert_eq!(distances.distance(&20), Some(5));
+        assert_eq!(distances.path_to(&20), Some(vec![1, 2, 4, 5, 10, 20]));
+        assert_eq!(distances.distance(&21), None);
+        assert_eq!(distances.path_to(&21), None);
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        assert_eq!(
+            dijkstra('a', edges, |n| *n == 'd'),
+            Some((vec!['a', 'c', 'b', 'd'], 6))
+        );
+        assert_eq!(dijkstra('b', edges, |n| *n == 'a'), None);
+        let distances = dijkstra_all('a', edges);
+        assert_eq!(distances.len(), 4);
+        assert_eq!(distances.distance(&'b'), Some(5));
+        assert_eq!(distances.path_to(&'a'), Some(vec!['a']));
+    }
+
+    #[test]
+    fn test_astar() {
+        // manhattan distance on open 10 x 10 grid
+        let neighbors = |&(x, y): &(i32, i32)| {
+            [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+                .into_iter()
+                .filter(|(x, y)| (0..10).contains(x) && (0..10).contains(y))
+                .map(|p| (p, 1))
+        };
+        let goal = (7, 4);
+        let heuristic = |&(x, y): &(i32, i32)| (goal.0 - x).abs() + (goal.1 - y).abs();
+        let (path, cost) = astar((0, 0), neighbors, heuristic, |p| *p == goal).unwrap();
+        assert_eq!(cost, 11);
+        assert_eq!(path.len(), 12);
+        assert_eq!(path.first(), Some(&(0, 0)));
+        assert_eq!(path.last(), Some(&goal));
+        assert_eq!(
+            dijkstra((0, 0), neighbors, |p| *p == goal).map(|(_, c)| c),
+            Some(cost)
+        );
+    }
+}
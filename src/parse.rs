@@ -0,0 +1,261 @@
+//!parse.rs
+
+use crate::my_lib::{my_map_point::MapSize, my_map_two_dim::MyMap2D};
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// error of parsing puzzle input with position of offending token.
+/// Parsers create it from a token, which is a slice of puzzle input; day, line and column are
+/// derived from address of token in puzzle input by `locate()`.
+#[derive(Debug, Clone)]
+pub struct ParseError {
+    pub day: u8,
+    /// line of token starting at 1; 0 if not located in puzzle input
+    pub line: usize,
+    /// column of token starting at 1; 0 if not located in puzzle input
+    pub column: usize,
+    pub token: String,
+    pub expected: String,
+    address: Option<usize>,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "day {:02}", self.day)?;
+        if self.line > 0 {
+            write!(f, ", line {}, column {}", self.line, self.column)?;
+        }
+        if self.token.is_empty() {
+            write!(f, ": expected {}, found end of line", self.expected)
+        } else {
+            write!(f, ": expected {}, found `{}`", self.expected, self.token)
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl ParseError {
+    /// error of token, which should be a slice of puzzle input
+    pub fn new(token: &str, expected: impl Into<String>) -> Self {
+        ParseError {
+            day: 0,
+            line: 0,
+            column: 0,
+            token: token.to_string(),
+            expected: expected.into(),
+            address: Some(token.as_ptr() as usize),
+        }
+    }
+    /// error of a single char, which is located by `at()`
+    pub fn char(token: char, expected: impl Into<String>) -> Self {
+        ParseError {
+            address: None,
+            ..ParseError::new(&token.to_string(), expected)
+        }
+    }
+    /// set slice of puzzle input, which contains offending token
+    pub fn at(self, token: &str) -> Self {
+        ParseError {
+            address: Some(token.as_ptr() as usize),
+            ..self
+        }
+    }
+    /// set day and position of token in puzzle input. If token is not a slice of input,
+    /// first occurrence of token is used, unless it is a single char.
+    pub fn locate(&mut self, day: u8, input: &str) {
+        self.day = day;
+        let start = input.as_ptr() as usize;
+        let offset = match self.address {
+            Some(address) if (start..=start + input.len()).contains(&address) => address - start,
+            _ if self.token.chars().count() > 1 => match input.find(&self.token) {
+                Some(offset) => offset,
+                None => return,
+            },
+            _ => return,
+        };
+        let line_start = input[..offset]
+            .rfind('\n')
+            .map(|i| i + 1)
+            .unwrap_or_default();
+        self.line = input[..offset].matches('\n').count() + 1;
+        self.column = input[line_start..offset].chars().count() + 1;
+    }
+}
+
+/// locate a parse error of day inside of err, if there is one
+pub fn locate(mut err: anyhow::Error, day: u8, input: &str) -> anyhow::Error {
+    if let Some(parse_error) = err.downcast_mut::<ParseError>() {
+        parse_error.locate(day, input);
+    }
+    err
+}
+
+/// parse trimmed token as number
+pub fn number<T: FromStr>(token: &str) -> Result<T, ParseError> {
+    let token = token.trim();
+    token
+        .parse::<T>()
+        .map_err(|_| ParseError::new(token, "number"))
+}
+
+/// split s at first occurrence of delimiter
+pub fn split_once<'a>(s: &'a str, delimiter: &str) -> Result<(&'a str, &'a str), ParseError> {
+    s.split_once(delimiter)
+        .ok_or_else(|| ParseError::new(s, format!("`{}`", delimiter)))
+}
+
+/// next token of iter, which splits s
+pub fn next_token<'a>(
+    iter: &mut impl Iterator<Item = &'a str>,
+    s: &'a str,
+    expected: &str,
+) -> Result<&'a str, ParseError> {
+    iter.next()
+        .ok_or_else(|| ParseError::new(&s[s.len()..], expected))
+}
+
+/// fail, if iter, which splits s, has tokens left
+pub fn no_more_tokens<'a>(
+    iter: &mut impl Iterator<Item = &'a str>,
+    expected: &str,
+) -> Result<(), ParseError> {
+    match iter.next() {
+        Some(token) => Err(ParseError::new(token, expected)),
+        None => Ok(()),
+    }
+}
+
+/// size of rectangular grid of chars; empty lines are skipped and lines are trimmed
+pub fn grid_size(input: &str) -> Result<MapSize, ParseError> {
+    let mut rows = input.lines().map(|l| l.trim()).filter(|l| !l.is_empty());
+    let width = match rows.next() {
+        Some(row) => row.chars().count(),
+        None => return Err(ParseError::new(input, "grid of cells")),
+    };
+    let mut height = 1;
+    for row in rows {
+        if let Some((index, c)) = row.char_indices().nth(width) {
+            return Err(ParseError::new(
+                &row[index..index + c.len_utf8()],
+                format!("end of row of {} cells", width),
+            ));
+        }
+        if row.chars().count() < width {
+            return Err(ParseError::new(
+                &row[row.len()..],
+                format!("row of {} cells", width),
+            ));
+        }
+        height += 1;
+    }
+    Ok(MapSize::new(width, height))
+}
+
+/// parse rectangular grid of chars, which sets size of map
+pub fn grid<T>(input: &str) -> Result<MyMap2D<T>, ParseError>
+where
+    T: TryFrom<char, Error = ParseError>,
+{
+    grid_with(input, T::try_from)
+}
+
+/// parse rectangular grid of chars with cell parser
+pub fn grid_with<T, F>(input: &str, cell: F) -> Result<MyMap2D<T>, ParseError>
+where
+    F: Fn(char) -> Result<T, ParseError>,
+{
+    let size = grid_size(input)?;
+    let mut items = Vec::with_capacity(size.len());
+    for line in input.lines().map(|l| l.trim()).filter(|l| !l.is_empty()) {
+        for (index, c) in line.char_indices() {
+            let token = &line[index..index + c.len_utf8()];
+            items.push(cell(c).map_err(|e| e.at(token))?);
+        }
+    }
+    Ok(MyMap2D::from_vec(size, items))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::my_lib::my_map_point::MapPoint;
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Cell(bool);
+
+    impl TryFrom<char> for Cell {
+        type Error = ParseError;
+
+        fn try_from(value: char) -> Result<Self, Self::Error> {
+            match value {
+                '#' => Ok(Cell(true)),
+                '.' => Ok(Cell(false)),
+                _ => Err(ParseError::char(value, "`#` or `.`")),
+            }
+        }
+    }
+
+    #[test]
+    fn test_locate() {
+        let input = "1 2 3\n4 x 6\n";
+        let token = input.lines().nth(1).unwrap().split(' ').nth(1).unwrap();
+        let mut error = number::<u8>(token).unwrap_err();
+        assert_eq!(error.to_string(), "day 00: expected number, found `x`");
+        error.locate(5, input);
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(
+            error.to_string(),
+            "day 05, line 2, column 3: expected number, found `x`"
+        );
+
+        let line = input.lines().next().unwrap();
+        let mut iter = line.split(' ').skip(3);
+        let mut error = next_token(&mut iter, line, "number").unwrap_err();
+        error.locate(5, input);
+        assert_eq!(
+            error.to_string(),
+            "day 05, line 1, column 6: expected number, found end of line"
+        );
+
+        let mut error = ParseError::char('x', "digit");
+        error.locate(9, input);
+        assert_eq!(error.to_string(), "day 09: expected digit, found `x`");
+        let mut error = ParseError::new("4 x", "digit");
+        error.locate(9, input);
+        assert_eq!((error.line, error.column), (2, 1));
+    }
+
+    #[test]
+    fn test_grid() {
+        let map = grid::<Cell>("#..\n.#.\n").unwrap();
+        assert_eq!(map.size(), MapSize::new(3, 2));
+        assert_eq!(*map.get(MapPoint::new(1, 1)), Cell(true));
+        let map = grid::<Cell>("#.\n.#\n..\n#.\n").unwrap();
+        assert_eq!(map.size(), MapSize::new(2, 4));
+        assert_eq!(*map.get(MapPoint::new(0, 3)), Cell(true));
+
+        let input = "#..\n.#?\n";
+        let mut error = grid::<Cell>(input).unwrap_err();
+        error.locate(1, input);
+        assert_eq!(
+            error.to_string(),
+            "day 01, line 2, column 3: expected `#` or `.`, found `?`"
+        );
+        let input = "#..\n.#..\n...\n";
+        let mut error = grid::<Cell>(input).unwrap_err();
+        error.locate(1, input);
+        assert_eq!(
+            error.to_string(),
+            "day 01, line 2, column 4: expected end of row of 3 cells, found `.`"
+        );
+        let input = "#..\n.#.\n..\n";
+        let mut error = grid::<Cell>(input).unwrap_err();
+        error.locate(1, input);
+        assert_eq!(
+            error.to_string(),
+            "day 01, line 3, column 3: expected row of 3 cells, found end of line"
+        );
+    }
+}
@@ -0,0 +1,182 @@
+//!render.rs
+
+use crate::my_lib::my_map_point::{MapPoint, MapSize};
+use crate::my_lib::my_map_two_dim::MyMap2D;
+use anyhow::{anyhow, Context, Result};
+use std::path::Path;
+
+/// pixels per cell in width and height of PPM images
+pub const PPM_CELL_PIXELS: usize = 4;
+/// units per cell in width and height of SVG images
+pub const SVG_CELL_UNITS: usize = 8;
+
+/// colour of a cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+    pub const GREY: Rgb = Rgb(128, 128, 128);
+    pub const DARK_GREY: Rgb = Rgb(48, 48, 48);
+    pub const RED: Rgb = Rgb(220, 40, 40);
+    pub const GREEN: Rgb = Rgb(40, 160, 60);
+    pub const BLUE: Rgb = Rgb(40, 80, 220);
+    pub const YELLOW: Rgb = Rgb(250, 210, 40);
+    pub const ORANGE: Rgb = Rgb(250, 140, 30);
+
+    /// colour between self (t = 0) and other (t = 1)
+    pub fn blend(self, other: Rgb, t: f64) -> Rgb {
+        let t = t.clamp(0.0, 1.0);
+        let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+        Rgb(
+            mix(self.0, other.0),
+            mix(self.1, other.1),
+            mix(self.2, other.2),
+        )
+    }
+    fn hex(&self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+/// grid of coloured cells, which is written as PPM or SVG image
+#[derive(Debug, Clone, PartialEq)]
+pub struct Image {
+    size: MapSize,
+    cells: Vec<Rgb>,
+}
+
+impl Image {
+    /// image of given size with colour of each cell
+    pub fn new(size: MapSize, colour: impl Fn(MapPoint) -> Rgb) -> Self {
+        let cells = (0..size.height)
+            .flat_map(|y| (0..size.width).map(move |x| MapPoint::new(x, y)))
+            .map(colour)
+            .collect();
+        Self { size, cells }
+    }
+    /// image of map with colour of each cell depending on its position and value
+    pub fn from_map<T>(map: &MyMap2D<T>, colour: impl Fn(MapPoint, &T) -> Rgb) -> Self {
+        Self::new(map.size(), |p| colour(p, map.get(p)))
+    }
+    pub fn size(&self) -> MapSize {
+        self.size
+    }
+    pub fn get(&self, point: MapPoint) -> Rgb {
+        self.cells[point.y() * self.size.width + point.x()]
+    }
+    /// binary PPM (P6) with PPM_CELL_PIXELS x PPM_CELL_PIXELS pixels per cell
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let (width, height) = (
+            self.size.width * PPM_CELL_PIXELS,
+            self.size.height * PPM_CELL_PIXELS,
+        );
+        let mut ppm = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+        ppm.reserve(width * height * 3);
+        for row in self.cells.chunks(self.size.width.max(1)) {
+            for _ in 0..PPM_CELL_PIXELS {
+                for cell in row {
+                    for _ in 0..PPM_CELL_PIXELS {
+                        ppm.extend([cell.0, cell.1, cell.2]);
+                    }
+                }
+            }
+        }
+        ppm
+    }
+    /// SVG with one rect per run of cells of same colour in a row
+    pub fn to_svg(&self) -> String {
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\" shape-rendering=\"crispEdges\">\n",
+            self.size.width * SVG_CELL_UNITS,
+            self.size.height * SVG_CELL_UNITS,
+            self.size.width,
+            self.size.height
+        );
+        for (y, row) in self.cells.chunks(self.size.width.max(1)).enumerate() {
+            let mut x = 0;
+            for run in row.chunk_by(|a, b| a == b) {
+                svg.push_str(&format!(
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"1\" fill=\"{}\"/>\n",
+                    x,
+                    y,
+                    run.len(),
+                    run[0].hex()
+                ));
+                x += run.len();
+            }
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+    /// writes image as PPM or SVG depending on extension of path
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let image = match path.extension().and_then(|e| e.to_str()) {
+            Some("ppm") => self.to_ppm(),
+            Some("svg") => self.to_svg().into_bytes(),
+            _ => {
+                return Err(anyhow!(
+                    "`{}` is neither a `.ppm` nor a `.svg` file",
+                    path.display()
+                ))
+            }
+        };
+        std::fs::write(path, image).with_context(|| format!("cannot write `{}`", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::cli::Cli;
+    use clap::Parser;
+
+    #[test]
+    fn test_image() {
+        let map: MyMap2D<char> = MyMap2D::from("#..\n.##");
+        let image = Image::from_map(&map, |_, c| match c {
+            '#' => Rgb::BLACK,
+            _ => Rgb::WHITE,
+        });
+        assert_eq!(image.size(), MapSize::new(3, 2));
+        assert_eq!(image.get(MapPoint::new(2, 1)), Rgb::BLACK);
+        assert_eq!(image.get(MapPoint::new(2, 0)), Rgb::WHITE);
+
+        let ppm = image.to_ppm();
+        let header = format!("P6\n{} {}\n255\n", 3 * PPM_CELL_PIXELS, 2 * PPM_CELL_PIXELS);
+        assert!(ppm.starts_with(header.as_bytes()));
+        assert_eq!(
+            ppm.len(),
+            header.len() + 6 * PPM_CELL_PIXELS * PPM_CELL_PIXELS * 3
+        );
+        // last pixel is bottom right cell
+        assert_eq!(ppm[ppm.len() - 3..], [0, 0, 0]);
+
+        let svg = image.to_svg();
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.contains("viewBox=\"0 0 3 2\""));
+        // runs of same colour are merged
+        assert_eq!(svg.matches("<rect ").count(), 4);
+        assert!(svg.contains("<rect x=\"1\" y=\"1\" width=\"2\" height=\"1\" fill=\"#000000\"/>"));
+        assert!(image.write(Path::new("image.png")).is_err());
+    }
+
+    #[test]
+    fn test_blend() {
+        assert_eq!(Rgb::BLACK.blend(Rgb::WHITE, 0.0), Rgb::BLACK);
+        assert_eq!(Rgb::BLACK.blend(Rgb::WHITE, 1.0), Rgb::WHITE);
+        assert_eq!(Rgb::BLACK.blend(Rgb(200, 100, 50), 0.5), Rgb(100, 50, 25));
+        assert_eq!(Rgb::BLACK.blend(Rgb::WHITE, 2.0), Rgb::WHITE);
+    }
+
+    #[test]
+    fn test_render_flag() {
+        let cli = Cli::parse_from(["aoc", "16", "--render", "chamber.svg"]);
+        assert_eq!(
+            cli.run.render,
+            Some(std::path::PathBuf::from("chamber.svg"))
+        );
+    }
+}
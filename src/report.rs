@@ -0,0 +1,481 @@
+//!report.rs
+
+use crate::answers::Verification;
+use crate::mem::{format_bytes, MemStats};
+use anyhow::Result;
+use clap::ValueEnum;
+use serde::Serialize;
+use std::fmt::Write;
+use std::time::Duration;
+
+/// output format of run report
+#[derive(Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// one line per part, printed while solving
+    #[default]
+    Text,
+    Json,
+    Csv,
+    Markdown,
+}
+
+/// status of one part of a day
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Match,
+    Mismatch,
+    Unknown,
+    Skipped,
+    TimedOut,
+    Error,
+}
+
+impl Status {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Status::Match => "match",
+            Status::Mismatch => "mismatch",
+            Status::Unknown => "unknown",
+            Status::Skipped => "skipped",
+            Status::TimedOut => "timed_out",
+            Status::Error => "error",
+        }
+    }
+}
+
+/// report of one part of a day
+#[derive(Debug, Clone, Serialize)]
+pub struct PartReport {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub status: Status,
+    pub expected: Option<String>,
+    pub parse_time_ms: f64,
+    pub solve_time_ms: f64,
+    pub error: Option<String>,
+    /// answer is taken from result cache with times of run, which solved it
+    pub cached: bool,
+    /// allocations of part with `--profile-mem`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory: Option<MemStats>,
+}
+
+impl PartReport {
+    pub fn answer(
+        day: u8,
+        part: u8,
+        answer: String,
+        verification: Verification,
+        parse_time: Duration,
+        solve_time: Duration,
+    ) -> Self {
+        let (status, expected) = match verification {
+            Verification::Match => (Status::Match, None),
+            Verification::Mismatch { expected } => (Status::Mismatch, Some(expected)),
+            Verification::Unknown => (Status::Unknown, None),
+        };
+        PartReport {
+            day,
+            part,
+            answer: Some(answer),
+            status,
+            expected,
+            parse_time_ms: as_ms(parse_time),
+            solve_time_ms: as_ms(solve_time),
+            error: None,
+            cached: false,
+            memory: None,
+        }
+    }
+    pub fn skipped(day: u8, part: u8) -> Self {
+        PartReport {
+            day,
+            part,
+            answer: None,
+            status: Status::Skipped,
+            expected: None,
+            parse_time_ms: 0.0,
+            solve_time_ms: 0.0,
+            error: None,
+            cached: false,
+            memory: None,
+        }
+    }
+    pub fn timed_out(day: u8, part: u8, time_budget: Duration, parse_time: Duration) -> Self {
+        PartReport {
+            day,
+            part,
+            answer: None,
+            status: Status::TimedOut,
+            expected: None,
+            parse_time_ms: as_ms(parse_time),
+            solve_time_ms: 0.0,
+            error: Some(format!(
+                "time budget of {:.1} s exceeded",
+                time_budget.as_secs_f64()
+            )),
+            cached: false,
+            memory: None,
+        }
+    }
+    pub fn error(
+        day: u8,
+        part: u8,
+        error: &anyhow::Error,
+        parse_time: Duration,
+        solve_time: Duration,
+    ) -> Self {
+        PartReport {
+            day,
+            part,
+            answer: None,
+            status: Status::Error,
+            expected: None,
+            parse_time_ms: as_ms(parse_time),
+            solve_time_ms: as_ms(solve_time),
+            error: Some(format!("{:#}", error)),
+            cached: false,
+            memory: None,
+        }
+    }
+    /// answer marked, if it is taken from result cache
+    pub fn answer_with_mark(&self) -> String {
+        let answer = self.answer.as_deref().unwrap_or_default();
+        if self.cached {
+            format!("{} (cached)", answer)
+        } else {
+            answer.to_string()
+        }
+    }
+    /// line of text output; verification status is only shown in verify mode
+    pub fn text_line(&self, verify: bool) -> String {
+        let line = match (self.status, &self.answer) {
+            (Status::Skipped, _) => format!(
+                "day {:02} part {} skipped because of long run time (use --include-slow)",
+                self.day, self.part
+            ),
+            (Status::TimedOut, _) => format!(
+                "day {:02} part {} timed out: {}",
+                self.day,
+                self.part,
+                self.error.as_deref().unwrap_or_default()
+            ),
+            (Status::Error, _) | (_, None) => format!(
+                "day {:02} part {} failed: {}",
+                self.day,
+                self.part,
+                self.error.as_deref().unwrap_or_default()
+            ),
+            (status, Some(answer)) => {
+                let mut line = format!("result day {:02} part {}: {}", self.day, self.part, answer);
+                if self.cached {
+                    line += " (cached)";
+                }
+                if verify {
+                    match &self.expected {
+                        Some(expected) => {
+                            write!(line, " -> {} (expected {})", status.as_str(), expected)
+                        }
+                        None => write!(line, " -> {}", status.as_str()),
+                    }
+                    .unwrap();
+                }
+                line
+            }
+        };
+        match self.memory {
+            Some(memory) => format!("{} [{}]", line, memory),
+            None => line,
+        }
+    }
+}
+
+fn as_ms(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1_000.0
+}
+
+/// wall clock time of one day including loading of input
+#[derive(Debug, Clone, Serialize)]
+pub struct DayTime {
+    pub day: u8,
+    pub elapsed_ms: f64,
+    /// allocations of parsing and solving day with `--profile-mem`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory: Option<MemStats>,
+}
+
+impl DayTime {
+    pub fn new(day: u8, elapsed: Duration) -> Self {
+        DayTime {
+            day,
+            elapsed_ms: as_ms(elapsed),
+            memory: None,
+        }
+    }
+    pub fn text_line(&self) -> String {
+        let line = format!("day {:02} took {:.3} ms", self.day, self.elapsed_ms);
+        match self.memory {
+            Some(memory) => format!("{} [{}]", line, memory),
+            None => line,
+        }
+    }
+}
+
+/// report of all solved parts in order of days
+#[derive(Debug, Default, Serialize)]
+pub struct Report {
+    pub parts: Vec<PartReport>,
+    pub days: Vec<DayTime>,
+    /// wall clock time of whole run
+    pub elapsed_ms: f64,
+}
+
+impl Report {
+    pub fn push(&mut self, part_report: PartReport) {
+        self.parts.push(part_report);
+    }
+    pub fn push_day(&mut self, day_time: DayTime) {
+        self.days.push(day_time);
+    }
+    pub fn set_elapsed(&mut self, elapsed: Duration) {
+        self.elapsed_ms = as_ms(elapsed);
+    }
+    pub fn elapsed_line(&self) -> String {
+        format!("total elapsed time {:.3} ms", self.elapsed_ms)
+    }
+    pub fn count(&self, status: Status) -> usize {
+        self.parts.iter().filter(|p| p.status == status).count()
+    }
+    pub fn render(&self, format: Format, verify: bool) -> Result<String> {
+        match format {
+            Format::Text => {
+                let mut text = String::new();
+                for day_time in self.days.iter() {
+                    for p in self.parts.iter().filter(|p| p.day == day_time.day) {
+                        writeln!(text, "{}", p.text_line(verify)).unwrap();
+                    }
+                    writeln!(text, "{}", day_time.text_line()).unwrap();
+                }
+                writeln!(text, "{}", self.elapsed_line()).unwrap();
+                Ok(text)
+            }
+            Format::Json => Ok(serde_json::to_string_pretty(self)? + "\n"),
+            Format::Csv => Ok(self.render_csv()),
+            Format::Markdown => Ok(self.render_markdown()),
+        }
+    }
+    /// reports contain allocations of `--profile-mem`
+    fn has_memory(&self) -> bool {
+        self.parts.iter().any(|p| p.memory.is_some())
+    }
+    fn render_csv(&self) -> String {
+        let mut csv = String::from(
+            "day,part,answer,status,expected,parse_time_ms,solve_time_ms,error,cached",
+        );
+        if self.has_memory() {
+            csv += ",allocations,allocated_bytes,peak_bytes";
+        }
+        csv += "\n";
+        for p in self.parts.iter() {
+            write!(
+                csv,
+                "{},{},{},{},{},{:.3},{:.3},{},{}",
+                p.day,
+                p.part,
+                csv_field(p.answer.as_deref()),
+                p.status.as_str(),
+                csv_field(p.expected.as_deref()),
+                p.parse_time_ms,
+                p.solve_time_ms,
+                csv_field(p.error.as_deref()),
+                p.cached
+            )
+            .unwrap();
+            if self.has_memory() {
+                let m = p.memory.unwrap_or_default();
+                write!(
+                    csv,
+                    ",{},{},{}",
+                    m.allocations, m.allocated_bytes, m.peak_bytes
+                )
+                .unwrap();
+            }
+            csv += "\n";
+        }
+        csv
+    }
+    fn render_markdown(&self) -> String {
+        let mut md = String::from(
+            "| day | part | answer | status | parse time (ms) | solve time (ms) | error |",
+        );
+        if self.has_memory() {
+            md += " allocations | allocated | peak |";
+        }
+        md += "\n|----:|-----:|-------:|:------:|----------------:|----------------:|:------|";
+        if self.has_memory() {
+            md += "------------:|----------:|-----:|";
+        }
+        md += "\n";
+        for p in self.parts.iter() {
+            write!(
+                md,
+                "| {} | {} | {} | {} | {:.3} | {:.3} | {} |",
+                p.day,
+                p.part,
+                p.answer_with_mark(),
+                p.status.as_str(),
+                p.parse_time_ms,
+                p.solve_time_ms,
+                p.error.as_deref().unwrap_or_default().replace('|', "\\|")
+            )
+            .unwrap();
+            if let Some(m) = p.memory {
+                write!(md, "{}", memory_columns(&m)).unwrap();
+            } else if self.has_memory() {
+                md += "  |  |  |";
+            }
+            md += "\n";
+        }
+        if !self.days.is_empty() {
+            md += "\n| day | elapsed time (ms) |";
+            if self.has_memory() {
+                md += " allocations | allocated | peak |";
+            }
+            md += "\n|----:|------------------:|";
+            if self.has_memory() {
+                md += "------------:|----------:|-----:|";
+            }
+            md += "\n";
+            for d in self.days.iter() {
+                write!(md, "| {} | {:.3} |", d.day, d.elapsed_ms).unwrap();
+                if let Some(m) = d.memory {
+                    write!(md, "{}", memory_columns(&m)).unwrap();
+                } else if self.has_memory() {
+                    md += "  |  |  |";
+                }
+                md += "\n";
+            }
+        }
+        writeln!(md, "\n{}", self.elapsed_line()).unwrap();
+        md
+    }
+}
+
+/// columns of markdown table with allocations
+fn memory_columns(memory: &MemStats) -> String {
+    format!(
+        " {} | {} | {} |",
+        memory.allocations,
+        format_bytes(memory.allocated_bytes),
+        format_bytes(memory.peak_bytes)
+    )
+}
+
+/// quote csv field, if it contains separator, quotes or line breaks
+pub(crate) fn csv_field(field: Option<&str>) -> String {
+    match field {
+        Some(f) if f.contains([',', '"', '\n']) => format!("\"{}\"", f.replace('"', "\"\"")),
+        Some(f) => f.to_string(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use anyhow::anyhow;
+
+    #[test]
+    fn test_render() {
+        let mut report = Report::default();
+        report.push(PartReport::answer(
+            1,
+            1,
+            "42".into(),
+            Verification::Match,
+            Duration::from_millis(1),
+            Duration::from_millis(2),
+        ));
+        report.push(PartReport::error(
+            1,
+            2,
+            &anyhow!("bad input, \"line 3\""),
+            Duration::from_millis(1),
+            Duration::ZERO,
+        ));
+        report.push(PartReport::skipped(17, 1));
+        report.push(PartReport::timed_out(
+            17,
+            2,
+            Duration::from_secs(5),
+            Duration::from_millis(1),
+        ));
+
+        report.push_day(DayTime::new(1, Duration::from_millis(4)));
+        report.push_day(DayTime::new(17, Duration::from_secs(5)));
+        report.set_elapsed(Duration::from_millis(5_004));
+
+        let csv = report.render(Format::Csv, false).unwrap();
+        let mut lines = csv.lines().skip(1);
+        assert_eq!(lines.next(), Some("1,1,42,match,,1.000,2.000,,false"));
+        assert_eq!(
+            lines.next(),
+            Some("1,2,,error,,1.000,0.000,\"bad input, \"\"line 3\"\"\",false")
+        );
+        assert_eq!(lines.next(), Some("17,1,,skipped,,0.000,0.000,,false"));
+        assert_eq!(
+            lines.next(),
+            Some("17,2,,timed_out,,1.000,0.000,time budget of 5.0 s exceeded,false")
+        );
+
+        let json: serde_json::Value =
+            serde_json::from_str(&report.render(Format::Json, false).unwrap()).unwrap();
+        assert_eq!(json["parts"][0]["answer"], "42");
+        assert_eq!(json["parts"][0]["status"], "match");
+        assert_eq!(json["parts"][1]["error"], "bad input, \"line 3\"");
+        assert_eq!(json["days"][1]["day"], 17);
+        assert_eq!(json["elapsed_ms"], 5004.0);
+
+        let markdown = report.render(Format::Markdown, false).unwrap();
+        assert_eq!(markdown.lines().count(), 13);
+        assert!(markdown.contains("| 1 | 1 | 42 | match | 1.000 | 2.000 |  |"));
+
+        assert_eq!(
+            report.parts[0].text_line(true),
+            "result day 01 part 1: 42 -> match"
+        );
+        assert_eq!(
+            report.parts[3].text_line(false),
+            "day 17 part 2 timed out: time budget of 5.0 s exceeded"
+        );
+        assert_eq!(report.count(Status::Error), 1);
+        report.parts[0].cached = true;
+        assert_eq!(
+            report.parts[0].text_line(true),
+            "result day 01 part 1: 42 (cached) -> match"
+        );
+        report.parts[0].cached = false;
+        report.parts[0].memory = Some(MemStats {
+            allocations: 3,
+            allocated_bytes: 2_048,
+            peak_bytes: 1_024,
+        });
+        assert_eq!(
+            report.parts[0].text_line(false),
+            "result day 01 part 1: 42 [3 allocations, 2.0 KiB allocated, peak 1.0 KiB]"
+        );
+        let csv = report.render(Format::Csv, false).unwrap();
+        assert!(csv.lines().next().unwrap().ends_with(",peak_bytes"));
+        assert!(csv.lines().nth(1).unwrap().ends_with(",3,2048,1024"));
+        assert!(csv.lines().nth(3).unwrap().ends_with(",0,0,0"));
+        report.parts[0].memory = None;
+
+        let text = report.render(Format::Text, false).unwrap();
+        let mut lines = text.lines().skip(2);
+        assert_eq!(lines.next(), Some("day 01 took 4.000 ms"));
+        assert_eq!(lines.last(), Some("total elapsed time 5004.000 ms"));
+    }
+}
@@ -0,0 +1,270 @@
+//!scaffold.rs
+
+use crate::cli::NewArgs;
+use crate::input;
+use crate::years::DEFAULT_YEAR;
+use anyhow::{anyhow, Context, Result};
+use std::path::{Path, PathBuf};
+
+/// add skeleton of a day to crate in current directory and register it
+pub fn run_new(args: &NewArgs) -> Result<()> {
+    let root = Path::new(".");
+    if !root.join("src").join("lib.rs").exists() {
+        return Err(anyhow!("`new` must run in root directory of crate"));
+    }
+    for path in scaffold(root, &input::input_dir(), args.year, args.day)? {
+        println!("{}", path.display());
+    }
+    Ok(())
+}
+
+/// module of days of year in `src`
+fn year_module(year: u16) -> String {
+    if year == DEFAULT_YEAR {
+        String::from("days")
+    } else {
+        format!("year_{}", year)
+    }
+}
+
+/// writes skeleton of day with a test stub and an empty puzzle input and registers day. First day
+/// of a year creates module of year and registers year. Returns created and changed files.
+pub fn scaffold(root: &Path, input_dir: &Path, year: u16, day: u8) -> Result<Vec<PathBuf>> {
+    let src = root.join("src");
+    let module = year_module(year);
+    let module_dir = src.join(&module);
+    let day_file = module_dir.join(format!("day_{:02}.rs", day));
+    if day_file.exists() {
+        return Err(anyhow!(
+            "day {:02} of year {} exists: `{}`",
+            day,
+            year,
+            day_file.display()
+        ));
+    }
+    let mut changed = Vec::with_capacity(5);
+    let registry = module_dir.join("mod.rs");
+    if registry.exists() {
+        edit(&registry, |text| {
+            let text = insert_sorted(text, "pub mod day_", &format!("pub mod day_{:02};", day))?;
+            insert_sorted(
+                &text,
+                "    &day_",
+                &format!("    &day_{:02}::Day{:02},", day, day),
+            )
+        })?;
+    } else {
+        std::fs::create_dir_all(&module_dir)
+            .with_context(|| format!("cannot create `{}`", module_dir.display()))?;
+        write(&registry, &year_skeleton(year, day))?;
+        let lib = src.join("lib.rs");
+        edit(&lib, |text| {
+            insert_sorted(text, "pub mod ", &format!("pub mod {};", module))
+        })?;
+        let years = src.join("years.rs");
+        edit(&years, |text| {
+            insert_sorted(
+                text,
+                "    (",
+                &format!("    ({}, crate::{}::DAYS),", year, module),
+            )
+        })?;
+        changed.extend([lib, years]);
+    }
+    changed.push(registry);
+    write(&day_file, &day_skeleton(day))?;
+    changed.push(day_file);
+    // placeholder of puzzle input, which is replaced by your own input
+    let input_file = input::input_path(&input::year_input_dir(input_dir, year), day);
+    if !input_file.exists() {
+        if let Some(dir) = input_file.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("cannot create `{}`", dir.display()))?;
+        }
+        write(&input_file, "")?;
+        changed.push(input_file);
+    }
+    Ok(changed)
+}
+
+fn write(path: &Path, text: &str) -> Result<()> {
+    std::fs::write(path, text).with_context(|| format!("cannot write `{}`", path.display()))
+}
+
+fn edit(path: &Path, change: impl FnOnce(&str) -> Result<String>) -> Result<()> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("cannot read `{}`", path.display()))?;
+    let text = change(&text).with_context(|| format!("cannot register in `{}`", path.display()))?;
+    write(path, &text)
+}
+
+/// inserts line in order of lines starting with prefix; existing line is not inserted again
+fn insert_sorted(text: &str, prefix: &str, line: &str) -> Result<String> {
+    let mut lines: Vec<&str> = text.lines().collect();
+    if lines.contains(&line) {
+        return Ok(text.to_string());
+    }
+    let prefixed: Vec<usize> = (0..lines.len())
+        .filter(|i| lines[*i].starts_with(prefix))
+        .collect();
+    let Some(first) = prefixed.first() else {
+        return Err(anyhow!("no line starting with `{}`", prefix));
+    };
+    let index = prefixed
+        .iter()
+        .rev()
+        .find(|i| lines[**i] < line)
+        .map(|i| i + 1)
+        .unwrap_or(*first);
+    lines.insert(index, line);
+    Ok(lines.join("\n") + "\n")
+}
+
+fn year_skeleton(year: u16, day: u8) -> String {
+    format!(
+        "//!mod.rs
+//pub mod year_{year}
+
+pub mod day_{day:02};
+
+use crate::solution::Solver;
+
+/// registry of days of {year} in order of advent calendar
+pub static DAYS: &[&dyn Solver] = &[
+    &day_{day:02}::Day{day:02},
+    // `new <DAY> --year {year}` inserts further days in order of days
+];
+"
+    )
+}
+
+fn day_skeleton(day: u8) -> String {
+    format!(
+        "//!day_{day:02}.rs
+
+use crate::generate::InputRng;
+use crate::solution::Solution;
+use anyhow::{{anyhow, Result}};
+
+pub struct Day{day:02};
+
+impl Solution for Day{day:02} {{
+    const DAY: u8 = {day};
+    const GEN_SIZE: (usize, &'static str) = (0, \"no input generator\");
+    /// lines of puzzle input
+    type Input<'a> = Vec<&'a str>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>> {{
+        Ok(input.lines().collect())
+    }}
+    fn part_1(_input: &Self::Input<'_>) -> Result<Self::Answer1> {{
+        Err(anyhow!(\"part 1 of day {day:02} is not solved yet\"))
+    }}
+    fn part_2(_input: &Self::Input<'_>) -> Result<Self::Answer2> {{
+        Err(anyhow!(\"part 2 of day {day:02} is not solved yet\"))
+    }}
+    fn generate(_rng: &mut InputRng, _size: usize) -> Result<String> {{
+        Err(anyhow!(\"no input generator for day {day:02}\"))
+    }}
+}}
+
+#[cfg(test)]
+mod tests {{
+
+    use super::*;
+
+    #[test]
+    fn test_example() -> Result<()> {{
+        // example of puzzle description
+        let input = \"\";
+        let _input = Day{day:02}::parse(input)?;
+        // assert_eq!(Day{day:02}::part_1(&_input)?, 0);
+        Ok(())
+    }}
+}}
+"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::cli::{Cli, Command};
+    use clap::Parser;
+
+    #[test]
+    fn test_insert_sorted() {
+        let text = "pub mod day_01;\npub mod day_05;\n\nuse x;\n";
+        assert_eq!(
+            insert_sorted(text, "pub mod day_", "pub mod day_03;").unwrap(),
+            "pub mod day_01;\npub mod day_03;\npub mod day_05;\n\nuse x;\n"
+        );
+        assert_eq!(
+            insert_sorted(text, "pub mod day_", "pub mod day_07;").unwrap(),
+            "pub mod day_01;\npub mod day_05;\npub mod day_07;\n\nuse x;\n"
+        );
+        assert_eq!(
+            insert_sorted(text, "pub mod day_", "pub mod day_05;").unwrap(),
+            text
+        );
+        assert!(insert_sorted(text, "    &day_", "    &day_02::Day02,").is_err());
+    }
+
+    #[test]
+    fn test_scaffold() {
+        let root = std::env::temp_dir().join(format!("aoc_scaffold_{}", std::process::id()));
+        let input_dir = root.join("assets");
+        let src = root.join("src");
+        std::fs::create_dir_all(src.join("days")).unwrap();
+        std::fs::write(src.join("days").join("day_01.rs"), "").unwrap();
+        std::fs::write(src.join("lib.rs"), "pub mod days;\npub mod years;\n").unwrap();
+        std::fs::write(
+            src.join("years.rs"),
+            "static YEARS: &[(u16, &[&dyn Solver])] = &[\n    (2023, &crate::days::DAYS),\n];\n",
+        )
+        .unwrap();
+        let read = |path: &[&str]| {
+            std::fs::read_to_string(path.iter().fold(root.clone(), |p, s| p.join(s))).unwrap()
+        };
+
+        // first day of new year registers year
+        let changed = scaffold(&root, &input_dir, 2024, 3).unwrap();
+        assert_eq!(changed.len(), 5);
+        assert_eq!(
+            read(&["src", "lib.rs"]),
+            "pub mod days;\npub mod year_2024;\npub mod years;\n"
+        );
+        assert!(read(&["src", "years.rs"])
+            .contains("    (2023, &crate::days::DAYS),\n    (2024, crate::year_2024::DAYS),\n];"));
+        assert!(read(&["src", "year_2024", "day_03.rs"]).contains("const DAY: u8 = 3;"));
+        assert_eq!(read(&["assets", "2024", "day_03.txt"]), "");
+
+        // further days are registered in order of days
+        let changed = scaffold(&root, &input_dir, 2024, 1).unwrap();
+        assert_eq!(changed.len(), 3);
+        let registry = read(&["src", "year_2024", "mod.rs"]);
+        assert!(registry.contains("pub mod day_01;\npub mod day_03;\n"));
+        assert!(registry.contains("    &day_01::Day01,\n    &day_03::Day03,\n"));
+
+        // existing days are not overwritten
+        assert!(scaffold(&root, &input_dir, 2024, 3).is_err());
+        assert!(scaffold(&root, &input_dir, DEFAULT_YEAR, 1).is_err());
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_new_args() {
+        let cli = Cli::parse_from(["aoc", "new", "3", "--year", "2024"]);
+        match cli.command {
+            Some(Command::New(new)) => {
+                assert_eq!(new.day, 3);
+                assert_eq!(new.year, 2024);
+            }
+            _ => panic!("expected new command"),
+        }
+        assert!(Cli::try_parse_from(["aoc", "new", "26"]).is_err());
+    }
+}
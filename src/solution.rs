@@ -0,0 +1,266 @@
+//!solution.rs
+
+use crate::generate::InputRng;
+use crate::parse;
+use crate::render::Image;
+use crate::step::Simulation;
+use anyhow::Result;
+use std::fmt::Display;
+use std::time::{Duration, Instant};
+use tracing::{info, info_span, warn};
+
+/// stack size of threads, which solve days; some days recurse deeply
+pub const SOLVER_STACK_SIZE: usize = 64 * 1024 * 1024;
+
+/// solution of one day, split into parsing of input and solving of both parts
+pub trait Solution {
+    /// day of advent calendar
+    const DAY: u8;
+    /// number of parts of day; day 25 has only one part
+    const PARTS: u8 = 2;
+    /// parts with long run time, which are only solved on request
+    const SLOW_PARTS: &'static [u8] = &[];
+    /// parsed puzzle input, which may borrow from raw input
+    type Input<'a>;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>>;
+    fn part_1(input: &Self::Input<'_>) -> Result<Self::Answer1>;
+    fn part_2(input: &Self::Input<'_>) -> Result<Self::Answer2>;
+
+    /// default size of generated inputs and its meaning, e.g. `(140, "width of grid")`
+    const GEN_SIZE: (usize, &'static str);
+    /// random but valid puzzle input of given size
+    fn generate(rng: &mut InputRng, size: usize) -> Result<String>;
+
+    /// image of grid of day, e.g. state of grid after solving; days without grid have no image
+    fn render(_input: &Self::Input<'_>) -> Result<Option<Image>> {
+        Ok(None)
+    }
+    /// simulation of day, which is advanced step by step; most days have no simulation
+    fn simulation<'a>(_input: &'a Self::Input<'_>) -> Result<Option<Box<dyn Simulation + 'a>>> {
+        Ok(None)
+    }
+}
+
+/// parts of a day to solve
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum Parts {
+    #[default]
+    Both,
+    One,
+    Two,
+}
+
+impl From<Option<u8>> for Parts {
+    fn from(value: Option<u8>) -> Self {
+        match value {
+            Some(1) => Parts::One,
+            Some(2) => Parts::Two,
+            _ => Parts::Both,
+        }
+    }
+}
+
+impl Parts {
+    pub fn contains(&self, part: u8) -> bool {
+        matches!(
+            (self, part),
+            (Parts::Both, 1 | 2) | (Parts::One, 1) | (Parts::Two, 2)
+        )
+    }
+}
+
+/// run time tier of a part of a day
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tier {
+    Fast,
+    /// only solved with `--include-slow`
+    Slow,
+}
+
+/// answer of one part of a day
+#[derive(Debug)]
+pub struct Answer {
+    pub part: u8,
+    pub value: Result<String>,
+    pub solve_time: Duration,
+}
+
+/// parse time and answers of selected parts of a day
+#[derive(Debug)]
+pub struct Solved {
+    pub parse_time: Duration,
+    /// error of parsing, if input could not be parsed
+    pub answers: Result<Vec<Answer>>,
+}
+
+/// progress of solving a day, which is reported after each step
+#[derive(Debug)]
+pub enum Progress {
+    Parsed(Duration),
+    ParseFailed(Duration, anyhow::Error),
+    Solved(Answer),
+}
+
+/// measured times of repeated parsing and solving of selected parts of a day
+#[derive(Debug)]
+pub struct Timings {
+    pub parse: Vec<Duration>,
+    pub parts: Vec<(u8, Vec<Duration>)>,
+}
+
+/// object safe wrapper of Solution, which is used by registry of days
+pub trait Solver: Sync {
+    fn day(&self) -> u8;
+    fn parts(&self) -> u8;
+    fn tier(&self, part: u8) -> Tier;
+    /// parse input and solve selected parts, reporting progress after each step
+    fn solve_with(&self, input: &str, parts: &[u8], progress: &mut dyn FnMut(Progress));
+    /// parse input and solve selected parts
+    fn solve(&self, input: &str, parts: &[u8]) -> Solved {
+        let mut solved = Solved {
+            parse_time: Duration::ZERO,
+            answers: Ok(Vec::with_capacity(parts.len())),
+        };
+        self.solve_with(input, parts, &mut |p| match p {
+            Progress::Parsed(parse_time) => solved.parse_time = parse_time,
+            Progress::ParseFailed(parse_time, err) => {
+                solved.parse_time = parse_time;
+                solved.answers = Err(err);
+            }
+            Progress::Solved(answer) => {
+                if let Ok(answers) = solved.answers.as_mut() {
+                    answers.push(answer);
+                }
+            }
+        });
+        solved
+    }
+    /// parse input and solve selected parts iterations times each
+    fn bench(&self, input: &str, parts: &[u8], iterations: usize) -> Result<Timings>;
+    /// default size of generated inputs and its meaning
+    fn gen_size(&self) -> (usize, &'static str);
+    /// random but valid puzzle input of given size
+    fn generate(&self, rng: &mut InputRng, size: usize) -> Result<String>;
+    /// parse input and render grid of day; None, if day has no grid
+    fn render(&self, input: &str) -> Result<Option<Image>>;
+    /// parse input and hand over simulation of day to run; false, if day has no simulation
+    fn simulate(
+        &self,
+        input: &str,
+        run: &mut dyn FnMut(&mut dyn Simulation) -> Result<()>,
+    ) -> Result<bool>;
+    /// split selected parts of day into parts to solve and slow parts to skip
+    fn select_parts(&self, parts: Parts, include_slow: bool) -> (Vec<u8>, Vec<u8>) {
+        (1..=self.parts())
+            .filter(|p| parts.contains(*p))
+            .partition(|p| include_slow || self.tier(*p) == Tier::Fast)
+    }
+}
+
+impl<S: Solution + Sync> Solver for S {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+    fn parts(&self) -> u8 {
+        S::PARTS
+    }
+    fn tier(&self, part: u8) -> Tier {
+        if S::SLOW_PARTS.contains(&part) {
+            Tier::Slow
+        } else {
+            Tier::Fast
+        }
+    }
+    fn solve_with(&self, input: &str, parts: &[u8], progress: &mut dyn FnMut(Progress)) {
+        let _day = info_span!("day", day = S::DAY).entered();
+        let start = Instant::now();
+        let parsed = S::parse(input).map_err(|err| parse::locate(err, S::DAY, input));
+        let parse_time = start.elapsed();
+        let parsed = match parsed {
+            Ok(parsed) => parsed,
+            Err(err) => {
+                warn!(?parse_time, "parsing failed: {:#}", err);
+                return progress(Progress::ParseFailed(parse_time, err));
+            }
+        };
+        info!(?parse_time, "parsed input");
+        progress(Progress::Parsed(parse_time));
+        for &part in parts {
+            let _part = info_span!("part", part).entered();
+            let start = Instant::now();
+            let value = match part {
+                1 => S::part_1(&parsed).map(|a| a.to_string()),
+                2 if S::PARTS == 2 => S::part_2(&parsed).map(|a| a.to_string()),
+                _ => continue,
+            };
+            let solve_time = start.elapsed();
+            // some days parse parts of input lazily while solving
+            let value = value.map_err(|err| parse::locate(err, S::DAY, input));
+            match &value {
+                Ok(answer) => info!(?solve_time, answer, "solved"),
+                Err(err) => warn!(?solve_time, "solving failed: {:#}", err),
+            }
+            progress(Progress::Solved(Answer {
+                part,
+                value,
+                solve_time,
+            }));
+        }
+    }
+    fn bench(&self, input: &str, parts: &[u8], iterations: usize) -> Result<Timings> {
+        let mut parse = Vec::with_capacity(iterations);
+        for _ in 0..iterations {
+            let start = Instant::now();
+            let parsed = S::parse(input).map_err(|err| parse::locate(err, S::DAY, input))?;
+            parse.push(start.elapsed());
+            drop(parsed);
+        }
+        let input = S::parse(input).map_err(|err| parse::locate(err, S::DAY, input))?;
+        let mut timings = Timings {
+            parse,
+            parts: Vec::with_capacity(parts.len()),
+        };
+        for &part in parts
+            .iter()
+            .filter(|p| **p == 1 || (**p == 2 && S::PARTS == 2))
+        {
+            let mut times = Vec::with_capacity(iterations);
+            for _ in 0..iterations {
+                let start = Instant::now();
+                if part == 1 {
+                    S::part_1(&input)?;
+                } else {
+                    S::part_2(&input)?;
+                }
+                times.push(start.elapsed());
+            }
+            timings.parts.push((part, times));
+        }
+        Ok(timings)
+    }
+    fn gen_size(&self) -> (usize, &'static str) {
+        S::GEN_SIZE
+    }
+    fn generate(&self, rng: &mut InputRng, size: usize) -> Result<String> {
+        S::generate(rng, size)
+    }
+    fn render(&self, input: &str) -> Result<Option<Image>> {
+        let input = S::parse(input).map_err(|err| parse::locate(err, S::DAY, input))?;
+        S::render(&input)
+    }
+    fn simulate(
+        &self,
+        input: &str,
+        run: &mut dyn FnMut(&mut dyn Simulation) -> Result<()>,
+    ) -> Result<bool> {
+        let input = S::parse(input).map_err(|err| parse::locate(err, S::DAY, input))?;
+        let Some(mut simulation) = S::simulation(&input)? else {
+            return Ok(false);
+        };
+        run(simulation.as_mut())?;
+        Ok(true)
+    }
+}
@@ -0,0 +1,200 @@
+//!step.rs
+
+use crate::cli::StepArgs;
+use crate::input;
+use crate::years;
+use anyhow::{anyhow, Result};
+use std::io::{BufRead, Write};
+use std::time::Duration;
+
+/// simulation of a day, which is advanced step by step
+pub trait Simulation {
+    /// advance simulation by one step; false, if simulation is finished
+    fn step(&mut self) -> Result<bool>;
+    /// grid or module states of current step
+    fn frame(&self) -> String;
+    /// counters of current step, e.g. `("load", 136)`
+    fn counters(&self) -> Vec<(&'static str, u64)>;
+}
+
+/// step through simulation of a day in terminal
+pub fn run_step(args: &StepArgs) -> Result<()> {
+    let day = years::day_of_year(args.year, args.day)?;
+    let input_dir = input::year_input_dir(&input::input_dir(), args.year);
+    let input = input::load_input(args.day, &input_dir, args.input.as_deref())?;
+    let speed = args.speed.map(|s| Duration::from_secs_f64(1.0 / s));
+    let has_simulation = day.simulate(&input, &mut |simulation| {
+        step_through(
+            simulation,
+            speed,
+            &mut std::io::stdin().lock(),
+            &mut std::io::stdout().lock(),
+        )
+        .map(|_| ())
+    })?;
+    if !has_simulation {
+        return Err(anyhow!(
+            "day {:02} has no simulation to step through",
+            args.day
+        ));
+    }
+    Ok(())
+}
+
+/// redraws simulation after each step. Without speed each line of commands advances simulation:
+/// an empty line one step, a number that many steps, `r` until simulation is finished and `q`
+/// quits. With speed simulation runs until finished, waiting speed between steps.
+/// Returns number of steps.
+pub fn step_through(
+    simulation: &mut dyn Simulation,
+    speed: Option<Duration>,
+    commands: &mut impl BufRead,
+    out: &mut impl Write,
+) -> Result<u64> {
+    let mut steps = 0;
+    let mut finished = false;
+    // steps to advance without waiting for commands
+    let mut pending: u64 = 0;
+    loop {
+        if pending == 0 || finished {
+            draw(simulation, steps, finished, out)?;
+        }
+        if finished {
+            return Ok(steps);
+        }
+        if pending == 0 {
+            match speed {
+                Some(speed) => {
+                    std::thread::sleep(speed);
+                    pending = 1;
+                }
+                None => {
+                    write!(out, "[enter] step, [N] N steps, [r] run, [q] quit: ")?;
+                    out.flush()?;
+                    let mut command = String::new();
+                    if commands.read_line(&mut command)? == 0 {
+                        return Ok(steps);
+                    }
+                    pending = match command.trim() {
+                        "" => 1,
+                        "q" => return Ok(steps),
+                        "r" => u64::MAX,
+                        n => match n.parse::<u64>() {
+                            Ok(n) if n > 0 => n,
+                            _ => continue,
+                        },
+                    };
+                }
+            }
+        }
+        finished = !simulation.step()?;
+        if !finished {
+            steps += 1;
+        }
+        pending -= 1;
+    }
+}
+
+fn draw(
+    simulation: &dyn Simulation,
+    steps: u64,
+    finished: bool,
+    out: &mut impl Write,
+) -> Result<()> {
+    // clear screen and move cursor to top left corner
+    write!(out, "\x1b[2J\x1b[H{}", simulation.frame())?;
+    let counters: Vec<String> = [("step", steps)]
+        .into_iter()
+        .chain(simulation.counters())
+        .map(|(name, value)| format!("{}: {}", name, value))
+        .collect();
+    writeln!(out, "{}", counters.join(" | "))?;
+    if finished {
+        writeln!(out, "simulation finished")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::cli::{Cli, Command};
+    use clap::Parser;
+    use std::io::Cursor;
+
+    /// counts up to limit
+    struct Counter {
+        value: u64,
+        limit: u64,
+    }
+
+    impl Simulation for Counter {
+        fn step(&mut self) -> Result<bool> {
+            if self.value == self.limit {
+                return Ok(false);
+            }
+            self.value += 1;
+            Ok(true)
+        }
+        fn frame(&self) -> String {
+            format!("{}\n", "#".repeat(self.value as usize))
+        }
+        fn counters(&self) -> Vec<(&'static str, u64)> {
+            vec![("value", self.value)]
+        }
+    }
+
+    #[test]
+    fn test_step_through() {
+        let mut counter = Counter {
+            value: 0,
+            limit: 10,
+        };
+        let mut out = Vec::new();
+        let steps = step_through(
+            &mut counter,
+            None,
+            &mut Cursor::new("\n3\nx\nq\n"),
+            &mut out,
+        )
+        .unwrap();
+        assert_eq!(steps, 4);
+        assert_eq!(counter.value, 4);
+        let out = String::from_utf8(out).unwrap();
+        // invalid command redraws current step
+        assert_eq!(out.matches("\x1b[2J").count(), 4);
+        assert!(out.contains("####\nstep: 4 | value: 4\n"));
+
+        let mut out = Vec::new();
+        let steps = step_through(&mut counter, None, &mut Cursor::new("r\n"), &mut out).unwrap();
+        assert_eq!(steps, 6);
+        assert!(String::from_utf8(out)
+            .unwrap()
+            .ends_with("step: 6 | value: 10\nsimulation finished\n"));
+
+        let mut counter = Counter { value: 0, limit: 3 };
+        let steps = step_through(
+            &mut counter,
+            Some(Duration::ZERO),
+            &mut Cursor::new(""),
+            &mut Vec::new(),
+        )
+        .unwrap();
+        assert_eq!(steps, 3);
+    }
+
+    #[test]
+    fn test_step_args() {
+        let cli = Cli::parse_from(["aoc", "step", "20", "--speed", "2.5"]);
+        match cli.command {
+            Some(Command::Step(step)) => {
+                assert_eq!(step.day, 20);
+                assert_eq!(step.speed, Some(2.5));
+                assert_eq!(step.input, None);
+            }
+            _ => panic!("expected step command"),
+        }
+        assert!(Cli::try_parse_from(["aoc", "step", "20", "--speed", "0"]).is_err());
+    }
+}
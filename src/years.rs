@@ -0,0 +1,50 @@
+//!years.rs
+
+use crate::solution::Solver;
+use anyhow::{anyhow, Result};
+
+/// year of days in module `days`; days of other years are in modules `year_YYYY`
+pub const DEFAULT_YEAR: u16 = 2023;
+
+/// registry of all years with their days in order of advent calendar; `new` adds years
+pub static YEARS: &[(u16, &[&dyn Solver])] = &[
+    (2023, &crate::days::DAYS),
+    // `new <DAY> --year <YEAR>` inserts further years in order of years
+];
+
+/// registered days of year
+pub fn days_of_year(year: u16) -> Result<&'static [&'static dyn Solver]> {
+    YEARS
+        .iter()
+        .find(|(y, _)| *y == year)
+        .map(|(_, days)| *days)
+        .ok_or_else(|| {
+            anyhow!(
+                "no days of year {} registered; add a day with `new <DAY> --year {}`",
+                year,
+                year
+            )
+        })
+}
+
+/// registered day of year
+pub fn day_of_year(year: u16, day: u8) -> Result<&'static dyn Solver> {
+    days_of_year(year)?
+        .iter()
+        .copied()
+        .find(|d| d.day() == day)
+        .ok_or_else(|| anyhow!("day {:02} of year {} is not registered", day, year))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_years() {
+        assert_eq!(days_of_year(DEFAULT_YEAR).unwrap().len(), 25);
+        assert_eq!(day_of_year(DEFAULT_YEAR, 7).unwrap().day(), 7);
+        assert!(days_of_year(2015).is_err());
+    }
+}
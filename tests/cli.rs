@@ -0,0 +1,103 @@
+//!cli.rs
+
+use advent_of_code_2023::answers::fingerprint;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+const EXAMPLE_DAY_01: &str = "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\n";
+
+/// input directory with example of day 01 and given expected answer of part 1
+fn input_dir(name: &str, expected: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("aoc_cli_{}_{}", name, std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("day_01.txt"), EXAMPLE_DAY_01).unwrap();
+    std::fs::write(
+        dir.join("answers.txt"),
+        format!("1 1 {} {}\n", fingerprint(EXAMPLE_DAY_01), expected),
+    )
+    .unwrap();
+    dir
+}
+
+fn run(input_dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_advent-of-code-2023"))
+        .env("AOC_INPUT_DIR", input_dir)
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_verify_exit_status() {
+    let dir = input_dir("verify", "142");
+    let matching = run(&dir, &["verify", "1", "-p", "1", "--no-cache"]);
+    std::fs::write(
+        dir.join("answers.txt"),
+        format!("1 1 {} 143\n", fingerprint(EXAMPLE_DAY_01)),
+    )
+    .unwrap();
+    let mismatching = run(&dir, &["verify", "1", "-p", "1", "--no-cache"]);
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert!(matching.status.success());
+    assert_eq!(mismatching.status.code(), Some(1));
+    let stderr = String::from_utf8(mismatching.stderr).unwrap();
+    assert!(stderr.contains("1 answer(s) do not match expected answers"));
+    let stdout = String::from_utf8(mismatching.stdout).unwrap();
+    assert!(stdout.contains("result day 01 part 1: 142 -> mismatch (expected 143)"));
+    assert!(!stdout.contains("Error occured"));
+}
+
+#[test]
+fn test_report_stays_machine_readable() {
+    // day 02 fails, because its input is missing
+    let dir = input_dir("report", "142");
+    let json = run(&dir, &["1-2", "--format", "json", "--no-cache"]);
+    let csv = run(&dir, &["1-2", "--format", "csv", "--no-cache"]);
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(json.status.code(), Some(1));
+    let report: serde_json::Value = serde_json::from_slice(&json.stdout).unwrap();
+    assert_eq!(report["parts"][0]["answer"], "142");
+    assert_eq!(report["parts"][2]["status"], "error");
+    assert!(String::from_utf8(json.stderr)
+        .unwrap()
+        .contains("2 part(s) failed"));
+
+    assert_eq!(csv.status.code(), Some(1));
+    let csv = String::from_utf8(csv.stdout).unwrap();
+    let columns = csv.lines().next().unwrap().split(',').count();
+    assert_eq!(csv.lines().count(), 5);
+    assert!(csv
+        .lines()
+        .all(|line| line.matches(',').count() + 1 == columns || line.contains('"')));
+}
+
+#[test]
+fn test_bench_regression_and_baseline() {
+    let dir = input_dir("bench", "142");
+    let baseline = dir.join("baseline.json");
+    // baseline of day 01 is too fast to be reached; baseline of day 02 must be kept
+    let step = |day: u8, step: &str| serde_json::json!({"day": day, "step": step, "min_ms": 1e-9, "median_ms": 1e-9, "max_ms": 1e-9});
+    let results = serde_json::json!({"results": [step(1, "parse"), step(2, "parse")]});
+    std::fs::write(&baseline, results.to_string()).unwrap();
+    let baseline_arg = baseline.to_str().unwrap();
+    let regression = run(&dir, &["bench", "1", "-n", "1", "-b", baseline_arg]);
+    let saved = run(&dir, &["bench", "1", "-n", "1", "-b", baseline_arg, "-s"]);
+    let saved_baseline: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&baseline).unwrap()).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(regression.status.code(), Some(1));
+    assert!(String::from_utf8(regression.stderr)
+        .unwrap()
+        .contains("slower than baseline"));
+    assert_eq!(saved.status.code(), Some(1));
+    let days: Vec<u64> = saved_baseline["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| r["day"].as_u64().unwrap())
+        .collect();
+    assert_eq!(days, vec![1, 1, 1, 2]);
+}